@@ -0,0 +1,70 @@
+//! Benchmarks of the decoders' hot path (i.e. `update()`), as called from within interrupt handlers.
+//!
+//! Run via `cargo bench -p quadrature-decoder`, reporting the mean time per update.
+
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use quadrature_decoder::{FullStep, HalfStep, IncrementalDecoder, QuadStep};
+
+const SAMPLES: usize = 4096;
+const ITERATIONS: usize = 2_000;
+
+/// Returns a sequence of readings, moving back and forth over multiple cycles,
+/// with every `noise`-th reading (if any) replaced by an invalid jump.
+fn samples(noise: Option<usize>) -> Vec<(bool, bool)> {
+    const CYCLE: [(bool, bool); 4] = [(false, true), (false, false), (true, false), (true, true)];
+
+    (0..SAMPLES)
+        .map(|index| {
+            let phase = match (index / 64) % 2 {
+                0 => index % 4,
+                _ => 3 - (index % 4),
+            };
+            match noise {
+                Some(noise) if index % noise == 0 => {
+                    let (a, b) = CYCLE[phase];
+                    (!a, !b)
+                }
+                _ => CYCLE[phase],
+            }
+        })
+        .collect()
+}
+
+fn bench<Mode>(name: &str, samples: &[(bool, bool)])
+where
+    Mode: quadrature_decoder::StepMode,
+    IncrementalDecoder<Mode>: Default,
+{
+    let mut decoder: IncrementalDecoder<Mode> = Default::default();
+    let mut elapsed = Duration::ZERO;
+
+    for _ in 0..ITERATIONS {
+        let start = Instant::now();
+        for &(a, b) in samples {
+            let _ = black_box(decoder.update(black_box(a), black_box(b)));
+        }
+        elapsed += start.elapsed();
+    }
+
+    black_box(decoder.counter());
+
+    let updates = (ITERATIONS * samples.len()) as f64;
+    let nanos = elapsed.as_nanos() as f64 / updates;
+    println!("{name:<24} {nanos:>8.2} ns/update");
+}
+
+fn main() {
+    let clean = samples(None);
+    let noisy = samples(Some(7));
+
+    bench::<FullStep>("full-step (clean)", &clean);
+    bench::<HalfStep>("half-step (clean)", &clean);
+    bench::<QuadStep>("quad-step (clean)", &clean);
+    bench::<FullStep>("full-step (noisy)", &noisy);
+    bench::<HalfStep>("half-step (noisy)", &noisy);
+    bench::<QuadStep>("quad-step (noisy)", &noisy);
+}
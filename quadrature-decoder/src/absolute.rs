@@ -0,0 +1,161 @@
+//! Decoding of absolute encoder positions (e.g. of Gray-coded, or SSI encoders).
+
+/// Converts the given Gray-coded `gray` word into its binary value.
+pub const fn gray_to_binary(gray: u32) -> u32 {
+    let mut binary = gray;
+    let mut shift = 1;
+
+    while shift < u32::BITS {
+        binary ^= binary >> shift;
+        shift <<= 1;
+    }
+
+    binary
+}
+
+/// Converts the given binary value into its Gray-coded word.
+pub const fn binary_to_gray(binary: u32) -> u32 {
+    binary ^ (binary >> 1)
+}
+
+/// The coding of an absolute encoder's position words.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Coding {
+    /// Plain binary words.
+    Binary,
+    /// Gray-coded words (i.e. only a single bit changing between adjacent positions).
+    #[default]
+    Gray,
+}
+
+/// A decoder of an absolute encoder's position words of a fixed number of bits,
+/// with support for zero offsets and reversal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AbsoluteDecoder {
+    bits: u8,
+    coding: Coding,
+    offset: u32,
+    is_reversed: bool,
+}
+
+impl AbsoluteDecoder {
+    /// Creates a decoder for Gray-coded words of the given number of `bits`,
+    /// or `None` if the number of bits is not within `1..=31`.
+    pub fn new(bits: u8) -> Option<Self> {
+        if !(1..u32::BITS as u8).contains(&bits) {
+            return None;
+        }
+
+        Some(Self {
+            bits,
+            coding: Coding::default(),
+            offset: 0,
+            is_reversed: false,
+        })
+    }
+
+    /// Sets the decoder's coding, returning the modified decoder.
+    pub fn with_coding(mut self, coding: Coding) -> Self {
+        self.coding = coding;
+        self
+    }
+
+    /// Sets the decoder's zero offset (i.e. the raw position decoded as `0`),
+    /// returning the modified decoder.
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.offset = offset % self.resolution();
+        self
+    }
+
+    /// Sets the decoder's reversed mode, making it decode positions in the opposite direction.
+    pub fn reversed(mut self) -> Self {
+        self.is_reversed = true;
+        self
+    }
+
+    /// Returns the decoder's number of bits per word.
+    pub fn bits(&self) -> u8 {
+        self.bits
+    }
+
+    /// Returns the decoder's coding.
+    pub fn coding(&self) -> Coding {
+        self.coding
+    }
+
+    /// Returns the decoder's zero offset.
+    pub fn offset(&self) -> u32 {
+        self.offset
+    }
+
+    /// Returns `true` if the decoder is reversed, otherwise `false`.
+    pub fn is_reversed(&self) -> bool {
+        self.is_reversed
+    }
+
+    /// Returns the decoder's number of positions (i.e. `2^bits`).
+    pub fn resolution(&self) -> u32 {
+        1 << self.bits
+    }
+
+    /// Decodes the given `word` (ignoring any bits beyond the decoder's number of bits)
+    /// into a position within `[0, resolution)`, subject to the decoder's offset and reversal.
+    pub fn decode(&self, word: u32) -> u32 {
+        let resolution = self.resolution();
+        let word = word & (resolution - 1);
+
+        let raw = match self.coding {
+            Coding::Binary => word,
+            Coding::Gray => gray_to_binary(word),
+        };
+
+        let position = raw.wrapping_sub(self.offset) & (resolution - 1);
+
+        match self.is_reversed {
+            true => resolution.wrapping_sub(position) & (resolution - 1),
+            false => position,
+        }
+    }
+
+    /// Decodes the given `bits` (most significant bit first, e.g. as shifted out by an SSI encoder)
+    /// like [`decode()`](Self::decode), taking only the decoder's number of bits into account.
+    pub fn decode_bits(&self, bits: impl IntoIterator<Item = bool>) -> u32 {
+        let word = bits
+            .into_iter()
+            .take(self.bits as usize)
+            .fold(0, |word, bit| (word << 1) | bit as u32);
+
+        self.decode(word)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gray() {
+        for binary in 0..1024 {
+            let gray = binary_to_gray(binary);
+            assert_eq!(gray_to_binary(gray), binary);
+            assert_eq!((gray ^ binary_to_gray(binary + 1)).count_ones(), 1);
+        }
+    }
+
+    #[test]
+    fn decode() {
+        let decoder = AbsoluteDecoder::new(4).unwrap().with_offset(3);
+
+        assert_eq!(decoder.resolution(), 16);
+        assert_eq!(decoder.decode(binary_to_gray(3)), 0);
+        assert_eq!(decoder.decode(binary_to_gray(2)), 15);
+
+        let decoder = decoder.reversed();
+        assert_eq!(decoder.decode(binary_to_gray(4)), 15);
+
+        let decoder = AbsoluteDecoder::new(3).unwrap().with_coding(Coding::Binary);
+        assert_eq!(decoder.decode_bits([true, false, true, true]), 0b101);
+
+        assert!(AbsoluteDecoder::new(32).is_none());
+    }
+}
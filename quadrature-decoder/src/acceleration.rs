@@ -0,0 +1,157 @@
+//! Input acceleration (e.g. for UI knobs).
+
+use crate::Change;
+
+/// A profile for accelerating movements based on the time between them,
+/// e.g. making fast spins of a UI knob move farther per detent.
+///
+/// Movements at least `slow` ticks apart are not accelerated,
+/// while movements at most `fast` ticks apart are scaled by the maximum multiplier,
+/// with the multiplier increasing linearly in between.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct AccelProfile {
+    slow: u64,
+    fast: u64,
+    max_multiplier: u16,
+}
+
+impl AccelProfile {
+    /// Creates a profile accelerating movements less than `slow` ticks apart,
+    /// up to `max_multiplier` for movements at most `fast` ticks apart.
+    ///
+    /// Multipliers below `1` are clamped to `1`.
+    pub fn new(slow: u64, fast: u64, max_multiplier: u16) -> Self {
+        Self {
+            slow,
+            fast,
+            max_multiplier: max_multiplier.max(1),
+        }
+    }
+
+    /// Returns the profile's threshold (in ticks) below which movements get accelerated.
+    pub fn slow(&self) -> u64 {
+        self.slow
+    }
+
+    /// Returns the profile's threshold (in ticks) below which movements get the maximum multiplier.
+    pub fn fast(&self) -> u64 {
+        self.fast
+    }
+
+    /// Returns the profile's maximum multiplier.
+    pub fn max_multiplier(&self) -> u16 {
+        self.max_multiplier
+    }
+
+    /// Returns the multiplier for movements `interval` ticks apart.
+    pub fn multiplier(&self, interval: u64) -> u16 {
+        if interval >= self.slow {
+            1
+        } else if interval <= self.fast {
+            self.max_multiplier
+        } else {
+            let gain = u64::from(self.max_multiplier - 1) * (self.slow - interval);
+            1 + (gain / (self.slow - self.fast)) as u16
+        }
+    }
+}
+
+/// An accelerator, scaling movements according to an [`AccelProfile`].
+///
+/// Movements are only accelerated if following a movement in the same direction,
+/// so that reversing the direction always starts out with a single step.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Accelerator {
+    profile: AccelProfile,
+    last_change: Option<(u64, Change)>,
+    delta: i32,
+}
+
+impl Accelerator {
+    /// Creates an accelerator for the given profile.
+    pub fn new(profile: AccelProfile) -> Self {
+        Self {
+            profile,
+            last_change: None,
+            delta: 0,
+        }
+    }
+
+    /// Returns the accelerator's profile.
+    pub fn profile(&self) -> AccelProfile {
+        self.profile
+    }
+
+    /// Sets the accelerator's profile.
+    pub fn set_profile(&mut self, profile: AccelProfile) {
+        self.profile = profile;
+    }
+
+    /// Updates the accelerator with the change (if any) detected at the given `timestamp`,
+    /// returning the scaled delta (i.e. `0` if there was no change).
+    pub fn update(&mut self, timestamp: u64, change: Option<Change>) -> i32 {
+        let Some(change) = change else {
+            self.delta = 0;
+            return self.delta;
+        };
+
+        let multiplier = match self.last_change {
+            Some((last_timestamp, last_change)) if last_change == change => self
+                .profile
+                .multiplier(timestamp.wrapping_sub(last_timestamp)),
+            _ => 1,
+        };
+
+        self.last_change = Some((timestamp, change));
+        self.delta = i32::from(change as i8) * i32::from(multiplier);
+
+        self.delta
+    }
+
+    /// Returns the scaled delta of the most recent update.
+    pub fn delta(&self) -> i32 {
+        self.delta
+    }
+
+    /// Resets the accelerator to its initial state.
+    pub fn reset(&mut self) {
+        self.last_change = None;
+        self.delta = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn multiplier() {
+        let profile = AccelProfile::new(100, 20, 9);
+
+        assert_eq!(profile.multiplier(200), 1);
+        assert_eq!(profile.multiplier(100), 1);
+        assert_eq!(profile.multiplier(60), 5);
+        assert_eq!(profile.multiplier(20), 9);
+        assert_eq!(profile.multiplier(0), 9);
+
+        assert_eq!(AccelProfile::new(100, 20, 0).multiplier(0), 1);
+    }
+
+    #[test]
+    fn update() {
+        let mut accelerator = Accelerator::new(AccelProfile::new(100, 20, 9));
+
+        assert_eq!(accelerator.update(0, Some(Change::Positive)), 1);
+        assert_eq!(accelerator.update(10, Some(Change::Positive)), 9);
+        assert_eq!(accelerator.update(15, None), 0);
+        assert_eq!(accelerator.update(70, Some(Change::Positive)), 5);
+
+        // Reversing the direction starts out with a single step:
+        assert_eq!(accelerator.update(75, Some(Change::Negative)), -1);
+        assert_eq!(accelerator.update(80, Some(Change::Negative)), -9);
+        assert_eq!(accelerator.delta(), -9);
+
+        accelerator.reset();
+        assert_eq!(accelerator.update(85, Some(Change::Negative)), -1);
+    }
+}
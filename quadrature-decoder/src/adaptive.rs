@@ -0,0 +1,120 @@
+//! Speed-dependent resolution switching.
+
+use crate::Change;
+
+/// A post-processing stage that adapts the reported resolution to the speed of movement.
+///
+/// At low speed every detent is reported individually (i.e. as a delta of `±1`),
+/// keeping interfaces responsive, while at high speed the detents are coalesced into
+/// multi-detent deltas, reported at most once per interval, to avoid flooding the consumer.
+///
+/// Timestamps are provided as monotonic ticks of arbitrary resolution.
+#[derive(Clone, Debug)]
+pub struct AdaptiveResolution {
+    pulses_per_detent: u32,
+    fast_period: u64,
+    interval: u64,
+    pulses: i32,
+    last_change: Option<u64>,
+    last_report: Option<u64>,
+    is_fast: bool,
+}
+
+impl AdaptiveResolution {
+    /// Creates an adaptive resolution stage.
+    ///
+    /// - `pulses_per_detent`: the number of pulses per detent (relative to the decoder's step-mode).
+    /// - `fast_period`: the period (in ticks) between consecutive changes below which movement is considered fast.
+    /// - `interval`: the minimum interval (in ticks) between reports while moving fast.
+    pub fn new(pulses_per_detent: u32, fast_period: u64, interval: u64) -> Self {
+        Self {
+            pulses_per_detent: pulses_per_detent.max(1),
+            fast_period,
+            interval,
+            pulses: 0,
+            last_change: None,
+            last_report: None,
+            is_fast: false,
+        }
+    }
+
+    /// Returns `true` if the movement is currently considered fast, otherwise `false`.
+    pub fn is_fast(&self) -> bool {
+        self.is_fast
+    }
+
+    /// Updates the stage with the change (if any) detected at the given `timestamp`,
+    /// returning the number of detents moved, if any are to be reported.
+    ///
+    /// Should be called regularly, even without any changes,
+    /// to flush detents coalesced while moving fast.
+    pub fn update(&mut self, timestamp: u64, change: Option<Change>) -> Option<i32> {
+        if let Some(change) = change {
+            self.is_fast = self
+                .last_change
+                .is_some_and(|last| timestamp.wrapping_sub(last) < self.fast_period);
+            self.last_change = Some(timestamp);
+            self.pulses += change as i32;
+        }
+
+        let pulses_per_detent = self.pulses_per_detent as i32;
+        let detents = self.pulses / pulses_per_detent;
+
+        if detents == 0 {
+            return None;
+        }
+
+        let is_due = self
+            .last_report
+            .map_or(true, |last| timestamp.wrapping_sub(last) >= self.interval);
+
+        if self.is_fast && !is_due {
+            return None;
+        }
+
+        self.pulses -= detents * pulses_per_detent;
+        self.last_report = Some(timestamp);
+
+        Some(detents)
+    }
+
+    /// Resets the stage to its initial state.
+    pub fn reset(&mut self) {
+        self.pulses = 0;
+        self.last_change = None;
+        self.last_report = None;
+        self.is_fast = false;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow() {
+        let mut stage = AdaptiveResolution::new(2, 10, 100);
+
+        assert_eq!(stage.update(0, Some(Change::Positive)), None);
+        assert_eq!(stage.update(20, Some(Change::Positive)), Some(1));
+        assert_eq!(stage.update(40, Some(Change::Negative)), None);
+        assert_eq!(stage.update(60, Some(Change::Negative)), Some(-1));
+        assert!(!stage.is_fast());
+    }
+
+    #[test]
+    fn fast() {
+        let mut stage = AdaptiveResolution::new(1, 10, 100);
+
+        assert_eq!(stage.update(0, Some(Change::Positive)), Some(1));
+
+        for timestamp in 1..=5 {
+            assert_eq!(stage.update(timestamp, Some(Change::Positive)), None);
+        }
+        assert!(stage.is_fast());
+
+        assert_eq!(stage.update(50, None), None);
+        assert_eq!(stage.update(100, None), Some(5));
+        assert_eq!(stage.update(200, None), None);
+    }
+}
@@ -0,0 +1,206 @@
+//! Batch decoding of buffered samples (e.g. captured via DMA).
+
+use num_traits::{One, SaturatingAdd, Zero};
+
+use crate::{
+    Change, DynIncrementalDecoder, Error, IncrementalDecoder, IndexedIncrementalDecoder, StepMode,
+};
+
+/// The summary of decoding a batch of samples, as returned by `update_batch()`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BatchReport<T = i32> {
+    /// The net movement across the batch (i.e. the sum of all detected changes).
+    pub delta: T,
+    /// The number of detected changes.
+    pub changes: usize,
+    /// The number of invalid transitions.
+    pub errors: usize,
+    /// The index (within the batch) and kind of the first invalid transition, if any.
+    pub first_error: Option<(usize, Error)>,
+}
+
+impl<T> Default for BatchReport<T>
+where
+    T: Zero,
+{
+    fn default() -> Self {
+        Self {
+            delta: Zero::zero(),
+            changes: 0,
+            errors: 0,
+            first_error: None,
+        }
+    }
+}
+
+impl<T> BatchReport<T>
+where
+    T: Copy + SaturatingAdd + From<i8>,
+{
+    /// Returns `true` if the batch contained no invalid transitions, otherwise `false`.
+    pub fn is_clean(&self) -> bool {
+        self.errors == 0
+    }
+
+    /// Records the result of decoding the sample at the given `index` within the batch.
+    pub(crate) fn record(&mut self, index: usize, result: Result<Option<Change>, Error>) {
+        match result {
+            Ok(None) => {}
+            Ok(Some(change)) => {
+                let delta: T = (change as i8).into();
+                self.delta = self.delta.saturating_add(&delta);
+                self.changes += 1;
+            }
+            Err(error) => {
+                self.first_error.get_or_insert((index, error));
+                self.errors += 1;
+            }
+        }
+    }
+}
+
+/// A decoder taking ownership of buffers of samples (e.g. filled via DMA),
+/// handing them back once decoded, as built on `update_batch()`.
+///
+/// Buffers are accepted as any type providing its samples as a slice,
+/// such as the `&'static mut [S; N]` buffers handed out by `embedded-dma` based HALs,
+/// allowing for zero-copy double-buffered pipelines, with one buffer being decoded
+/// while the other one is being filled.
+pub trait BufferDecoder<S> {
+    /// The type of the decoder's counter.
+    type Counter;
+
+    /// Decodes the samples of the given `buffer`, returning a summary of the net movement
+    /// and any invalid transitions, along with the buffer (e.g. for handing it back to the DMA).
+    fn decode_buffer<B>(&mut self, buffer: B) -> (BatchReport<Self::Counter>, B)
+    where
+        B: AsRef<[S]>;
+}
+
+impl<Mode, T> BufferDecoder<(bool, bool)> for IncrementalDecoder<Mode, T>
+where
+    Mode: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
+{
+    type Counter = T;
+
+    fn decode_buffer<B>(&mut self, buffer: B) -> (BatchReport<T>, B)
+    where
+        B: AsRef<[(bool, bool)]>,
+    {
+        let report = self.update_batch(buffer.as_ref());
+        (report, buffer)
+    }
+}
+
+impl<Mode, T> BufferDecoder<(bool, bool, bool)> for IndexedIncrementalDecoder<Mode, T>
+where
+    Mode: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
+{
+    type Counter = T;
+
+    fn decode_buffer<B>(&mut self, buffer: B) -> (BatchReport<T>, B)
+    where
+        B: AsRef<[(bool, bool, bool)]>,
+    {
+        let report = self.update_batch(buffer.as_ref());
+        (report, buffer)
+    }
+}
+
+impl<T> BufferDecoder<(bool, bool)> for DynIncrementalDecoder<T>
+where
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
+{
+    type Counter = T;
+
+    fn decode_buffer<B>(&mut self, buffer: B) -> (BatchReport<T>, B)
+    where
+        B: AsRef<[(bool, bool)]>,
+    {
+        let report = self.update_batch(buffer.as_ref());
+        (report, buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IncrementalDecoder, IndexedIncrementalDecoder, QuadStep};
+
+    use super::*;
+
+    #[test]
+    fn update_batch() {
+        let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+
+        let samples = [
+            (false, true),
+            (false, false),
+            (true, false),
+            (true, true),
+            (false, false), // Invalid transition
+            (false, true),
+            (true, true),
+        ];
+
+        let report = decoder.update_batch(samples);
+
+        assert_eq!(
+            report,
+            BatchReport {
+                delta: 2,
+                changes: 6,
+                errors: 1,
+                first_error: Some((4, Error::E11_00)),
+            }
+        );
+        assert!(!report.is_clean());
+        assert_eq!(decoder.counter(), 2);
+    }
+
+    #[test]
+    fn decode_buffer() {
+        const CYCLE: [(bool, bool); 4] =
+            [(false, true), (false, false), (true, false), (true, true)];
+
+        let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+
+        // Double-buffering, with one buffer being decoded while the other one gets filled:
+        let mut buffers = [[(true, true); 4], [(true, true); 4]];
+        let (mut front, mut back) = {
+            let [front, back] = &mut buffers;
+            (front, back)
+        };
+
+        for _ in 0..3 {
+            front.copy_from_slice(&CYCLE);
+
+            let (report, buffer) = decoder.decode_buffer(front);
+            assert_eq!(report.delta, 4);
+            assert!(report.is_clean());
+
+            (front, back) = (back, buffer);
+        }
+
+        assert_eq!(decoder.counter(), 12);
+    }
+
+    #[test]
+    fn update_batch_indexed() {
+        let mut decoder: IndexedIncrementalDecoder<QuadStep> = Default::default();
+
+        let samples = [
+            (false, true, false),
+            (false, false, false),
+            (true, false, true), // Index resets the counter
+            (true, true, false),
+        ];
+
+        let report = decoder.update_batch(samples);
+
+        assert_eq!(report.delta, 4);
+        assert!(report.is_clean());
+        assert_eq!(decoder.counter(), 1);
+    }
+}
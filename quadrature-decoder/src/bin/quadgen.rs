@@ -0,0 +1,348 @@
+//! Generates quadrature waveforms (as CSV, or VCD) for a scripted motion profile,
+//! optionally with contact bounce, for producing test captures.
+
+use std::{
+    env,
+    io::{self, BufWriter, Write},
+    process::ExitCode,
+};
+
+use quadrature_decoder::{
+    simulator::{Bounce, Distribution, Sample, Simulator},
+    Change,
+};
+
+const USAGE: &str = "\
+Usage: quadgen [OPTIONS] <SEGMENT>...
+
+Generates quadrature waveforms for a scripted motion profile.
+
+Segments:
+  <+|-><STEPS>@<RATE>     Moves by STEPS quarter-cycles at RATE quarter-cycles per second (e.g. `+400@2000`)
+  wait@<SECONDS>          Holds still for SECONDS seconds (e.g. `wait@0.5`)
+
+Options:
+  --format <csv|vcd>      Output format [default: csv]
+  --cpr <CYCLES>          Cycles per revolution, enabling the index (i.e. `z`) channel
+  --bounce <MIN>-<MAX>    Contact bounces per transition, uniformly distributed within MIN..=MAX
+  --sample-rate <HZ>      Rate of the bounce model's samples [default: 1000000]
+  --seed <SEED>           Seed of the bounce model [default: 1]
+  --help                  Prints this help
+";
+
+/// A segment of a motion profile.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum Segment {
+    /// Moves by `steps` quarter-cycles at `rate` quarter-cycles per second.
+    Move { steps: i64, rate: f64 },
+    /// Holds still for `seconds` seconds.
+    Wait { seconds: f64 },
+}
+
+/// The output format.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Format {
+    /// Comma-separated values, with one row per edge.
+    Csv,
+    /// Value change dump, as understood by most logic analyzers.
+    Vcd,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct Options {
+    format: Format,
+    cycles_per_revolution: Option<u32>,
+    bounce: Option<(u32, u32)>,
+    sample_rate: u32,
+    seed: u32,
+    segments: Vec<Segment>,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            format: Format::Csv,
+            cycles_per_revolution: None,
+            bounce: None,
+            sample_rate: 1_000_000,
+            seed: 1,
+            segments: Vec::new(),
+        }
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(value: &str, what: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("invalid {what}: `{value}`"))
+}
+
+fn parse_segment(segment: &str) -> Result<Segment, String> {
+    let Some((head, tail)) = segment.split_once('@') else {
+        return Err(format!("invalid segment: `{segment}`"));
+    };
+
+    if head == "wait" {
+        let seconds: f64 = parse_number(tail, "duration")?;
+
+        if seconds.is_nan() || seconds < 0.0 {
+            return Err(format!("invalid duration: `{tail}`"));
+        }
+
+        return Ok(Segment::Wait { seconds });
+    }
+
+    if !head.starts_with(['+', '-']) {
+        return Err(format!("invalid segment: `{segment}`"));
+    }
+
+    let steps: i64 = parse_number(head, "steps")?;
+    let rate: f64 = parse_number(tail, "rate")?;
+
+    if rate.is_nan() || rate <= 0.0 {
+        return Err(format!("invalid rate: `{tail}`"));
+    }
+
+    Ok(Segment::Move { steps, rate })
+}
+
+fn parse_options(args: impl IntoIterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let mut value = |name: &str| args.next().ok_or(format!("missing value of `{name}`"));
+
+        match arg.as_str() {
+            "--format" => {
+                options.format = match value("--format")?.as_str() {
+                    "csv" => Format::Csv,
+                    "vcd" => Format::Vcd,
+                    format => return Err(format!("invalid format: `{format}`")),
+                }
+            }
+            "--cpr" => options.cycles_per_revolution = Some(parse_number(&value("--cpr")?, "CPR")?),
+            "--bounce" => {
+                let bounce = value("--bounce")?;
+                let (min, max) = bounce.split_once('-').unwrap_or((&bounce, &bounce));
+                options.bounce = Some((parse_number(min, "bounce")?, parse_number(max, "bounce")?));
+            }
+            "--sample-rate" => {
+                options.sample_rate = parse_number(&value("--sample-rate")?, "sample rate")?;
+
+                if options.sample_rate == 0 {
+                    return Err("invalid sample rate: `0`".into());
+                }
+            }
+            "--seed" => options.seed = parse_number(&value("--seed")?, "seed")?,
+            segment => options.segments.push(parse_segment(segment)?),
+        }
+    }
+
+    if options.segments.is_empty() {
+        return Err("missing segments".into());
+    }
+
+    Ok(options)
+}
+
+/// Returns the waveform's edges as `(nanoseconds, sample)` pairs,
+/// starting with the initial sample at time `0`.
+fn generate(options: &Options) -> Vec<(u64, Sample)> {
+    let mut simulator = Simulator::new();
+
+    if let Some(cycles_per_revolution) = options.cycles_per_revolution {
+        simulator = simulator.with_cycles_per_revolution(cycles_per_revolution);
+    }
+
+    if let Some((min, max)) = options.bounce {
+        let count = Distribution::Uniform { min, max };
+        let spacing = Distribution::Uniform { min: 1, max: 3 };
+        simulator = simulator.with_bounce(Bounce::new(count, spacing).with_seed(options.seed));
+    }
+
+    let sample_period = 1e9 / options.sample_rate as f64;
+
+    let mut edges = vec![(0, simulator.sample())];
+    let mut time = 0.0_f64;
+
+    for segment in &options.segments {
+        let (steps, rate) = match *segment {
+            Segment::Move { steps, rate } => (steps, rate),
+            Segment::Wait { seconds } => {
+                time += seconds * 1e9;
+                continue;
+            }
+        };
+
+        let change = match steps < 0 {
+            true => Change::Negative,
+            false => Change::Positive,
+        };
+
+        for _ in 0..steps.unsigned_abs() {
+            time += 1e9 / rate;
+
+            for (offset, sample) in simulator.transition(change).enumerate() {
+                let (last_time, last_sample) = *edges.last().unwrap();
+
+                if sample != last_sample {
+                    let time = (time + offset as f64 * sample_period) as u64;
+                    edges.push((time.max(last_time), sample));
+                }
+            }
+        }
+    }
+
+    edges
+}
+
+fn write_csv(out: &mut impl Write, edges: &[(u64, Sample)]) -> io::Result<()> {
+    writeln!(out, "time_ns,a,b,z")?;
+
+    for (time, sample) in edges {
+        let Sample { a, b, z } = *sample;
+        writeln!(out, "{time},{},{},{}", a as u8, b as u8, z as u8)?;
+    }
+
+    Ok(())
+}
+
+fn write_vcd(out: &mut impl Write, edges: &[(u64, Sample)]) -> io::Result<()> {
+    writeln!(out, "$timescale 1 ns $end")?;
+    writeln!(out, "$scope module encoder $end")?;
+    for channel in ["a", "b", "z"] {
+        writeln!(out, "$var wire 1 {channel} {channel} $end")?;
+    }
+    writeln!(out, "$upscope $end")?;
+    writeln!(out, "$enddefinitions $end")?;
+
+    let mut last: Option<Sample> = None;
+
+    for (time, sample) in edges {
+        writeln!(out, "#{time}")?;
+
+        for (channel, level, last_level) in [
+            ("a", sample.a, last.map(|last| last.a)),
+            ("b", sample.b, last.map(|last| last.b)),
+            ("z", sample.z, last.map(|last| last.z)),
+        ] {
+            if last_level != Some(level) {
+                writeln!(out, "{}{channel}", level as u8)?;
+            }
+        }
+
+        last = Some(*sample);
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    if args.is_empty() || args.iter().any(|arg| arg == "--help") {
+        print!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    let options = match parse_options(args) {
+        Ok(options) => options,
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let edges = generate(&options);
+
+    let mut out = BufWriter::new(io::stdout().lock());
+
+    let result = match options.format {
+        Format::Csv => write_csv(&mut out, &edges),
+        Format::Vcd => write_vcd(&mut out, &edges),
+    };
+
+    match result.and_then(|_| out.flush()) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(args: &[&str]) -> Result<Options, String> {
+        parse_options(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn segments() {
+        assert_eq!(
+            parse_segment("+400@2000"),
+            Ok(Segment::Move {
+                steps: 400,
+                rate: 2000.0
+            })
+        );
+        assert_eq!(
+            parse_segment("-8@100.5"),
+            Ok(Segment::Move {
+                steps: -8,
+                rate: 100.5
+            })
+        );
+        assert_eq!(
+            parse_segment("wait@0.5"),
+            Ok(Segment::Wait { seconds: 0.5 })
+        );
+        assert!(parse_segment("400@2000").is_err());
+        assert!(parse_segment("+400@0").is_err());
+        assert!(parse_segment("wait").is_err());
+    }
+
+    #[test]
+    fn csv() {
+        let options = options(&["+4@1000", "wait@0.001", "-1@1000"]).unwrap();
+
+        let mut out = Vec::new();
+        write_csv(&mut out, &generate(&options)).unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "time_ns,a,b,z\n\
+             0,1,1,0\n\
+             1000000,0,1,0\n\
+             2000000,0,0,0\n\
+             3000000,1,0,0\n\
+             4000000,1,1,0\n\
+             6000000,1,0,0\n"
+        );
+    }
+
+    #[test]
+    fn vcd() {
+        let options = options(&["--format", "vcd", "--cpr", "1", "+4@1000"]).unwrap();
+
+        let mut out = Vec::new();
+        write_vcd(&mut out, &generate(&options)).unwrap();
+
+        let out = String::from_utf8(out).unwrap();
+
+        assert!(out.starts_with("$timescale 1 ns $end\n"));
+        assert!(out.ends_with("#4000000\n1b\n1z\n"));
+    }
+
+    #[test]
+    fn bounce() {
+        let clean = generate(&options(&["+4@1000"]).unwrap());
+        let bouncy = generate(&options(&["--bounce", "2-3", "+4@1000"]).unwrap());
+
+        assert!(bouncy.len() > clean.len());
+        assert_eq!(bouncy.last().unwrap().1, clean.last().unwrap().1);
+    }
+}
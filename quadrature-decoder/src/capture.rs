@@ -0,0 +1,461 @@
+//! A compact binary capture format (`.qrec`) for timestamped pin states and decoded events,
+//! for building regression suites from field captures of misbehaving encoders.
+//!
+//! A capture consists of a header, followed by a sequence of records:
+//!
+//! ```plain
+//! header: b"QREC" | version: u8 | ticks per second: u32 (little-endian)
+//! record: tag: u8 | timestamp delta: varint (LEB128) | payload (tag-dependent)
+//! ```
+//!
+//! Timestamps are monotonic ticks of arbitrary resolution (as specified by the header),
+//! stored relative to the preceding record's timestamp (or zero for the first record).
+//!
+//! ```
+//! use quadrature_decoder::{
+//!     capture::{Entry, Reader, Record, Writer},
+//!     Change, IncrementalDecoder, QuadStep,
+//! };
+//!
+//! let mut buffer = [0_u8; 64];
+//! let mut writer = Writer::new(&mut buffer, 1_000_000).unwrap();
+//! writer.write(Entry::new(10, Record::pins(false, true, false))).unwrap();
+//! writer.write(Entry::new(25, Record::pins(false, false, false))).unwrap();
+//! let len = writer.len();
+//!
+//! let reader = Reader::new(&buffer[..len]).unwrap();
+//! assert_eq!(reader.ticks_per_second(), 1_000_000);
+//!
+//! let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+//! for entry in reader {
+//!     if let Record::Pins { a, b, .. } = entry.unwrap().record {
+//!         let _ = decoder.update(a, b);
+//!     }
+//! }
+//!
+//! assert_eq!(decoder.counter(), 2);
+//! ```
+
+use crate::{Change, Error};
+
+/// The magic bytes at the start of every capture.
+pub const MAGIC: [u8; 4] = *b"QREC";
+
+/// The version of the capture format written by [`Writer`].
+pub const VERSION: u8 = 1;
+
+/// The length of a capture's header in bytes.
+pub const HEADER_LEN: usize = MAGIC.len() + 1 + 4;
+
+/// The maximum length of a single encoded record in bytes.
+pub const MAX_RECORD_LEN: usize = 1 + MAX_VARINT_LEN + 1;
+
+const MAX_VARINT_LEN: usize = 10;
+
+const TAG_PINS: u8 = 0x00;
+const TAG_CHANGE: u8 = 0x10;
+const TAG_ERROR: u8 = 0x20;
+const TAG_KIND_MASK: u8 = 0xF0;
+
+/// An error encountered while writing or reading a capture.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CaptureError {
+    /// The writer's buffer has insufficient capacity for the record.
+    BufferFull,
+    /// The capture does not start with the expected magic bytes.
+    InvalidMagic,
+    /// The capture has been written with an unsupported format version.
+    UnsupportedVersion(u8),
+    /// The record's timestamp precedes the preceding record's timestamp.
+    NonMonotonicTimestamp,
+    /// The capture ends in the middle of its header or a record.
+    Truncated,
+    /// The capture contains a record with an invalid tag or payload.
+    InvalidRecord(u8),
+}
+
+/// A record of a capture.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Record {
+    /// A reading of the `a`, `b` and `z` pulse trains.
+    Pins {
+        /// The `a` pulse train.
+        a: bool,
+        /// The `b` pulse train.
+        b: bool,
+        /// The `z` (i.e. index) pulse train.
+        z: bool,
+    },
+    /// A change detected by a decoder.
+    Change(Change),
+    /// An error detected by a decoder.
+    Error(Error),
+}
+
+impl Record {
+    /// Creates a record of the given `a`, `b` and `z` pulse train readings.
+    pub const fn pins(a: bool, b: bool, z: bool) -> Self {
+        Self::Pins { a, b, z }
+    }
+
+    /// Creates a record of a decoder's update result, returning `None` if no change was detected.
+    pub fn from_result(result: Result<Option<Change>, Error>) -> Option<Self> {
+        match result {
+            Ok(change) => change.map(Record::Change),
+            Err(error) => Some(Record::Error(error)),
+        }
+    }
+}
+
+/// A timestamped record of a capture.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Entry {
+    /// The record's timestamp in ticks.
+    pub timestamp: u64,
+    /// The record.
+    pub record: Record,
+}
+
+impl Entry {
+    /// Creates an entry of `record` at the given `timestamp`.
+    pub const fn new(timestamp: u64, record: Record) -> Self {
+        Self { timestamp, record }
+    }
+}
+
+/// A writer, encoding a capture into a caller-provided buffer.
+#[derive(Debug)]
+pub struct Writer<'a> {
+    buffer: &'a mut [u8],
+    len: usize,
+    last_timestamp: u64,
+}
+
+impl<'a> Writer<'a> {
+    /// Creates a writer for timestamps with the given number of ticks per second,
+    /// writing the capture's header into `buffer`.
+    pub fn new(buffer: &'a mut [u8], ticks_per_second: u32) -> Result<Self, CaptureError> {
+        let header = buffer
+            .get_mut(..HEADER_LEN)
+            .ok_or(CaptureError::BufferFull)?;
+
+        header[..MAGIC.len()].copy_from_slice(&MAGIC);
+        header[MAGIC.len()] = VERSION;
+        header[(MAGIC.len() + 1)..].copy_from_slice(&ticks_per_second.to_le_bytes());
+
+        Ok(Self {
+            buffer,
+            len: HEADER_LEN,
+            last_timestamp: 0,
+        })
+    }
+
+    /// Appends the given entry to the capture.
+    ///
+    /// Fails without modifying the capture if the entry's timestamp
+    /// precedes the preceding entry's, or if the buffer is full.
+    pub fn write(&mut self, entry: Entry) -> Result<(), CaptureError> {
+        let delta = entry
+            .timestamp
+            .checked_sub(self.last_timestamp)
+            .ok_or(CaptureError::NonMonotonicTimestamp)?;
+
+        let mut record = [0_u8; MAX_RECORD_LEN];
+        let mut len = 0;
+
+        let (tag, payload) = match entry.record {
+            Record::Pins { a, b, z } => {
+                (TAG_PINS | a as u8 | (b as u8) << 1 | (z as u8) << 2, None)
+            }
+            Record::Change(Change::Positive) => (TAG_CHANGE, None),
+            Record::Change(Change::Negative) => (TAG_CHANGE | 1, None),
+            Record::Error(error) => (TAG_ERROR, Some(error as u8)),
+        };
+
+        record[len] = tag;
+        len += 1;
+        len += encode_varint(delta, &mut record[len..]);
+        if let Some(payload) = payload {
+            record[len] = payload;
+            len += 1;
+        }
+
+        self.buffer
+            .get_mut(self.len..(self.len + len))
+            .ok_or(CaptureError::BufferFull)?
+            .copy_from_slice(&record[..len]);
+
+        self.len += len;
+        self.last_timestamp = entry.timestamp;
+
+        Ok(())
+    }
+
+    /// Returns the number of bytes written so far (including the header).
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if no entries have been written so far, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.len == HEADER_LEN
+    }
+
+    /// Returns the bytes written so far (including the header).
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+/// A reader, decoding a capture's entries from a byte slice.
+#[derive(Clone, Debug)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+    ticks_per_second: u32,
+    last_timestamp: u64,
+    is_failed: bool,
+}
+
+impl<'a> Reader<'a> {
+    /// Creates a reader for the given capture, validating its header.
+    pub fn new(bytes: &'a [u8]) -> Result<Self, CaptureError> {
+        let header = bytes.get(..HEADER_LEN).ok_or(CaptureError::Truncated)?;
+
+        if header[..MAGIC.len()] != MAGIC {
+            return Err(CaptureError::InvalidMagic);
+        }
+
+        let version = header[MAGIC.len()];
+        if version != VERSION {
+            return Err(CaptureError::UnsupportedVersion(version));
+        }
+
+        let mut ticks_per_second = [0_u8; 4];
+        ticks_per_second.copy_from_slice(&header[(MAGIC.len() + 1)..]);
+
+        Ok(Self {
+            bytes: &bytes[HEADER_LEN..],
+            ticks_per_second: u32::from_le_bytes(ticks_per_second),
+            last_timestamp: 0,
+            is_failed: false,
+        })
+    }
+
+    /// Returns the capture's number of timestamp ticks per second.
+    pub fn ticks_per_second(&self) -> u32 {
+        self.ticks_per_second
+    }
+
+    fn read(&mut self) -> Result<Entry, CaptureError> {
+        let (&tag, rest) = self.bytes.split_first().ok_or(CaptureError::Truncated)?;
+        let (delta, rest) = decode_varint(rest)?;
+
+        let (record, rest) = match tag & TAG_KIND_MASK {
+            TAG_PINS if tag & !0b111 == TAG_PINS => {
+                let record = Record::pins(tag & 0b001 != 0, tag & 0b010 != 0, tag & 0b100 != 0);
+                (record, rest)
+            }
+            TAG_CHANGE if tag == TAG_CHANGE => (Record::Change(Change::Positive), rest),
+            TAG_CHANGE if tag == TAG_CHANGE | 1 => (Record::Change(Change::Negative), rest),
+            TAG_ERROR if tag == TAG_ERROR => {
+                let (&payload, rest) = rest.split_first().ok_or(CaptureError::Truncated)?;
+                let error = decode_error(payload).ok_or(CaptureError::InvalidRecord(tag))?;
+                (Record::Error(error), rest)
+            }
+            _ => return Err(CaptureError::InvalidRecord(tag)),
+        };
+
+        let timestamp = self
+            .last_timestamp
+            .checked_add(delta)
+            .ok_or(CaptureError::NonMonotonicTimestamp)?;
+
+        self.bytes = rest;
+        self.last_timestamp = timestamp;
+
+        Ok(Entry::new(timestamp, record))
+    }
+}
+
+impl Iterator for Reader<'_> {
+    type Item = Result<Entry, CaptureError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.bytes.is_empty() || self.is_failed {
+            return None;
+        }
+
+        let result = self.read();
+        // A malformed record leaves the reader unable to find the next record's start:
+        self.is_failed = result.is_err();
+
+        Some(result)
+    }
+}
+
+fn decode_error(bits: u8) -> Option<Error> {
+    [Error::E00_11, Error::E11_00, Error::E01_10, Error::E10_01]
+        .into_iter()
+        .find(|&error| error as u8 == bits)
+}
+
+fn encode_varint(mut value: u64, bytes: &mut [u8]) -> usize {
+    let mut len = 0;
+
+    loop {
+        let byte = (value & 0x7F) as u8;
+        value >>= 7;
+
+        if value == 0 {
+            bytes[len] = byte;
+            return len + 1;
+        }
+
+        bytes[len] = byte | 0x80;
+        len += 1;
+    }
+}
+
+fn decode_varint(bytes: &[u8]) -> Result<(u64, &[u8]), CaptureError> {
+    let mut value: u64 = 0;
+
+    for (index, &byte) in bytes.iter().enumerate().take(MAX_VARINT_LEN) {
+        value |= ((byte & 0x7F) as u64) << (7 * index);
+
+        if byte & 0x80 == 0 {
+            return Ok((value, &bytes[(index + 1)..]));
+        }
+    }
+
+    Err(CaptureError::Truncated)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        simulator::{Fault, ScriptedFault, Simulator},
+        IncrementalDecoder, QuadStep,
+    };
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let entries = [
+            Entry::new(0, Record::pins(true, true, true)),
+            Entry::new(0, Record::Change(Change::Positive)),
+            Entry::new(127, Record::pins(false, true, false)),
+            Entry::new(128, Record::Change(Change::Negative)),
+            Entry::new(u64::MAX, Record::Error(Error::E01_10)),
+        ];
+
+        let mut buffer = [0_u8; HEADER_LEN + 5 * MAX_RECORD_LEN];
+        let mut writer = Writer::new(&mut buffer, 48_000).unwrap();
+        assert!(writer.is_empty());
+
+        for entry in entries {
+            writer.write(entry).unwrap();
+        }
+
+        let reader = Reader::new(writer.as_bytes()).unwrap();
+        assert_eq!(reader.ticks_per_second(), 48_000);
+
+        let decoded: Vec<Entry> = reader.map(Result::unwrap).collect();
+        assert_eq!(decoded, entries);
+    }
+
+    #[test]
+    fn write_errors() {
+        let mut buffer = [0_u8; HEADER_LEN + 2];
+        let mut writer = Writer::new(&mut buffer, 1_000).unwrap();
+
+        writer
+            .write(Entry::new(5, Record::Change(Change::Positive)))
+            .unwrap();
+        assert_eq!(
+            writer.write(Entry::new(4, Record::Change(Change::Positive))),
+            Err(CaptureError::NonMonotonicTimestamp)
+        );
+        assert_eq!(
+            writer.write(Entry::new(6, Record::Change(Change::Positive))),
+            Err(CaptureError::BufferFull)
+        );
+        assert_eq!(writer.len(), HEADER_LEN + 2);
+
+        assert_eq!(
+            Writer::new(&mut [0_u8; HEADER_LEN - 1], 1_000).err(),
+            Some(CaptureError::BufferFull)
+        );
+    }
+
+    #[test]
+    fn read_errors() {
+        assert_eq!(Reader::new(b"QREC").err(), Some(CaptureError::Truncated));
+        assert_eq!(
+            Reader::new(b"QRAC\x01\0\0\0\0").err(),
+            Some(CaptureError::InvalidMagic)
+        );
+        assert_eq!(
+            Reader::new(b"QREC\x02\0\0\0\0").err(),
+            Some(CaptureError::UnsupportedVersion(2))
+        );
+
+        let results: Vec<_> = Reader::new(b"QREC\x01\0\0\0\0\x10\x00\x30\x00\x10\x00")
+            .unwrap()
+            .collect();
+        assert_eq!(
+            results,
+            vec![
+                Ok(Entry::new(0, Record::Change(Change::Positive))),
+                Err(CaptureError::InvalidRecord(0x30)),
+            ]
+        );
+
+        let results: Vec<_> = Reader::new(b"QREC\x01\0\0\0\0\x20\x80").unwrap().collect();
+        assert_eq!(results, vec![Err(CaptureError::Truncated)]);
+    }
+
+    #[test]
+    fn replay() {
+        let script = [
+            ScriptedFault::inject(3, Fault::DroppedEdges),
+            ScriptedFault::clear(4, Fault::DroppedEdges),
+        ];
+        let mut simulator = Simulator::new().with_script(&script);
+        let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+
+        let mut buffer = [0_u8; 256];
+        let mut writer = Writer::new(&mut buffer, 1_000).unwrap();
+        let mut recorded = vec![];
+
+        for timestamp in 0..8 {
+            let sample = simulator.step(Change::Positive);
+            writer
+                .write(Entry::new(
+                    timestamp,
+                    Record::pins(sample.a, sample.b, sample.z),
+                ))
+                .unwrap();
+
+            if let Some(record) = Record::from_result(decoder.update(sample.a, sample.b)) {
+                writer.write(Entry::new(timestamp, record)).unwrap();
+                recorded.push(record);
+            }
+        }
+
+        // Replaying the captured pin states reproduces the captured events:
+        let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+        let mut replayed = vec![];
+
+        for entry in Reader::new(writer.as_bytes()).unwrap() {
+            if let Record::Pins { a, b, .. } = entry.unwrap().record {
+                replayed.extend(Record::from_result(decoder.update(a, b)));
+            }
+        }
+
+        assert!(recorded
+            .iter()
+            .any(|record| matches!(record, Record::Error(_))));
+        assert_eq!(replayed, recorded);
+    }
+}
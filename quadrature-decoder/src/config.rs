@@ -0,0 +1,213 @@
+//! Compile-time validated configuration.
+
+use core::mem::size_of;
+
+use crate::{max_poll_interval, StepMode};
+
+/// A decoder configuration, validated at compile time
+/// when constructed in a `const` context.
+///
+/// ```
+/// use quadrature_decoder::{Config, QuadStep};
+///
+/// const CONFIG: Config = Config::new(400)
+///     .with_max_rpm(3_000)
+///     .with_poll_interval(10_000)
+///     .with_max_revolutions(1_000)
+///     .validate::<QuadStep, i32>();
+/// ```
+///
+/// Impossible configurations get rejected at compile time:
+///
+/// ```compile_fail
+/// use quadrature_decoder::{Config, QuadStep};
+///
+/// // A 16-bit counter is too narrow for 1000 revolutions at 400 PPR:
+/// const CONFIG: Config = Config::new(400)
+///     .with_max_revolutions(1_000)
+///     .validate::<QuadStep, i16>();
+/// ```
+///
+/// All durations are provided in nanoseconds.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Config {
+    pulses_per_revolution: u32,
+    max_rpm: Option<u32>,
+    poll_interval: Option<u64>,
+    debounce_time: Option<u64>,
+    max_revolutions: Option<u32>,
+}
+
+impl Config {
+    /// Creates a configuration for an encoder with the given number of pulses per revolution (PPR).
+    ///
+    /// The number of pulses is relative to the decoder's step-mode
+    /// (i.e. `cycles_per_revolution * Steps::PULSES_PER_CYCLE`).
+    pub const fn new(pulses_per_revolution: u32) -> Self {
+        assert!(
+            pulses_per_revolution > 0,
+            "Pulses per revolution must be positive."
+        );
+
+        Self {
+            pulses_per_revolution,
+            max_rpm: None,
+            poll_interval: None,
+            debounce_time: None,
+            max_revolutions: None,
+        }
+    }
+
+    /// Sets the encoder's maximum speed in revolutions per minute (RPM).
+    pub const fn with_max_rpm(mut self, max_rpm: u32) -> Self {
+        assert!(max_rpm > 0, "Maximum RPM must be positive.");
+
+        self.max_rpm = Some(max_rpm);
+        self
+    }
+
+    /// Sets the interval at which the decoder gets polled.
+    pub const fn with_poll_interval(mut self, poll_interval: u64) -> Self {
+        assert!(poll_interval > 0, "Poll interval must be positive.");
+
+        self.poll_interval = Some(poll_interval);
+        self
+    }
+
+    /// Sets the duration for which inputs get debounced.
+    pub const fn with_debounce_time(mut self, debounce_time: u64) -> Self {
+        self.debounce_time = Some(debounce_time);
+        self
+    }
+
+    /// Sets the maximum number of revolutions (in either direction)
+    /// the counter is expected to track.
+    pub const fn with_max_revolutions(mut self, max_revolutions: u32) -> Self {
+        self.max_revolutions = Some(max_revolutions);
+        self
+    }
+
+    /// Validates the configuration for the given step-mode and counter type,
+    /// panicking (i.e. failing compilation in a `const` context) if it is impossible.
+    ///
+    /// Checks that:
+    /// - the poll interval is short enough to not miss states at maximum speed.
+    /// - the debounce time is shorter than the minimum spacing of edges at maximum speed.
+    /// - the counter type is wide enough for the maximum number of revolutions.
+    pub const fn validate<Steps, T>(self) -> Self
+    where
+        Steps: StepMode,
+    {
+        if let Some(max_rpm) = self.max_rpm {
+            let min_edge_spacing = max_poll_interval::<Steps>(max_rpm, self.pulses_per_revolution);
+
+            if let Some(poll_interval) = self.poll_interval {
+                assert!(
+                    poll_interval <= min_edge_spacing,
+                    "Poll interval is too long for maximum RPM."
+                );
+            }
+
+            if let Some(debounce_time) = self.debounce_time {
+                assert!(
+                    debounce_time < min_edge_spacing,
+                    "Debounce time is longer than minimum edge spacing at maximum RPM."
+                );
+            }
+        }
+
+        if let Some(max_revolutions) = self.max_revolutions {
+            // Counters are signed (i.e. `T: From<i8>`), leaving one bit for the sign:
+            let bits = (size_of::<T>() * 8) as u32 - 1;
+            let max_counter = if bits >= 64 {
+                u64::MAX
+            } else {
+                (1_u64 << bits) - 1
+            };
+            let max_pulses = max_revolutions as u64 * self.pulses_per_revolution as u64;
+
+            assert!(
+                max_pulses <= max_counter,
+                "Counter type is too narrow for maximum revolutions."
+            );
+        }
+
+        self
+    }
+
+    /// Returns the configured number of pulses per revolution (PPR).
+    pub const fn pulses_per_revolution(&self) -> u32 {
+        self.pulses_per_revolution
+    }
+
+    /// Returns the configured maximum speed in revolutions per minute (RPM), if any.
+    pub const fn max_rpm(&self) -> Option<u32> {
+        self.max_rpm
+    }
+
+    /// Returns the configured poll interval, if any.
+    pub const fn poll_interval(&self) -> Option<u64> {
+        self.poll_interval
+    }
+
+    /// Returns the configured debounce time, if any.
+    pub const fn debounce_time(&self) -> Option<u64> {
+        self.debounce_time
+    }
+
+    /// Returns the configured maximum number of revolutions, if any.
+    pub const fn max_revolutions(&self) -> Option<u32> {
+        self.max_revolutions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FullStep, QuadStep};
+
+    use super::*;
+
+    #[test]
+    fn valid() {
+        let config = Config::new(400)
+            .with_max_rpm(1_500)
+            .with_poll_interval(25_000)
+            .with_debounce_time(5_000)
+            .with_max_revolutions(80)
+            .validate::<QuadStep, i16>();
+
+        assert_eq!(config.pulses_per_revolution(), 400);
+    }
+
+    #[test]
+    #[should_panic(expected = "Pulses per revolution must be positive.")]
+    fn zero_pulses_per_revolution() {
+        let _ = Config::new(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Poll interval is too long for maximum RPM.")]
+    fn poll_interval_too_long() {
+        let _ = Config::new(100)
+            .with_max_rpm(1_500)
+            .with_poll_interval(100_001)
+            .validate::<FullStep, i32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "Debounce time is longer than minimum edge spacing at maximum RPM.")]
+    fn debounce_time_too_long() {
+        let _ = Config::new(100)
+            .with_max_rpm(1_500)
+            .with_debounce_time(100_000)
+            .validate::<FullStep, i32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "Counter type is too narrow for maximum revolutions.")]
+    fn counter_too_narrow() {
+        let _ = Config::new(400)
+            .with_max_revolutions(82)
+            .validate::<QuadStep, i16>();
+    }
+}
@@ -0,0 +1,73 @@
+//! Conversions between counts of different step-modes and detents.
+
+use core::ops::{Div, Mul};
+
+use crate::StepMode;
+
+/// Converts the given number of pulses from one step-mode into another.
+///
+/// Converting to a step-mode of lower resolution truncates towards zero,
+/// corresponding to the number of complete steps of the target step-mode.
+///
+/// ```
+/// use quadrature_decoder::{convert_pulses, FullStep, QuadStep};
+///
+/// assert_eq!(convert_pulses::<FullStep, QuadStep, i32>(25), 100);
+/// assert_eq!(convert_pulses::<QuadStep, FullStep, i32>(-103), -25);
+/// ```
+pub fn convert_pulses<From, To, T>(pulses: T) -> T
+where
+    From: StepMode,
+    To: StepMode,
+    T: core::convert::From<i8> + Mul<Output = T> + Div<Output = T>,
+{
+    let from: T = (From::PULSES_PER_CYCLE as i8).into();
+    let to: T = (To::PULSES_PER_CYCLE as i8).into();
+
+    pulses * to / from
+}
+
+/// Converts the given number of pulses into detents,
+/// given the number of pulses per detent (relative to the pulses' step-mode).
+///
+/// Partial detents are truncated towards zero.
+pub fn pulses_to_detents<T>(pulses: T, pulses_per_detent: T) -> T
+where
+    T: Div<Output = T>,
+{
+    pulses / pulses_per_detent
+}
+
+/// Converts the given number of detents into pulses,
+/// given the number of pulses per detent (relative to the pulses' step-mode).
+pub fn detents_to_pulses<T>(detents: T, pulses_per_detent: T) -> T
+where
+    T: Mul<Output = T>,
+{
+    detents * pulses_per_detent
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FullStep, HalfStep, QuadStep};
+
+    use super::*;
+
+    #[test]
+    fn step_modes() {
+        assert_eq!(convert_pulses::<FullStep, FullStep, i32>(7), 7);
+        assert_eq!(convert_pulses::<FullStep, HalfStep, i32>(7), 14);
+        assert_eq!(convert_pulses::<FullStep, QuadStep, i32>(7), 28);
+        assert_eq!(convert_pulses::<HalfStep, FullStep, i32>(7), 3);
+        assert_eq!(convert_pulses::<HalfStep, QuadStep, i32>(7), 14);
+        assert_eq!(convert_pulses::<QuadStep, FullStep, i32>(-7), -1);
+        assert_eq!(convert_pulses::<QuadStep, HalfStep, i32>(-7), -3);
+    }
+
+    #[test]
+    fn detents() {
+        assert_eq!(pulses_to_detents(10, 4), 2);
+        assert_eq!(pulses_to_detents(-10, 4), -2);
+        assert_eq!(detents_to_pulses(-2, 4), -8);
+    }
+}
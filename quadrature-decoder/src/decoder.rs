@@ -1,6 +1,18 @@
 //! Quadrature-based decoder.
 
+mod dynamic;
 mod incremental;
 mod indexed;
+mod step_dir;
+mod up_down;
 
-pub use self::{incremental::IncrementalDecoder, indexed::IndexedIncrementalDecoder};
+pub use self::{
+    dynamic::DynIncrementalDecoder,
+    incremental::{DecoderState, IncrementalDecoder},
+    indexed::{
+        IndexAction, IndexBehavior, IndexEvent, IndexMode, IndexedDecoderState,
+        IndexedIncrementalDecoder,
+    },
+    step_dir::StepDirDecoder,
+    up_down::UpDownPulseDecoder,
+};
@@ -0,0 +1,314 @@
+//! Quadrature-based decoder with a runtime-selectable step-mode.
+
+use core::borrow::Borrow;
+
+use num_traits::{One, SaturatingAdd, Zero};
+
+use crate::{
+    BatchReport, Change, Error, FullStep, GlitchFilter, HalfStep, IncrementalDecoder, QuadStep,
+    Snapshot, SnapshotError, StepModeKind,
+};
+
+/// The decoder of the currently selected step-mode.
+#[derive(Debug)]
+enum Decoder<T> {
+    Full(IncrementalDecoder<FullStep, T>),
+    Half(IncrementalDecoder<HalfStep, T>),
+    Quad(IncrementalDecoder<QuadStep, T>),
+}
+
+/// Dispatches the given expression to the decoder of the currently selected step-mode.
+macro_rules! dispatch {
+    ($decoder:expr, $inner:ident => $expr:expr) => {
+        match $decoder {
+            Decoder::Full($inner) => $expr,
+            Decoder::Half($inner) => $expr,
+            Decoder::Quad($inner) => $expr,
+        }
+    };
+}
+
+/// A robust quadrature decoder like [`IncrementalDecoder`],
+/// but with its step-mode selected at runtime (e.g. from a configuration file),
+/// rather than via a type parameter.
+///
+/// Prefer `IncrementalDecoder<…>` whenever the step-mode is known at compile-time,
+/// as dispatching on the step-mode adds a (small) overhead to every update.
+#[derive(Debug)]
+pub struct DynIncrementalDecoder<T = i32> {
+    decoder: Decoder<T>,
+}
+
+impl<T> DynIncrementalDecoder<T>
+where
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
+{
+    /// Creates a decoder with the given step-mode.
+    pub fn new(step_mode: StepModeKind) -> Self {
+        let decoder = match step_mode {
+            StepModeKind::Full => Decoder::Full(Default::default()),
+            StepModeKind::Half => Decoder::Half(Default::default()),
+            StepModeKind::Quad => Decoder::Quad(Default::default()),
+        };
+
+        Self { decoder }
+    }
+
+    /// Returns the decoder's step-mode.
+    pub fn step_mode(&self) -> StepModeKind {
+        match self.decoder {
+            Decoder::Full(_) => StepModeKind::Full,
+            Decoder::Half(_) => StepModeKind::Half,
+            Decoder::Quad(_) => StepModeKind::Quad,
+        }
+    }
+
+    /// Sets the decoder's step-mode.
+    ///
+    /// The counter is kept as is (i.e. in pulses of the previous step-mode, see `convert_pulses()`),
+    /// while the decoding state gets re-derived from the most recent reading (as for `reset_state()`).
+    pub fn set_step_mode(&mut self, step_mode: StepModeKind) {
+        if step_mode == self.step_mode() {
+            return;
+        }
+
+        let previous = core::mem::replace(&mut self.decoder, Decoder::Full(Default::default()));
+
+        self.decoder = match step_mode {
+            StepModeKind::Full => Decoder::Full(dispatch!(previous, d => d.into_step_mode())),
+            StepModeKind::Half => Decoder::Half(dispatch!(previous, d => d.into_step_mode())),
+            StepModeKind::Quad => Decoder::Quad(dispatch!(previous, d => d.into_step_mode())),
+        };
+    }
+
+    /// Sets the decoder's glitch filter, returning the modified decoder.
+    pub fn with_glitch_filter(mut self, filter: GlitchFilter) -> Self {
+        self.set_glitch_filter(Some(filter));
+        self
+    }
+
+    /// Returns the decoder's glitch filter, if provided.
+    pub fn glitch_filter(&self) -> Option<GlitchFilter> {
+        dispatch!(&self.decoder, decoder => decoder.glitch_filter())
+    }
+
+    /// Sets the decoder's glitch filter, or disables glitch filtering if `None`.
+    pub fn set_glitch_filter(&mut self, filter: Option<GlitchFilter>) {
+        dispatch!(&mut self.decoder, decoder => decoder.set_glitch_filter(filter))
+    }
+
+    /// Enables the tracking of raw quarter-step changes (see [`sub_step()`](Self::sub_step)),
+    /// returning the modified decoder.
+    pub fn with_sub_steps(mut self) -> Self {
+        self.set_sub_steps(true);
+        self
+    }
+
+    /// Returns `true` if the decoder tracks raw quarter-step changes, otherwise `false`.
+    pub fn tracks_sub_steps(&self) -> bool {
+        dispatch!(&self.decoder, decoder => decoder.tracks_sub_steps())
+    }
+
+    /// Enables (or disables) the tracking of raw quarter-step changes (see [`sub_step()`](Self::sub_step)).
+    pub fn set_sub_steps(&mut self, enabled: bool) {
+        dispatch!(&mut self.decoder, decoder => decoder.set_sub_steps(enabled))
+    }
+
+    /// Returns the number of glitches rejected by the decoder's glitch filter
+    /// since the decoder's creation (or its most recent reset),
+    /// or `0` if the decoder has no glitch filter.
+    pub fn glitch_count(&self) -> u32 {
+        dispatch!(&self.decoder, decoder => decoder.glitch_count())
+    }
+
+    /// Clears the decoder's glitch counter.
+    pub fn clear_glitch_count(&mut self) {
+        dispatch!(&mut self.decoder, decoder => decoder.clear_glitch_count())
+    }
+
+    /// Updates the decoder's state based on the given `a` and `b` pulse train (aka channel) readings,
+    /// returning the direction if a change was detected, `None` if no change was detected,
+    /// or `Err(_)` if an invalid input (i.e. a counteral "jump") was detected.
+    pub fn update(&mut self, a: bool, b: bool) -> Result<Option<Change>, Error> {
+        dispatch!(&mut self.decoder, decoder => decoder.update(a, b))
+    }
+
+    /// Updates the decoder's state like [`update()`](Self::update),
+    /// with the readings taken at timestamp `now` (e.g. in microseconds),
+    /// as required by a [`GlitchFilter::Duration`] glitch filter.
+    pub fn update_at(&mut self, a: bool, b: bool, now: u64) -> Result<Option<Change>, Error> {
+        dispatch!(&mut self.decoder, decoder => decoder.update_at(a, b, now))
+    }
+
+    /// Updates the decoder's state with the given batch of `a` and `b` pulse train readings
+    /// (e.g. captured via DMA), returning a summary of the net movement and any invalid transitions.
+    pub fn update_batch<I>(&mut self, samples: I) -> BatchReport<T>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(bool, bool)>,
+    {
+        dispatch!(&mut self.decoder, decoder => decoder.update_batch(samples))
+    }
+
+    /// Resets the decoder's decoding state (e.g. after an invalid transition),
+    /// keeping its counter, saturation flag and most recent reading.
+    pub fn reset_state(&mut self) {
+        dispatch!(&mut self.decoder, decoder => decoder.reset_state())
+    }
+
+    /// Resets the decoder to its initial state and its counter back to `0`,
+    /// keeping its step-mode.
+    pub fn reset(&mut self) {
+        dispatch!(&mut self.decoder, decoder => decoder.reset())
+    }
+
+    /// Returns the raw quarter-step change detected by the most recent update,
+    /// or `None` if no quarter-step change was detected.
+    ///
+    /// This reports every quarter-step change, regardless of the decoder's step-mode.
+    /// Sub-steps are only tracked once enabled via [`with_sub_steps()`](Self::with_sub_steps).
+    pub fn sub_step(&self) -> Option<Change> {
+        dispatch!(&self.decoder, decoder => decoder.sub_step())
+    }
+
+    /// Returns `true` if the decoder's counter has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the decoder's creation (or its most recent reset), otherwise `false`.
+    pub fn is_saturated(&self) -> bool {
+        dispatch!(&self.decoder, decoder => decoder.is_saturated())
+    }
+
+    /// Clears the decoder's sticky saturation flag.
+    pub fn clear_saturated(&mut self) {
+        dispatch!(&mut self.decoder, decoder => decoder.clear_saturated())
+    }
+
+    /// Returns the decoder's counter relative to its initial counter.
+    pub fn counter(&self) -> T {
+        dispatch!(&self.decoder, decoder => decoder.counter())
+    }
+
+    /// Sets the decoder's counter.
+    pub fn set_counter(&mut self, counter: T) {
+        dispatch!(&mut self.decoder, decoder => decoder.set_counter(counter))
+    }
+
+    /// Returns a compact snapshot of the decoder's position (e.g. for persisting it across resets),
+    /// which the decoder can later be rehydrated from via [`restore()`](Self::restore).
+    pub fn snapshot(&self) -> Snapshot<T> {
+        dispatch!(&self.decoder, decoder => decoder.snapshot())
+    }
+
+    /// Restores the decoder's counter and phase from the given `snapshot`,
+    /// like [`IncrementalDecoder::restore()`], switching to the snapshot's step-mode
+    /// (keeping the decoder's glitch filter).
+    pub fn restore(&mut self, snapshot: Snapshot<T>) -> Result<(), SnapshotError> {
+        let mut decoder = match snapshot.step_mode {
+            StepModeKind::Full => Decoder::Full(Default::default()),
+            StepModeKind::Half => Decoder::Half(Default::default()),
+            StepModeKind::Quad => Decoder::Quad(Default::default()),
+        };
+
+        dispatch!(&mut decoder, decoder => decoder.restore(snapshot))?;
+
+        if let Some(filter) = self.glitch_filter() {
+            dispatch!(&mut decoder, decoder => decoder.set_glitch_filter(Some(filter)));
+        }
+
+        self.decoder = decoder;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FORWARDS: [(bool, bool); 4] =
+        [(false, true), (false, false), (true, false), (true, true)];
+
+    #[test]
+    fn step_modes() {
+        for (step_mode, expected) in [
+            (StepModeKind::Full, 1),
+            (StepModeKind::Half, 2),
+            (StepModeKind::Quad, 4),
+        ] {
+            let mut decoder: DynIncrementalDecoder = DynIncrementalDecoder::new(step_mode);
+            assert_eq!(decoder.step_mode(), step_mode);
+
+            let report = decoder.update_batch(FORWARDS);
+
+            assert_eq!(report.delta, expected);
+            assert_eq!(decoder.counter(), expected);
+        }
+    }
+
+    #[test]
+    fn set_step_mode() {
+        let mut decoder: DynIncrementalDecoder = DynIncrementalDecoder::new(StepModeKind::Full);
+
+        decoder.update_batch(FORWARDS);
+        assert_eq!(decoder.counter(), 1);
+
+        decoder.set_step_mode(StepModeKind::Quad);
+        assert_eq!(decoder.step_mode(), StepModeKind::Quad);
+        assert_eq!(decoder.counter(), 1);
+
+        decoder.update_batch(FORWARDS);
+        assert_eq!(decoder.counter(), 5);
+    }
+
+    #[test]
+    fn set_step_mode_mid_cycle() {
+        let mut decoder: DynIncrementalDecoder = DynIncrementalDecoder::new(StepModeKind::Full);
+
+        // Switching while the lines are at (0, 0), half-way through a cycle:
+        decoder.update_batch(&FORWARDS[..2]);
+        decoder.set_step_mode(StepModeKind::Quad);
+
+        let report = decoder.update_batch(&FORWARDS[2..]);
+        assert_eq!(report.errors, 0);
+        assert_eq!(decoder.counter(), 2);
+
+        // Switching back while the lines are at (1, 0), three quarters through a cycle:
+        decoder.update_batch(&FORWARDS[..3]);
+        assert_eq!(decoder.counter(), 5);
+        decoder.set_step_mode(StepModeKind::Full);
+
+        let report = decoder.update_batch(&FORWARDS[3..]);
+        assert_eq!(report.errors, 0);
+        assert_eq!(decoder.counter(), 6);
+    }
+
+    #[test]
+    fn reset_state() {
+        let mut decoder: DynIncrementalDecoder = DynIncrementalDecoder::new(StepModeKind::Full);
+        decoder.set_counter(5);
+
+        // An invalid transition mid-cycle:
+        assert_eq!(decoder.update(false, true), Ok(None));
+        assert!(decoder.update(true, false).is_err());
+
+        decoder.reset_state();
+        assert_eq!(decoder.counter(), 5);
+
+        // Decoding resumes from the most recent reading
+        // (i.e. `(true, false)`, taken as three quarters into a forward cycle):
+        assert_eq!(decoder.update(true, true), Ok(Some(Change::Positive)));
+        assert_eq!(decoder.counter(), 6);
+
+        let report = decoder.update_batch(FORWARDS);
+
+        assert!(report.is_clean());
+        assert_eq!(decoder.counter(), 7);
+
+        let mut decoder: DynIncrementalDecoder = DynIncrementalDecoder::new(StepModeKind::Quad);
+
+        assert!(decoder.update_batch(&FORWARDS[..2]).is_clean());
+        decoder.reset_state();
+
+        assert!(decoder.update_batch(&FORWARDS[2..]).is_clean());
+        assert_eq!(decoder.counter(), 4);
+    }
+}
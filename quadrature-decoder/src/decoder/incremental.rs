@@ -1,15 +1,80 @@
 //! Quadrature-based decoder.
 
-use core::marker::PhantomData;
+use core::{borrow::Borrow, marker::PhantomData};
 
 use num_traits::{One, SaturatingAdd, Zero};
 
 use crate::{
-    state_transducer::{Input, Output},
+    glitch::{Deglitcher, DeglitcherState},
+    state_transducer::{Input, Output, State},
     validator::InputValidator,
-    Change, Error, FullStep, HalfStep, QuadStep, StateTransducer, StepMode,
+    BatchReport, Change, Error, FullStep, GlitchFilter, HalfStep, QuadStep, Snapshot,
+    SnapshotError, StateTransducer, StepMode, StepModeKind,
 };
 
+/// A plain-data snapshot of an [`IncrementalDecoder`]'s state,
+/// as returned by [`IncrementalDecoder::state()`].
+///
+/// The snapshot only covers the decoder's state (including that of its glitch filter, if any),
+/// not its configuration, allowing for state transfer (e.g. between cores), test fixtures starting mid-sequence,
+/// as well as speculative decoding (see [`IncrementalDecoder::checkpoint()`]).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DecoderState<Mode, T = i32> {
+    state: State,
+    sub_step_state: State,
+    input: Input,
+    counter: T,
+    sub_step: Option<Change>,
+    is_saturated: bool,
+    glitch_state: Option<DeglitcherState<(bool, bool)>>,
+    _phantom: PhantomData<Mode>,
+}
+
+impl<Mode, T> Default for DecoderState<Mode, T>
+where
+    T: Zero,
+{
+    /// Returns the state of a newly created (or reset) decoder.
+    fn default() -> Self {
+        Self {
+            state: State::N0,
+            sub_step_state: State::N0,
+            input: Input::A1B1,
+            counter: Zero::zero(),
+            sub_step: None,
+            is_saturated: false,
+            glitch_state: None,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<Mode, T> DecoderState<Mode, T>
+where
+    T: Copy,
+{
+    /// Sets the state's counter, returning the modified state.
+    pub fn with_counter(mut self, counter: T) -> Self {
+        self.counter = counter;
+        self
+    }
+
+    /// Returns the state's counter.
+    pub fn counter(&self) -> T {
+        self.counter
+    }
+
+    /// Returns the state's raw quarter-step change.
+    pub fn sub_step(&self) -> Option<Change> {
+        self.sub_step
+    }
+
+    /// Returns the state's sticky saturation flag.
+    pub fn is_saturated(&self) -> bool {
+        self.is_saturated
+    }
+}
+
 /// A robust quadrature decoder with support for multiple step-modes,
 /// based on which channel (A vs. B) is leading the other.
 ///
@@ -33,8 +98,13 @@ use crate::{
 #[derive(Debug)]
 pub struct IncrementalDecoder<Mode, T = i32> {
     transducer: StateTransducer<'static, 8, 4>,
+    sub_step_transducer: StateTransducer<'static, 8, 4>,
     validator: InputValidator,
+    deglitcher: Option<Deglitcher<(bool, bool)>>,
     counter: T,
+    sub_step: Option<Change>,
+    tracks_sub_steps: bool,
+    is_saturated: bool,
     _phantom: PhantomData<Mode>,
 }
 
@@ -79,17 +149,86 @@ where
     pub(crate) fn new(transducer: StateTransducer<'static, 8, 4>) -> Self {
         Self {
             transducer,
+            sub_step_transducer: StateTransducer::new(
+                &crate::state_transducer::quad_step::TRANSITIONS,
+            ),
             validator: Default::default(),
+            deglitcher: None,
             counter: Zero::zero(),
+            sub_step: None,
+            tracks_sub_steps: false,
+            is_saturated: false,
             _phantom: PhantomData,
         }
     }
 }
 
+impl<Mode, T> IncrementalDecoder<Mode, T> {
+    /// Enables the tracking of raw quarter-step changes (see [`sub_step()`](Self::sub_step)),
+    /// returning the modified decoder.
+    pub fn with_sub_steps(mut self) -> Self {
+        self.set_sub_steps(true);
+        self
+    }
+
+    /// Returns `true` if the decoder tracks raw quarter-step changes, otherwise `false`.
+    pub fn tracks_sub_steps(&self) -> bool {
+        self.tracks_sub_steps
+    }
+
+    /// Enables (or disables) the tracking of raw quarter-step changes (see [`sub_step()`](Self::sub_step)).
+    ///
+    /// Tracking starts from the most recent reading.
+    pub fn set_sub_steps(&mut self, enabled: bool) {
+        if enabled && !self.tracks_sub_steps {
+            if let Some(state) = self
+                .sub_step_transducer
+                .stable_state(self.validator.input())
+            {
+                self.sub_step_transducer.set_state(state);
+            }
+        }
+        self.tracks_sub_steps = enabled;
+        self.sub_step = None;
+    }
+
+    /// Sets the decoder's glitch filter, returning the modified decoder.
+    ///
+    /// Samples rejected by the filter are reported as no change, never reaching the state machine.
+    pub fn with_glitch_filter(mut self, filter: GlitchFilter) -> Self {
+        self.deglitcher = Some(Deglitcher::new(filter));
+        self
+    }
+
+    /// Returns the decoder's glitch filter, if provided.
+    pub fn glitch_filter(&self) -> Option<GlitchFilter> {
+        self.deglitcher.as_ref().map(Deglitcher::filter)
+    }
+
+    /// Sets the decoder's glitch filter, or disables glitch filtering if `None`.
+    pub fn set_glitch_filter(&mut self, filter: Option<GlitchFilter>) {
+        self.deglitcher = filter.map(Deglitcher::new);
+    }
+
+    /// Returns the number of glitches (i.e. pending changes abandoned before becoming stable)
+    /// rejected by the decoder's glitch filter since the decoder's creation (or its most recent reset),
+    /// or `0` if the decoder has no glitch filter.
+    pub fn glitch_count(&self) -> u32 {
+        self.deglitcher.as_ref().map_or(0, Deglitcher::glitches)
+    }
+
+    /// Clears the decoder's glitch counter.
+    pub fn clear_glitch_count(&mut self) {
+        if let Some(deglitcher) = &mut self.deglitcher {
+            deglitcher.clear_glitches();
+        }
+    }
+}
+
 impl<Mode, T> IncrementalDecoder<Mode, T>
 where
     Mode: StepMode,
-    T: Copy + Zero + One + SaturatingAdd + From<i8>,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
 {
     /// Updates the decoder's state based on the given `a` and `b` pulse train (aka channel) readings,
     /// returning the direction if a change was detected, `None` if no change was detected,
@@ -100,11 +239,52 @@ where
     /// you would either call `decoder.update(a, b)` directly, or via `decoder.update(a, b).unwrap_or_default()`
     /// to fall back to `None` in case of `Err(_)`.
     pub fn update(&mut self, a: bool, b: bool) -> Result<Option<Change>, Error> {
+        self.update_filtered(a, b, None)
+    }
+
+    /// Updates the decoder's state like [`update()`](Self::update),
+    /// with the readings taken at timestamp `now` (e.g. in microseconds),
+    /// as required by a [`GlitchFilter::Duration`] glitch filter.
+    pub fn update_at(&mut self, a: bool, b: bool, now: u64) -> Result<Option<Change>, Error> {
+        self.update_filtered(a, b, Some(now))
+    }
+
+    fn update_filtered(
+        &mut self,
+        a: bool,
+        b: bool,
+        now: Option<u64>,
+    ) -> Result<Option<Change>, Error> {
+        let (a, b) = match &mut self.deglitcher {
+            Some(deglitcher) => match deglitcher.update((a, b), now) {
+                Some(sample) => sample,
+                None => {
+                    self.sub_step = None;
+                    return Ok(None);
+                }
+            },
+            None => (a, b),
+        };
+
         let input = Input::new(a, b);
 
         let validation_result = self.validator.validate(input);
         let transducer_output = self.transducer.step(input);
 
+        if self.tracks_sub_steps {
+            // The quad-step transducer already reports every quarter-step change:
+            let sub_step_output = match Mode::KIND {
+                StepModeKind::Quad => transducer_output,
+                _ => self.sub_step_transducer.step(input),
+            };
+
+            self.sub_step = match (validation_result, sub_step_output) {
+                (Ok(_), Output::AB) => Some(Change::Positive),
+                (Ok(_), Output::BA) => Some(Change::Negative),
+                _ => None,
+            };
+        }
+
         match (validation_result, transducer_output) {
             (Err(error), output) => {
                 debug_assert_eq!(output, Output::N, "Expected `None` output from transducer.");
@@ -113,14 +293,12 @@ where
             (Ok(_), Output::N) => Ok(None),
             (Ok(_), Output::AB) => {
                 let change = Change::Positive;
-                let delta: T = (change as i8).into();
-                self.counter = self.counter.saturating_add(&delta);
+                self.adjust_counter((change as i8).into());
                 Ok(Some(change))
             }
             (Ok(_), Output::BA) => {
                 let change = Change::Negative;
-                let delta: T = (change as i8).into();
-                self.counter = self.counter.saturating_add(&delta);
+                self.adjust_counter((change as i8).into());
                 Ok(Some(change))
             }
             (_, Output::E) => {
@@ -131,11 +309,193 @@ where
         }
     }
 
+    /// Updates the decoder's state with the given batch of `a` and `b` pulse train readings
+    /// (e.g. captured via DMA), returning a summary of the net movement and any invalid transitions.
+    ///
+    /// Decoding continues past invalid transitions, just like repeated calls of `update()` would.
+    pub fn update_batch<I>(&mut self, samples: I) -> BatchReport<T>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(bool, bool)>,
+    {
+        let mut report = BatchReport::default();
+
+        for (index, sample) in samples.into_iter().enumerate() {
+            let (a, b) = *sample.borrow();
+            report.record(index, self.update(a, b));
+        }
+
+        report
+    }
+
+    /// Adjusts the decoder's counter by the given `delta`, saturating at its bounds,
+    /// in which case the sticky saturation flag gets set.
+    pub(crate) fn adjust_counter(&mut self, delta: T) {
+        let counter = self.counter.saturating_add(&delta);
+        self.is_saturated |= counter == self.counter && !delta.is_zero();
+        self.counter = counter;
+    }
+
     /// Resets the decoder to its initial state and its counter counter back to `0`.
     pub fn reset(&mut self) {
         self.transducer.reset();
+        self.sub_step_transducer.reset();
         self.validator.reset();
+        if let Some(deglitcher) = &mut self.deglitcher {
+            deglitcher.reset();
+        }
         self.counter = Zero::zero();
+        self.sub_step = None;
+        self.is_saturated = false;
+    }
+
+    /// Resets the decoder's decoding state (e.g. after an invalid transition),
+    /// keeping its counter, saturation flag and most recent reading.
+    ///
+    /// The decoding state gets re-derived from the most recent reading (as for [`restore()`](Self::restore)),
+    /// with decoding resuming from there.
+    pub fn reset_state(&mut self) {
+        self.sync_states();
+        self.sub_step = None;
+    }
+
+    /// Sets the transducers' states to the ones stable for the most recent reading.
+    fn sync_states(&mut self) {
+        let input = self.validator.input();
+
+        match self.transducer.stable_state(input) {
+            Some(state) => self.transducer.set_state(state),
+            None => self.transducer.reset(),
+        }
+        match self.sub_step_transducer.stable_state(input) {
+            Some(state) => self.sub_step_transducer.set_state(state),
+            None => self.sub_step_transducer.reset(),
+        }
+    }
+
+    /// Returns a plain-data snapshot of the decoder's current state.
+    pub fn state(&self) -> DecoderState<Mode, T> {
+        DecoderState {
+            state: self.transducer.state(),
+            sub_step_state: self.sub_step_transducer.state(),
+            input: self.validator.input(),
+            counter: self.counter,
+            sub_step: self.sub_step,
+            is_saturated: self.is_saturated,
+            glitch_state: self.deglitcher.as_ref().map(Deglitcher::state),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the decoder's state to the given snapshot.
+    ///
+    /// The state of the decoder's glitch filter (if any) gets reset,
+    /// unless the snapshot was taken of a decoder with a glitch filter as well.
+    pub fn set_state(&mut self, state: DecoderState<Mode, T>) {
+        self.transducer.set_state(state.state);
+        self.sub_step_transducer.set_state(state.sub_step_state);
+        self.validator.set_input(state.input);
+        self.counter = state.counter;
+        self.sub_step = state.sub_step;
+        self.is_saturated = state.is_saturated;
+
+        if let Some(deglitcher) = &mut self.deglitcher {
+            match state.glitch_state {
+                Some(glitch_state) => deglitcher.set_state(glitch_state),
+                None => deglitcher.reset(),
+            }
+        }
+    }
+
+    /// Returns a compact snapshot of the decoder's position (e.g. for persisting it across resets),
+    /// which the decoder can later be rehydrated from via [`restore()`](Self::restore).
+    pub fn snapshot(&self) -> Snapshot<T> {
+        let input = self.validator.input();
+
+        Snapshot {
+            counter: self.counter,
+            phase: ((input.a() as u8) << 1) | (input.b() as u8),
+            step_mode: Mode::KIND,
+        }
+    }
+
+    /// Restores the decoder's counter and phase from the given `snapshot`,
+    /// returning `Err(_)` if the snapshot was taken of a decoder of another step-mode,
+    /// or has an invalid phase (leaving the decoder unchanged in that case).
+    ///
+    /// The decoding state gets re-derived from the snapshot's reading of channels `a` and `b`,
+    /// hence a partially travelled cycle (of `FullStep` and `HalfStep` decoders) may be off by one pulse.
+    /// The saturation flag gets cleared.
+    pub fn restore(&mut self, snapshot: Snapshot<T>) -> Result<(), SnapshotError> {
+        if snapshot.step_mode != Mode::KIND {
+            return Err(SnapshotError::StepMode(snapshot.step_mode));
+        }
+
+        let invalid_phase = SnapshotError::Phase(snapshot.phase);
+
+        if snapshot.phase >> 2 != 0 {
+            return Err(invalid_phase);
+        }
+
+        let input = Input::new(snapshot.phase & 0b_10 != 0, snapshot.phase & 0b_01 != 0);
+
+        let state = self.transducer.stable_state(input).ok_or(invalid_phase)?;
+        let sub_step_state = self
+            .sub_step_transducer
+            .stable_state(input)
+            .ok_or(invalid_phase)?;
+
+        self.transducer.set_state(state);
+        self.sub_step_transducer.set_state(sub_step_state);
+        self.validator.set_input(input);
+        self.counter = snapshot.counter;
+        self.sub_step = None;
+        self.is_saturated = false;
+
+        Ok(())
+    }
+
+    /// Returns a snapshot of the decoder's current state,
+    /// which the decoder can later be reverted to via [`rollback()`](Self::rollback).
+    ///
+    /// This allows for speculatively decoding hypothetical sample sequences
+    /// (e.g. in lock-step simulations, or predictive control code) and reverting afterwards.
+    pub fn checkpoint(&self) -> DecoderState<Mode, T> {
+        self.state()
+    }
+
+    /// Reverts the decoder to the state of the given `checkpoint`.
+    pub fn rollback(&mut self, checkpoint: DecoderState<Mode, T>) {
+        self.set_state(checkpoint);
+    }
+
+    /// Returns the raw quarter-step change detected by the most recent update,
+    /// or `None` if no quarter-step change was detected.
+    ///
+    /// This reports every quarter-step change, regardless of the decoder's step-mode
+    /// (e.g. four per cycle, even with `FullStep` only reporting one change per cycle),
+    /// allowing for diagnostics and fine-grained velocity estimation without switching modes.
+    ///
+    /// Sub-steps are only tracked once enabled via [`with_sub_steps()`](Self::with_sub_steps),
+    /// sparing all other decoders the cost, and are reported as `None` otherwise.
+    pub fn sub_step(&self) -> Option<Change> {
+        self.sub_step
+    }
+
+    /// Returns `true` if the decoder's counter has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the decoder's creation (or its most recent reset), otherwise `false`.
+    ///
+    /// Once saturated, the counter stays pegged at its bound while moving further beyond it,
+    /// hence no longer reflecting the actual position.
+    /// The flag is sticky, staying set until cleared via [`clear_saturated()`](Self::clear_saturated),
+    /// or [`reset()`](Self::reset).
+    pub fn is_saturated(&self) -> bool {
+        self.is_saturated
+    }
+
+    /// Clears the decoder's sticky saturation flag (e.g. after re-homing via `set_counter()`).
+    pub fn clear_saturated(&mut self) {
+        self.is_saturated = false;
     }
 
     /// Returns the decoder's counter counter relative to its initial counter in number of cycles.
@@ -150,4 +510,26 @@ where
     pub fn set_counter(&mut self, counter: T) {
         self.counter = counter;
     }
+
+    /// Converts the decoder into one of another step-mode,
+    /// keeping its counter, saturation flag, glitch filter, sub-step tracking and most recent reading,
+    /// while re-deriving its decoding state from the latter.
+    pub(crate) fn into_step_mode<Mode2>(self) -> IncrementalDecoder<Mode2, T>
+    where
+        Mode2: StepMode,
+        IncrementalDecoder<Mode2, T>: Default,
+    {
+        let mut decoder = IncrementalDecoder {
+            deglitcher: self
+                .deglitcher
+                .map(|deglitcher| Deglitcher::new(deglitcher.filter())),
+            counter: self.counter,
+            tracks_sub_steps: self.tracks_sub_steps,
+            is_saturated: self.is_saturated,
+            ..Default::default()
+        };
+        decoder.validator.set_input(self.validator.input());
+        decoder.sync_states();
+        decoder
+    }
 }
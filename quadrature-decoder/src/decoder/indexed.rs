@@ -1,8 +1,172 @@
 //! Quadrature-based decoder.
 
-use num_traits::{One, SaturatingAdd, Zero};
+use core::{
+    borrow::Borrow,
+    ops::{Add, Mul, Sub},
+};
 
-use crate::{index_decoder::IndexDecoder, Change, Error, IncrementalDecoder, StepMode};
+use num_traits::{Euclid, One, SaturatingAdd, Zero};
+
+use crate::{
+    index_decoder::IndexDecoder, BatchReport, Change, DecoderState, Error, GlitchFilter,
+    IncrementalDecoder, Snapshot, SnapshotError, StepMode,
+};
+
+/// The behavior of an indexed decoder upon detecting an index.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum IndexMode {
+    /// Resets the counter back to `0` on every detected index.
+    #[default]
+    Always,
+    /// Resets the counter back to `0` on the first detected index only,
+    /// with any subsequent indices merely being reported.
+    ///
+    /// This is the behavior most motion controllers expect,
+    /// as it avoids repeated corrections from a slightly jittery index signal.
+    Once,
+    /// Never resets the counter, with every detected index merely latching the counter
+    /// (see `latched_counter()`) and counting revolutions (see `revolutions()`).
+    Latch,
+}
+
+/// The context of a detected index, as passed to an [`IndexBehavior`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IndexEvent<T> {
+    /// The decoder's counter at the time of the index.
+    pub counter: T,
+    /// Whether the counter had already been referenced to an index before this one
+    /// (i.e. by a previous `Set` or `Adjust` action, or by homing).
+    pub is_synced: bool,
+    /// Whether this is the first index detected since the decoder's creation (or its most recent reset).
+    pub is_first: bool,
+}
+
+/// The corrective action to take upon detecting an index, as returned by an [`IndexBehavior`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum IndexAction<T> {
+    /// Leaves the counter as is (e.g. for merely logging, or latching the index).
+    Keep,
+    /// Sets the counter to the given value.
+    Set(T),
+    /// Adjusts the counter by the given delta.
+    Adjust(T),
+}
+
+/// A custom behavior of an indexed decoder upon detecting an index,
+/// receiving the index's context and returning the corrective action to take.
+///
+/// ```
+/// use quadrature_decoder::{IndexAction, IndexEvent};
+///
+/// // Only ever correct the counter by a single pulse towards zero:
+/// fn nudge(event: IndexEvent<i32>) -> IndexAction<i32> {
+///     IndexAction::Adjust(-event.counter.signum())
+/// }
+/// ```
+pub type IndexBehavior<T> = fn(IndexEvent<T>) -> IndexAction<T>;
+
+/// Multiple evenly spaced index marks per revolution.
+#[derive(Clone, Copy, Debug)]
+struct IndexMarks<T> {
+    count: u8,
+    // Monomorphized at configuration time, keeping `update()`'s bounds unaffected:
+    nearest: fn(T, T, u8, T) -> T,
+}
+
+/// Returns the position (within `[offset, offset + pulses_per_revolution)`) of the index mark nearest to `counter`,
+/// with the marks being spaced evenly from `offset`.
+fn nearest_index_mark<T>(counter: T, pulses_per_revolution: T, marks: u8, offset: T) -> T
+where
+    T: Copy + Euclid + Mul<Output = T> + Add<Output = T> + Sub<Output = T> + From<i8>,
+{
+    let marks: T = (marks.min(i8::MAX as u8) as i8).into();
+    let spacing = pulses_per_revolution / marks;
+    let half_spacing = spacing / 2.into();
+
+    let mark = (counter - offset + half_spacing).div_euclid(&spacing);
+
+    (mark * spacing).rem_euclid(&pulses_per_revolution) + offset
+}
+
+/// A plain-data snapshot of an [`IndexedIncrementalDecoder`]'s state,
+/// as returned by [`IndexedIncrementalDecoder::state()`].
+///
+/// The snapshot only covers the decoder's state, not its configuration.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct IndexedDecoderState<Mode, T = i32> {
+    decoder: DecoderState<Mode, T>,
+    z: bool,
+    is_synced: bool,
+    is_at_index: bool,
+    latched: Option<T>,
+    revolutions: i32,
+    direction: Option<Change>,
+}
+
+impl<Mode, T> Default for IndexedDecoderState<Mode, T>
+where
+    T: Zero,
+{
+    /// Returns the state of a newly created (or reset) decoder.
+    fn default() -> Self {
+        Self {
+            decoder: Default::default(),
+            z: false,
+            is_synced: false,
+            is_at_index: false,
+            latched: None,
+            revolutions: 0,
+            direction: None,
+        }
+    }
+}
+
+impl<Mode, T> IndexedDecoderState<Mode, T>
+where
+    T: Copy,
+{
+    /// Sets the state's counter, returning the modified state.
+    pub fn with_counter(mut self, counter: T) -> Self {
+        self.decoder = self.decoder.with_counter(counter);
+        self
+    }
+
+    /// Sets whether the state is synced to the index, returning the modified state.
+    pub fn with_index_synced(mut self, is_synced: bool) -> Self {
+        self.is_synced = is_synced;
+        self
+    }
+
+    /// Returns the state's counter.
+    pub fn counter(&self) -> T {
+        self.decoder.counter()
+    }
+
+    /// Returns the state's raw quarter-step change.
+    pub fn sub_step(&self) -> Option<Change> {
+        self.decoder.sub_step()
+    }
+
+    /// Returns `true` if the state is synced to the index, otherwise `false`.
+    pub fn is_index_synced(&self) -> bool {
+        self.is_synced
+    }
+
+    /// Returns `true` if the state is at an index, otherwise `false`.
+    pub fn is_at_index(&self) -> bool {
+        self.is_at_index
+    }
+
+    /// Returns the state's counter latched at the most recent index, if any.
+    pub fn latched_counter(&self) -> Option<T> {
+        self.latched
+    }
+
+    /// Returns the state's number of revolutions.
+    pub fn revolutions(&self) -> i32 {
+        self.revolutions
+    }
+}
 
 /// A robust indexed quadrature decoder with support for multiple step-modes,
 /// based on which channel (A vs. B) is leading the other.
@@ -36,6 +200,16 @@ use crate::{index_decoder::IndexDecoder, Change, Error, IncrementalDecoder, Step
 pub struct IndexedIncrementalDecoder<Mode, T = i32> {
     decoder: IncrementalDecoder<Mode, T>,
     indexer: IndexDecoder,
+    index_mode: IndexMode,
+    pulses_per_revolution: Option<T>,
+    index_marks: Option<IndexMarks<T>>,
+    index_behavior: Option<IndexBehavior<T>>,
+    index_offset: Option<T>,
+    is_synced: bool,
+    is_at_index: bool,
+    latched: Option<T>,
+    revolutions: i32,
+    direction: Option<Change>,
 }
 
 impl<Mode, T> Default for IndexedIncrementalDecoder<Mode, T>
@@ -54,22 +228,174 @@ where
 {
     pub(crate) fn new(decoder: IncrementalDecoder<Mode, T>) -> Self {
         Self {
-            decoder,
+            // Sub-steps provide the direction of the movement across the index:
+            decoder: decoder.with_sub_steps(),
             indexer: Default::default(),
+            index_mode: IndexMode::default(),
+            pulses_per_revolution: None,
+            index_marks: None,
+            index_behavior: None,
+            index_offset: None,
+            is_synced: false,
+            is_at_index: false,
+            latched: None,
+            revolutions: 0,
+            direction: None,
         }
     }
+
+    /// Sets the decoder's index mode, returning the modified decoder.
+    pub fn with_index_mode(mut self, index_mode: IndexMode) -> Self {
+        self.index_mode = index_mode;
+        self
+    }
+
+    /// Returns the decoder's index mode.
+    pub fn index_mode(&self) -> IndexMode {
+        self.index_mode
+    }
+
+    /// Sets the decoder's index mode.
+    pub fn set_index_mode(&mut self, index_mode: IndexMode) {
+        self.index_mode = index_mode;
+    }
+
+    /// Sets the decoder's custom index behavior, returning the modified decoder.
+    ///
+    /// A custom index behavior takes precedence over the decoder's `IndexMode` and index marks.
+    pub fn with_index_behavior(mut self, index_behavior: IndexBehavior<T>) -> Self {
+        self.index_behavior = Some(index_behavior);
+        self
+    }
+
+    /// Returns the decoder's custom index behavior, if provided.
+    pub fn index_behavior(&self) -> Option<IndexBehavior<T>> {
+        self.index_behavior
+    }
+
+    /// Sets the decoder's custom index behavior, or reverts to the built-in behavior if `None`.
+    pub fn set_index_behavior(&mut self, index_behavior: Option<IndexBehavior<T>>) {
+        self.index_behavior = index_behavior;
+    }
+
+    /// Sets the decoder's calibration offset (i.e. the counter assigned at the index),
+    /// returning the modified decoder.
+    ///
+    /// This allows for the index to be offset from the application's origin
+    /// (e.g. a motor's electrical zero), rather than resetting the counter back to `0`.
+    pub fn with_index_offset(mut self, offset: T) -> Self {
+        self.index_offset = Some(offset);
+        self
+    }
+
+    /// Returns the decoder's calibration offset, if provided.
+    pub fn index_offset(&self) -> Option<T>
+    where
+        T: Copy,
+    {
+        self.index_offset
+    }
+
+    /// Sets the decoder's calibration offset, or reverts to `0` if `None`.
+    pub fn set_index_offset(&mut self, offset: Option<T>) {
+        self.index_offset = offset;
+    }
+
+    /// Sets the decoder's glitch filter (of the `a` and `b` pulse trains), returning the modified decoder.
+    ///
+    /// Samples rejected by the filter are reported as no change, never reaching the state machine.
+    pub fn with_glitch_filter(mut self, filter: GlitchFilter) -> Self {
+        self.decoder.set_glitch_filter(Some(filter));
+        self
+    }
+
+    /// Returns the decoder's glitch filter, if provided.
+    pub fn glitch_filter(&self) -> Option<GlitchFilter> {
+        self.decoder.glitch_filter()
+    }
+
+    /// Sets the decoder's glitch filter, or disables glitch filtering if `None`.
+    pub fn set_glitch_filter(&mut self, filter: Option<GlitchFilter>) {
+        self.decoder.set_glitch_filter(filter);
+    }
+
+    /// Returns the number of glitches rejected by the decoder's glitch filter
+    /// since the decoder's creation (or its most recent reset),
+    /// or `0` if the decoder has no glitch filter.
+    pub fn glitch_count(&self) -> u32 {
+        self.decoder.glitch_count()
+    }
+
+    /// Clears the decoder's glitch counter.
+    pub fn clear_glitch_count(&mut self) {
+        self.decoder.clear_glitch_count();
+    }
+
+    /// Sets the decoder's number of pulses per revolution (PPR),
+    /// returning the modified decoder.
+    ///
+    /// The number of pulses is expected to be positive and relative to the decoder's step-mode
+    /// (i.e. `cycles_per_revolution * Mode::PULSES_PER_CYCLE`).
+    pub fn with_pulses_per_revolution(mut self, pulses_per_revolution: T) -> Self {
+        self.pulses_per_revolution = Some(pulses_per_revolution);
+        self
+    }
+
+    /// Sets the decoder's number of pulses per revolution (PPR).
+    ///
+    /// The number of pulses is expected to be positive and relative to the decoder's step-mode
+    /// (i.e. `cycles_per_revolution * Mode::PULSES_PER_CYCLE`).
+    pub fn set_pulses_per_revolution(&mut self, pulses_per_revolution: Option<T>) {
+        self.pulses_per_revolution = pulses_per_revolution;
+    }
+
+    /// Returns the decoder's number of index marks per revolution.
+    pub fn index_marks(&self) -> u8 {
+        self.index_marks.as_ref().map_or(1, |marks| marks.count)
+    }
 }
 
 impl<Mode, T> IndexedIncrementalDecoder<Mode, T>
 where
     Mode: StepMode,
-    T: Copy + Zero + One + SaturatingAdd + From<i8>,
+    T: Copy + Euclid + Mul<Output = T> + Add<Output = T> + Sub<Output = T> + From<i8>,
+{
+    /// Sets the decoder's number of evenly spaced index marks per revolution (up to `127`),
+    /// returning the modified decoder.
+    ///
+    /// Some encoders provide multiple index pulses per revolution (e.g. one per pole pair).
+    /// Since marks are indistinguishable from one another, the first detected index is
+    /// treated as the revolution's origin, with any subsequent index (subject to the decoder's `IndexMode`)
+    /// correcting the counter to the position of the nearest mark, rather than resetting it back to `0`.
+    ///
+    /// Requires the decoder's number of pulses per revolution (PPR) to be provided.
+    pub fn with_index_marks(mut self, marks: u8) -> Self {
+        self.set_index_marks(marks);
+        self
+    }
+
+    /// Sets the decoder's number of evenly spaced index marks per revolution (up to `127`).
+    pub fn set_index_marks(&mut self, marks: u8) {
+        self.index_marks = (marks > 1).then_some(IndexMarks {
+            count: marks,
+            nearest: nearest_index_mark::<T>,
+        });
+    }
+}
+
+impl<Mode, T> IndexedIncrementalDecoder<Mode, T>
+where
+    Mode: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
 {
     /// Updates the decoder's state based on the given `a` and `b` pulse train (aka channel) readings,
     /// returning the direction if a change was detected, `None` if no change was detected,
     /// or `Err(_)` if an invalid input (i.e. a counteral "jump") was detected.
     ///
-    /// Upon detection of a raising edge on the `z` pulse train the counter gets reset back to `0`.
+    /// Upon detection of a raising edge on the `z` pulse train the counter gets reset back to `0`,
+    /// or to the decoder's calibration offset (subject to the decoder's `IndexMode`).
+    /// Every detected index latches the counter, and (once synced) counts a revolution
+    /// in the direction of the most recent change.
     ///
     /// Depending on whether it matters why the decoder did not detect a change
     /// (e.g. due to actual lack of change or an erroneous read)
@@ -77,18 +403,257 @@ where
     /// to fall back to `None` in case of `Err(_)`.
     pub fn update(&mut self, a: bool, b: bool, z: bool) -> Result<Option<Change>, Error> {
         let result = self.decoder.update(a, b);
+        self.update_index(result, z)
+    }
+
+    /// Updates the decoder's state like [`update()`](Self::update),
+    /// with the readings taken at timestamp `now` (e.g. in microseconds),
+    /// as required by a [`GlitchFilter::Duration`] glitch filter.
+    pub fn update_at(
+        &mut self,
+        a: bool,
+        b: bool,
+        z: bool,
+        now: u64,
+    ) -> Result<Option<Change>, Error> {
+        let result = self.decoder.update_at(a, b, now);
+        self.update_index(result, z)
+    }
+
+    fn update_index(
+        &mut self,
+        result: Result<Option<Change>, Error>,
+        z: bool,
+    ) -> Result<Option<Change>, Error> {
+        self.is_at_index = self.indexer.update(z);
 
-        if self.indexer.update(z) {
-            self.decoder.set_counter(Zero::zero());
+        if let Some(change) = self.decoder.sub_step() {
+            self.direction = Some(change);
+        }
+
+        if self.is_at_index {
+            let is_first = self.latched.is_none();
+
+            self.latched = Some(self.decoder.counter());
+
+            // Revolutions are counted from the first detected index (or homing) on:
+            if let (true, Some(direction)) = (self.is_synced || !is_first, self.direction) {
+                self.revolutions = self.revolutions.saturating_add(direction as i32);
+            }
+
+            let event = IndexEvent {
+                counter: self.decoder.counter(),
+                is_synced: self.is_synced,
+                is_first,
+            };
+
+            let action = match self.index_behavior {
+                Some(index_behavior) => index_behavior(event),
+                None => self.builtin_index_action(event),
+            };
+
+            // Only corrections reference the counter to the index:
+            match action {
+                IndexAction::Keep => {}
+                IndexAction::Set(counter) => {
+                    self.decoder.set_counter(counter);
+                    self.is_synced = true;
+                }
+                IndexAction::Adjust(delta) => {
+                    self.decoder.adjust_counter(delta);
+                    self.is_synced = true;
+                }
+            }
         }
 
         result
     }
 
+    /// Updates the decoder's state with the given batch of `a`, `b` and `z` pulse train readings
+    /// (e.g. captured via DMA), returning a summary of the net movement and any invalid transitions.
+    ///
+    /// The net movement is unaffected by index corrections of the counter.
+    /// Decoding continues past invalid transitions, just like repeated calls of `update()` would.
+    pub fn update_batch<I>(&mut self, samples: I) -> BatchReport<T>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(bool, bool, bool)>,
+    {
+        let mut report = BatchReport::default();
+
+        for (index, sample) in samples.into_iter().enumerate() {
+            let (a, b, z) = *sample.borrow();
+            report.record(index, self.update(a, b, z));
+        }
+
+        report
+    }
+
+    /// Returns the action of the built-in behavior (i.e. subject to the decoder's `IndexMode` and index marks).
+    fn builtin_index_action(&self, event: IndexEvent<T>) -> IndexAction<T> {
+        let should_reset = match self.index_mode {
+            IndexMode::Always => true,
+            IndexMode::Once => !event.is_synced,
+            IndexMode::Latch => false,
+        };
+
+        if !should_reset {
+            return IndexAction::Keep;
+        }
+
+        match (
+            event.is_synced,
+            self.index_marks,
+            self.pulses_per_revolution,
+        ) {
+            (true, Some(marks), Some(pulses_per_revolution)) => IndexAction::Set((marks.nearest)(
+                event.counter,
+                pulses_per_revolution,
+                marks.count,
+                self.origin(),
+            )),
+            _ => IndexAction::Set(self.origin()),
+        }
+    }
+
+    /// Returns the counter assigned at the index (i.e. the decoder's calibration offset, or `0`).
+    fn origin(&self) -> T {
+        self.index_offset.unwrap_or_else(Zero::zero)
+    }
+
+    /// Homes the decoder at its current position, treating it as the index
+    /// (e.g. once an index has been detected, regardless of the decoder's `IndexMode`):
+    /// setting the counter to the decoder's calibration offset (or `0`),
+    /// resetting the number of revolutions and marking the decoder as synced.
+    pub fn home(&mut self) {
+        self.decoder.set_counter(self.origin());
+        self.revolutions = 0;
+        self.is_synced = true;
+    }
+
+    /// Resets the decoder's decoding state (e.g. after an invalid transition),
+    /// keeping its counter, saturation flag, most recent reading and index state.
+    pub fn reset_state(&mut self) {
+        self.decoder.reset_state();
+    }
+
     /// Resets the decoder to its initial state and its counter counter back to `0`.
+    ///
+    /// The decoder is considered not synced to the index afterwards.
     pub fn reset(&mut self) {
         self.decoder.reset();
         self.indexer.reset();
+        self.is_synced = false;
+        self.is_at_index = false;
+        self.latched = None;
+        self.revolutions = 0;
+        self.direction = None;
+    }
+
+    /// Returns a plain-data snapshot of the decoder's current state.
+    pub fn state(&self) -> IndexedDecoderState<Mode, T> {
+        IndexedDecoderState {
+            decoder: self.decoder.state(),
+            z: self.indexer.z(),
+            is_synced: self.is_synced,
+            is_at_index: self.is_at_index,
+            latched: self.latched,
+            revolutions: self.revolutions,
+            direction: self.direction,
+        }
+    }
+
+    /// Sets the decoder's state to the given snapshot.
+    pub fn set_state(&mut self, state: IndexedDecoderState<Mode, T>) {
+        self.decoder.set_state(state.decoder);
+        self.indexer.set_z(state.z);
+        self.is_synced = state.is_synced;
+        self.is_at_index = state.is_at_index;
+        self.latched = state.latched;
+        self.revolutions = state.revolutions;
+        self.direction = state.direction;
+    }
+
+    /// Returns a snapshot of the decoder's current state,
+    /// which the decoder can later be reverted to via [`rollback()`](Self::rollback).
+    ///
+    /// This allows for speculatively decoding hypothetical sample sequences
+    /// (e.g. in lock-step simulations, or predictive control code) and reverting afterwards.
+    pub fn checkpoint(&self) -> IndexedDecoderState<Mode, T> {
+        self.state()
+    }
+
+    /// Reverts the decoder to the state of the given `checkpoint`.
+    pub fn rollback(&mut self, checkpoint: IndexedDecoderState<Mode, T>) {
+        self.set_state(checkpoint);
+    }
+
+    /// Returns a compact snapshot of the decoder's position (e.g. for persisting it across resets),
+    /// which the decoder can later be rehydrated from via [`restore()`](Self::restore).
+    pub fn snapshot(&self) -> Snapshot<T> {
+        self.decoder.snapshot()
+    }
+
+    /// Restores the decoder's counter and phase from the given `snapshot`,
+    /// like [`IncrementalDecoder::restore()`], keeping the decoder's index state as is.
+    pub fn restore(&mut self, snapshot: Snapshot<T>) -> Result<(), SnapshotError> {
+        self.decoder.restore(snapshot)
+    }
+
+    /// Returns `true` if the decoder's counter has been referenced to an index
+    /// (i.e. set or adjusted upon detecting one, or homed) since
+    /// the decoder's creation (or its most recent reset), otherwise `false`.
+    ///
+    /// Indices merely latched (e.g. in `IndexMode::Latch`) leave the decoder unsynced.
+    pub fn is_index_synced(&self) -> bool {
+        self.is_synced
+    }
+
+    /// Returns `true` if the most recent update detected an index, otherwise `false`.
+    ///
+    /// This reports every detected index, regardless of the decoder's `IndexMode`.
+    pub fn is_at_index(&self) -> bool {
+        self.is_at_index
+    }
+
+    /// Returns the counter latched at the most recent index (i.e. before any correction),
+    /// or `None` if no index has been detected yet.
+    ///
+    /// This latches every detected index, regardless of the decoder's `IndexMode`.
+    pub fn latched_counter(&self) -> Option<T> {
+        self.latched
+    }
+
+    /// Returns the number of revolutions counted since the first detected index
+    /// (or the most recent homing), with positive values corresponding to `Change::Positive`.
+    pub fn revolutions(&self) -> i32 {
+        self.revolutions
+    }
+
+    /// Returns `true` if the decoder's counter has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the decoder's creation (or its most recent reset), otherwise `false`.
+    ///
+    /// Once saturated, the counter stays pegged at its bound while moving further beyond it,
+    /// hence no longer reflecting the actual position.
+    /// The flag is sticky, staying set until cleared via [`clear_saturated()`](Self::clear_saturated),
+    /// or [`reset()`](Self::reset).
+    pub fn is_saturated(&self) -> bool {
+        self.decoder.is_saturated()
+    }
+
+    /// Clears the decoder's sticky saturation flag.
+    pub fn clear_saturated(&mut self) {
+        self.decoder.clear_saturated();
+    }
+
+    /// Returns the raw quarter-step change detected by the most recent update,
+    /// or `None` if no quarter-step change was detected.
+    ///
+    /// This reports every quarter-step change, regardless of the decoder's step-mode.
+    /// Unlike for [`IncrementalDecoder`], sub-steps are always tracked,
+    /// as they provide the direction for counting revolutions.
+    pub fn sub_step(&self) -> Option<Change> {
+        self.decoder.sub_step()
     }
 
     /// Returns the decoder's counter counter relative to its initial counter in number of cycles.
@@ -99,6 +664,32 @@ where
         self.decoder.counter()
     }
 
+    /// Returns the decoder's counter relative to the index,
+    /// or `None` if the counter has not been referenced to an index yet (see `is_index_synced()`).
+    ///
+    /// Prefer this over `counter()` whenever the counter is only meaningful
+    /// in relation to a physical reference (i.e. the index).
+    pub fn synced_counter(&self) -> Option<T> {
+        self.is_synced.then(|| self.decoder.counter())
+    }
+
+    /// Returns the decoder's counter within the current revolution, within `[0, PPR)`,
+    /// or `None` if the counter has not been referenced to an index yet, or no PPR has been provided.
+    pub fn counter_in_rev(&self) -> Option<T>
+    where
+        T: Euclid,
+    {
+        let pulses_per_revolution = self.pulses_per_revolution?;
+        let counter = self.synced_counter()?;
+
+        Some(counter.rem_euclid(&pulses_per_revolution))
+    }
+
+    /// Returns the decoder's number of pulses per revolution (PPR), if provided.
+    pub fn pulses_per_revolution(&self) -> Option<T> {
+        self.pulses_per_revolution
+    }
+
     /// Sets the decoder's counter.
     pub fn set_counter(&mut self, counter: T) {
         self.decoder.set_counter(counter);
@@ -145,4 +736,296 @@ mod tests {
             assert_eq!(decoder.counter(), expected_counter);
         }
     }
+
+    #[test]
+    fn checkpoint() {
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> = Default::default();
+
+        assert_eq!(decoder.update(false, true, false), Ok(None));
+        assert_eq!(
+            decoder.update(false, false, false),
+            Ok(Some(Change::Positive))
+        );
+
+        let checkpoint = decoder.checkpoint();
+
+        assert_eq!(decoder.update(true, false, true), Ok(None));
+        assert!(decoder.is_index_synced());
+        assert_eq!(decoder.counter(), 0);
+
+        decoder.rollback(checkpoint);
+
+        assert!(!decoder.is_index_synced());
+        assert!(!decoder.is_at_index());
+        assert_eq!(decoder.counter(), 1);
+        assert_eq!(decoder.checkpoint(), checkpoint);
+    }
+
+    #[test]
+    fn state() {
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> = Default::default();
+        assert_eq!(decoder.state(), IndexedDecoderState::default());
+
+        // Start mid-sequence, synced to the index:
+        let state = IndexedDecoderState::default()
+            .with_counter(41)
+            .with_index_synced(true);
+        decoder.set_state(state);
+
+        assert_eq!(decoder.synced_counter(), Some(41));
+        assert_eq!(decoder.update(false, true, false), Ok(None));
+        assert_eq!(
+            decoder.update(false, false, false),
+            Ok(Some(Change::Positive))
+        );
+        assert_eq!(decoder.state().counter(), 42);
+
+        // Transfer the state into another decoder:
+        let mut other: IndexedIncrementalDecoder<HalfStep> = Default::default();
+        other.set_state(decoder.state());
+
+        assert_eq!(
+            other.update(true, true, false),
+            decoder.update(true, true, false)
+        );
+        assert_eq!(other.state(), decoder.state());
+    }
+
+    #[test]
+    fn index_behavior() {
+        fn adjust(event: IndexEvent<i32>) -> IndexAction<i32> {
+            match event.is_first {
+                true => IndexAction::Keep,
+                false => IndexAction::Adjust(-event.counter.signum()),
+            }
+        }
+
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> =
+            IndexedIncrementalDecoder::default().with_index_behavior(adjust);
+        decoder.set_counter(3);
+
+        // The first index is merely logged:
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert!(decoder.is_at_index());
+        assert_eq!(decoder.counter(), 3);
+
+        // Subsequent indices nudge the counter towards zero:
+        assert_eq!(decoder.update(true, true, false), Ok(None));
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.counter(), 2);
+
+        decoder.set_index_behavior(None);
+
+        assert_eq!(decoder.update(true, true, false), Ok(None));
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.counter(), 0);
+    }
+
+    #[test]
+    fn synced_counter() {
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> = Default::default();
+
+        assert_eq!(decoder.update(false, true, false), Ok(None));
+        assert_eq!(
+            decoder.update(false, false, false),
+            Ok(Some(Change::Positive))
+        );
+        assert_eq!(decoder.counter(), 1);
+        assert_eq!(decoder.synced_counter(), None);
+
+        assert_eq!(decoder.update(true, false, true), Ok(None));
+        assert_eq!(decoder.synced_counter(), Some(0));
+
+        assert_eq!(
+            decoder.update(true, true, false),
+            Ok(Some(Change::Positive))
+        );
+        assert_eq!(decoder.synced_counter(), Some(1));
+
+        decoder.reset();
+        assert_eq!(decoder.synced_counter(), None);
+    }
+
+    #[test]
+    fn index_mode_once() {
+        let a: Vec<bool> = vec![false, false, true, true, false, false, true, true];
+        let b: Vec<bool> = vec![true, false, false, true, true, false, false, true];
+        let z: Vec<bool> = vec![false, true, false, false, true, false, false, false];
+
+        let pulse_trains = a.into_iter().zip(b).zip(z);
+
+        let counters: Vec<i32> = vec![0, 0, 0, 1, 1, 2, 2, 3];
+        let indices: Vec<bool> = vec![false, true, false, false, true, false, false, false];
+
+        let expected = counters.into_iter().zip(indices);
+
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> =
+            IndexedIncrementalDecoder::default().with_index_mode(IndexMode::Once);
+
+        assert!(!decoder.is_index_synced());
+
+        for (input, expected) in pulse_trains.zip(expected) {
+            let ((a, b), z) = input;
+            let (expected_counter, expected_index) = expected;
+
+            decoder.update(a, b, z).unwrap();
+
+            assert_eq!(decoder.counter(), expected_counter);
+            assert_eq!(decoder.is_at_index(), expected_index);
+        }
+
+        assert!(decoder.is_index_synced());
+    }
+
+    #[test]
+    fn counter_in_rev() {
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> = IndexedIncrementalDecoder::default()
+            .with_index_mode(IndexMode::Once)
+            .with_pulses_per_revolution(2);
+
+        assert_eq!(decoder.counter_in_rev(), None);
+
+        // Index, then forwards by 3 pulses:
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.counter_in_rev(), Some(0));
+
+        let forwards = [(false, true), (false, false), (true, false), (true, true)];
+        for (a, b) in forwards.into_iter().cycle().take(6) {
+            decoder.update(a, b, false).unwrap();
+        }
+        assert_eq!(decoder.counter(), 3);
+        assert_eq!(decoder.counter_in_rev(), Some(1));
+
+        // Backwards by 4 pulses:
+        let backwards = [(false, true), (true, true), (true, false), (false, false)];
+        for (a, b) in backwards.into_iter().cycle().take(8) {
+            decoder.update(a, b, false).unwrap();
+        }
+        assert_eq!(decoder.counter(), -1);
+        assert_eq!(decoder.counter_in_rev(), Some(1));
+    }
+
+    #[test]
+    fn nearest_index_mark() {
+        assert_eq!(super::nearest_index_mark(0, 100, 2, 0), 0);
+        assert_eq!(super::nearest_index_mark(48, 100, 2, 0), 50);
+        assert_eq!(super::nearest_index_mark(53, 100, 2, 0), 50);
+        assert_eq!(super::nearest_index_mark(97, 100, 2, 0), 0);
+        assert_eq!(super::nearest_index_mark(-3, 100, 2, 0), 0);
+        assert_eq!(super::nearest_index_mark(-48, 100, 2, 0), 50);
+        assert_eq!(super::nearest_index_mark(151, 100, 2, 0), 50);
+        assert_eq!(super::nearest_index_mark(26, 100, 4, 0), 25);
+
+        // Offset marks:
+        assert_eq!(super::nearest_index_mark(13, 100, 2, 10), 10);
+        assert_eq!(super::nearest_index_mark(57, 100, 2, 10), 60);
+        assert_eq!(super::nearest_index_mark(7, 100, 2, 10), 10);
+    }
+
+    #[test]
+    fn index_mode_latch() {
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> = IndexedIncrementalDecoder::default()
+            .with_index_mode(IndexMode::Latch)
+            .with_pulses_per_revolution(4);
+        decoder.set_counter(5);
+
+        // The first index latches without counting a revolution,
+        // nor referencing the counter to the index:
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.latched_counter(), Some(5));
+        assert_eq!(decoder.revolutions(), 0);
+        assert_eq!(decoder.counter(), 5);
+        assert!(!decoder.is_index_synced());
+        assert_eq!(decoder.synced_counter(), None);
+        assert_eq!(decoder.counter_in_rev(), None);
+
+        // Forwards by 2 pulses, then index:
+        let forwards = [(false, true), (false, false), (true, false), (true, true)];
+        for (a, b) in forwards {
+            decoder.update(a, b, false).unwrap();
+        }
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.latched_counter(), Some(7));
+        assert_eq!(decoder.revolutions(), 1);
+        assert_eq!(decoder.counter(), 7);
+
+        // Backwards by 2 pulses, then index:
+        let backwards = [(true, false), (false, false), (false, true), (true, true)];
+        for (a, b) in backwards {
+            decoder.update(a, b, false).unwrap();
+        }
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.latched_counter(), Some(5));
+        assert_eq!(decoder.revolutions(), 0);
+        assert!(!decoder.is_index_synced());
+
+        // Homing references the counter:
+        decoder.home();
+        assert!(decoder.is_index_synced());
+        assert_eq!(decoder.synced_counter(), Some(0));
+
+        decoder.reset();
+        assert_eq!(decoder.latched_counter(), None);
+    }
+
+    #[test]
+    fn index_behavior_keep() {
+        fn keep(_: IndexEvent<i32>) -> IndexAction<i32> {
+            IndexAction::Keep
+        }
+
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> =
+            IndexedIncrementalDecoder::default().with_index_behavior(keep);
+        decoder.set_counter(5);
+
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert!(decoder.is_at_index());
+        assert!(!decoder.is_index_synced());
+        assert_eq!(decoder.synced_counter(), None);
+    }
+
+    #[test]
+    fn index_offset() {
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> =
+            IndexedIncrementalDecoder::default().with_index_offset(10);
+
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.counter(), 10);
+
+        let forwards = [(false, true), (false, false), (true, false), (true, true)];
+        for (a, b) in forwards {
+            decoder.update(a, b, false).unwrap();
+        }
+        assert_eq!(decoder.counter(), 12);
+
+        decoder.home();
+        assert_eq!(decoder.counter(), 10);
+        assert_eq!(decoder.revolutions(), 0);
+        assert!(decoder.is_index_synced());
+    }
+
+    #[test]
+    fn index_marks() {
+        let mut decoder: IndexedIncrementalDecoder<HalfStep> = IndexedIncrementalDecoder::default()
+            .with_pulses_per_revolution(8)
+            .with_index_marks(2);
+
+        assert_eq!(decoder.index_marks(), 2);
+
+        // First index becomes the origin:
+        decoder.set_counter(3);
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.counter(), 0);
+
+        // Subsequent indices correct to the nearest mark (with drift of one pulse):
+        decoder.update(true, true, false).unwrap();
+        decoder.set_counter(5);
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.counter(), 4);
+
+        decoder.update(true, true, false).unwrap();
+        decoder.set_counter(7);
+        assert_eq!(decoder.update(true, true, true), Ok(None));
+        assert_eq!(decoder.counter(), 0);
+    }
 }
@@ -0,0 +1,129 @@
+//! Step/direction-based decoder (e.g. of motion controllers, or stepper drivers).
+
+use num_traits::{One, SaturatingAdd, Zero};
+
+use crate::Change;
+
+/// A decoder of step/direction signals (e.g. as output by motion controllers),
+/// detecting a change for every rising edge of the `step` signal,
+/// in the direction indicated by the level of the `dir` signal (high: positive, low: negative).
+///
+/// ```plain
+///                ┌─┐     ┌─┐     ┌─┐     ┌─┐             high
+///         Step   │ │     │ │     │ │     │ │                 
+///              ──┘ └─────┘ └─────┘ └─────┘ └─────────── low  
+///              ───────────────────┐                      high
+///         Dir                     │                          
+///                                 └──────────────────── low  
+/// Change:        +       +       -       -                   
+/// ```
+#[derive(Debug)]
+pub struct StepDirDecoder<T = i32> {
+    last: Option<bool>,
+    counter: T,
+    is_saturated: bool,
+}
+
+impl<T> Default for StepDirDecoder<T>
+where
+    T: Zero,
+{
+    fn default() -> Self {
+        Self {
+            last: None,
+            counter: Zero::zero(),
+            is_saturated: false,
+        }
+    }
+}
+
+impl<T> StepDirDecoder<T>
+where
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
+{
+    /// Updates the decoder's state based on the given `step` and `dir` signal readings,
+    /// returning the direction if a change (i.e. a rising edge of `step`) was detected,
+    /// or `None` if no change was detected.
+    ///
+    /// The first update only captures the initial level of `step`.
+    pub fn update(&mut self, step: bool, dir: bool) -> Option<Change> {
+        let last = self.last.replace(step);
+
+        if last != Some(false) || !step {
+            return None;
+        }
+
+        let change = match dir {
+            true => Change::Positive,
+            false => Change::Negative,
+        };
+
+        self.adjust_counter(change);
+
+        Some(change)
+    }
+
+    /// Resets the decoder to its initial state and its counter back to `0`.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.counter = Zero::zero();
+        self.is_saturated = false;
+    }
+
+    /// Returns `true` if the decoder's counter has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the decoder's creation (or its most recent reset), otherwise `false`.
+    pub fn is_saturated(&self) -> bool {
+        self.is_saturated
+    }
+
+    /// Clears the decoder's sticky saturation flag.
+    pub fn clear_saturated(&mut self) {
+        self.is_saturated = false;
+    }
+
+    /// Returns the decoder's counter relative to its initial counter in number of pulses.
+    pub fn counter(&self) -> T {
+        self.counter
+    }
+
+    /// Sets the decoder's counter.
+    pub fn set_counter(&mut self, counter: T) {
+        self.counter = counter;
+    }
+
+    fn adjust_counter(&mut self, change: Change) {
+        let delta: T = (change as i8).into();
+        let counter = self.counter.saturating_add(&delta);
+        self.is_saturated |= counter == self.counter;
+        self.counter = counter;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update() {
+        let mut decoder: StepDirDecoder = Default::default();
+
+        assert_eq!(decoder.update(false, true), None);
+        assert_eq!(decoder.update(true, true), Some(Change::Positive));
+        assert_eq!(decoder.update(true, true), None);
+        assert_eq!(decoder.update(false, false), None);
+        assert_eq!(decoder.update(true, false), Some(Change::Negative));
+        assert_eq!(decoder.update(false, true), None);
+        assert_eq!(decoder.update(true, true), Some(Change::Positive));
+
+        assert_eq!(decoder.counter(), 1);
+    }
+
+    #[test]
+    fn initial_level() {
+        let mut decoder: StepDirDecoder = Default::default();
+
+        // Starting high is not an edge:
+        assert_eq!(decoder.update(true, true), None);
+        assert_eq!(decoder.counter(), 0);
+    }
+}
@@ -0,0 +1,121 @@
+//! Up/down pulse-based decoder (aka CW/CCW, or count-up/count-down).
+
+use num_traits::{One, SaturatingAdd, Zero};
+
+use crate::Change;
+
+/// A decoder of separate up/down pulse signals (aka CW/CCW, as output by some drives and counters),
+/// detecting a positive change for every rising edge of the `up` signal,
+/// and a negative change for every rising edge of the `down` signal.
+///
+/// Simultaneous rising edges of both signals cancel each other out.
+///
+/// ```plain
+///                ┌─┐     ┌─┐                             high
+///         Up     │ │     │ │                                 
+///              ──┘ └─────┘ └─────────────────────────── low  
+///                                ┌─┐     ┌─┐             high
+///         Down                   │ │     │ │                 
+///              ──────────────────┘ └─────┘ └─────────── low  
+/// Change:        +       +       -       -                   
+/// ```
+#[derive(Debug)]
+pub struct UpDownPulseDecoder<T = i32> {
+    last: Option<(bool, bool)>,
+    counter: T,
+    is_saturated: bool,
+}
+
+impl<T> Default for UpDownPulseDecoder<T>
+where
+    T: Zero,
+{
+    fn default() -> Self {
+        Self {
+            last: None,
+            counter: Zero::zero(),
+            is_saturated: false,
+        }
+    }
+}
+
+impl<T> UpDownPulseDecoder<T>
+where
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
+{
+    /// Updates the decoder's state based on the given `up` and `down` signal readings,
+    /// returning the direction if a change (i.e. a rising edge of either signal) was detected,
+    /// or `None` if no change was detected.
+    ///
+    /// The first update only captures the initial levels of the signals.
+    pub fn update(&mut self, up: bool, down: bool) -> Option<Change> {
+        let (last_up, last_down) = self.last.replace((up, down))?;
+
+        let change = match (up && !last_up, down && !last_down) {
+            (true, false) => Change::Positive,
+            (false, true) => Change::Negative,
+            _ => return None,
+        };
+
+        self.adjust_counter(change);
+
+        Some(change)
+    }
+
+    /// Resets the decoder to its initial state and its counter back to `0`.
+    pub fn reset(&mut self) {
+        self.last = None;
+        self.counter = Zero::zero();
+        self.is_saturated = false;
+    }
+
+    /// Returns `true` if the decoder's counter has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the decoder's creation (or its most recent reset), otherwise `false`.
+    pub fn is_saturated(&self) -> bool {
+        self.is_saturated
+    }
+
+    /// Clears the decoder's sticky saturation flag.
+    pub fn clear_saturated(&mut self) {
+        self.is_saturated = false;
+    }
+
+    /// Returns the decoder's counter relative to its initial counter in number of pulses.
+    pub fn counter(&self) -> T {
+        self.counter
+    }
+
+    /// Sets the decoder's counter.
+    pub fn set_counter(&mut self, counter: T) {
+        self.counter = counter;
+    }
+
+    fn adjust_counter(&mut self, change: Change) {
+        let delta: T = (change as i8).into();
+        let counter = self.counter.saturating_add(&delta);
+        self.is_saturated |= counter == self.counter;
+        self.counter = counter;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn update() {
+        let mut decoder: UpDownPulseDecoder = Default::default();
+
+        assert_eq!(decoder.update(false, false), None);
+        assert_eq!(decoder.update(true, false), Some(Change::Positive));
+        assert_eq!(decoder.update(false, false), None);
+        assert_eq!(decoder.update(true, false), Some(Change::Positive));
+        assert_eq!(decoder.update(false, true), Some(Change::Negative));
+        assert_eq!(decoder.update(false, false), None);
+
+        // Simultaneous edges cancel each other out:
+        assert_eq!(decoder.update(true, true), None);
+
+        assert_eq!(decoder.counter(), 1);
+    }
+}
@@ -0,0 +1,256 @@
+//! Signal-quality diagnostics.
+
+use crate::Change;
+
+/// A diagnostic, indicating a systematic issue with the decoded signal.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Diagnostic {
+    /// The sample rate is too low for the observed edge rate,
+    /// causing edges to get missed (i.e. aliased into "jumps").
+    SampleRateTooLow,
+}
+
+/// A monitor for detecting a sample rate too low for the observed edge rate,
+/// for decoders being updated at a fixed sample rate.
+///
+/// Sporadic jumps (i.e. both channels changing between consecutive samples) are
+/// usually caused by noise, whereas jumps clustered with a high edge rate
+/// indicate that the signal is being undersampled (i.e. violating Nyquist).
+///
+/// The monitor evaluates its observations in windows of a fixed number of samples,
+/// reporting `Diagnostic::SampleRateTooLow` if within a window:
+///
+/// - edges occurred on at least every other sample, and
+/// - at least one in eight edges was part of a jump.
+#[derive(Clone, Debug)]
+pub struct SampleRateMonitor {
+    window: u32,
+    samples: u32,
+    edges: u32,
+    jumps: u32,
+    last_input: Option<(bool, bool)>,
+}
+
+impl SampleRateMonitor {
+    /// Creates a monitor evaluating windows of the given number of samples.
+    pub fn new(window: u32) -> Self {
+        Self {
+            window: window.max(1),
+            samples: 0,
+            edges: 0,
+            jumps: 0,
+            last_input: None,
+        }
+    }
+
+    /// Updates the monitor with the given `a` and `b` pulse train readings,
+    /// returning a diagnostic at the end of a window, if the window indicated an issue.
+    pub fn update(&mut self, a: bool, b: bool) -> Option<Diagnostic> {
+        if let Some((last_a, last_b)) = self.last_input.replace((a, b)) {
+            match ((last_a != a), (last_b != b)) {
+                (true, true) => {
+                    self.edges += 2;
+                    self.jumps += 1;
+                }
+                (true, false) | (false, true) => {
+                    self.edges += 1;
+                }
+                (false, false) => {}
+            }
+        }
+
+        self.samples += 1;
+
+        if self.samples < self.window {
+            return None;
+        }
+
+        let is_edge_rate_high = self.edges * 2 >= self.samples;
+        let are_jumps_frequent = self.jumps > 0 && self.jumps * 2 * 8 >= self.edges;
+
+        self.samples = 0;
+        self.edges = 0;
+        self.jumps = 0;
+
+        (is_edge_rate_high && are_jumps_frequent).then_some(Diagnostic::SampleRateTooLow)
+    }
+
+    /// Resets the monitor to its initial state.
+    pub fn reset(&mut self) {
+        self.samples = 0;
+        self.edges = 0;
+        self.jumps = 0;
+        self.last_input = None;
+    }
+}
+
+/// Statistics of the intervals between consecutive changes, as returned by [`JitterMonitor::stats()`].
+///
+/// All intervals are in ticks of the timestamps provided to the monitor.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct IntervalStats {
+    /// The number of intervals.
+    pub count: u32,
+    /// The shortest interval.
+    pub min: u64,
+    /// The longest interval.
+    pub max: u64,
+    /// The mean interval.
+    pub mean: f32,
+    /// The (population) variance of the intervals.
+    pub variance: f32,
+}
+
+impl IntervalStats {
+    /// Returns the spread between the shortest and the longest interval.
+    pub fn jitter(&self) -> u64 {
+        self.max - self.min
+    }
+}
+
+/// A monitor collecting statistics of the intervals between consecutive changes,
+/// for identifying the signatures of mechanical vibration or electrical noise.
+///
+/// At constant speed the intervals of a clean signal barely vary, whereas vibration
+/// shows up as periodically varying intervals, and noise as sporadic outliers.
+///
+/// Timestamps are provided as monotonic ticks of arbitrary resolution.
+#[derive(Clone, Default, Debug)]
+pub struct JitterMonitor {
+    last_timestamp: Option<u64>,
+    count: u32,
+    min: u64,
+    max: u64,
+    mean: f32,
+    m2: f32,
+}
+
+impl JitterMonitor {
+    /// Creates a monitor without any observed intervals.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Updates the monitor with the change (if any) detected at the given `timestamp`.
+    pub fn update(&mut self, timestamp: u64, change: Option<Change>) {
+        if change.is_none() {
+            return;
+        }
+
+        let Some(last_timestamp) = self.last_timestamp.replace(timestamp) else {
+            return;
+        };
+
+        let interval = timestamp.wrapping_sub(last_timestamp);
+
+        if self.count == 0 {
+            self.min = interval;
+            self.max = interval;
+        } else {
+            self.min = self.min.min(interval);
+            self.max = self.max.max(interval);
+        }
+
+        // Welford's online algorithm:
+        self.count = self.count.saturating_add(1);
+        let delta = interval as f32 - self.mean;
+        self.mean += delta / self.count as f32;
+        self.m2 += delta * (interval as f32 - self.mean);
+    }
+
+    /// Returns the statistics of the intervals observed so far,
+    /// or `None` until at least two changes have been detected.
+    pub fn stats(&self) -> Option<IntervalStats> {
+        (self.count > 0).then(|| IntervalStats {
+            count: self.count,
+            min: self.min,
+            max: self.max,
+            mean: self.mean,
+            variance: self.m2 / self.count as f32,
+        })
+    }
+
+    /// Resets the monitor to its initial state.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CYCLE: [(bool, bool); 4] = [(false, true), (false, false), (true, false), (true, true)];
+
+    #[test]
+    fn adequate_sample_rate() {
+        let mut monitor = SampleRateMonitor::new(16);
+
+        // Each state gets sampled twice:
+        let samples = CYCLE.into_iter().flat_map(|input| [input, input]);
+
+        for (a, b) in samples.cycle().take(64) {
+            assert_eq!(monitor.update(a, b), None);
+        }
+    }
+
+    #[test]
+    fn sporadic_noise() {
+        let mut monitor = SampleRateMonitor::new(64);
+
+        let mut samples: Vec<(bool, bool)> = vec![(true, true); 64];
+        samples[31] = (false, false);
+
+        let diagnostics: Vec<_> = samples
+            .into_iter()
+            .filter_map(|(a, b)| monitor.update(a, b))
+            .collect();
+
+        assert_eq!(diagnostics, vec![]);
+    }
+
+    #[test]
+    fn sample_rate_too_low() {
+        let mut monitor = SampleRateMonitor::new(16);
+
+        // Every other state gets skipped:
+        let samples = CYCLE.into_iter().step_by(2);
+
+        let diagnostics: Vec<_> = samples
+            .cycle()
+            .take(32)
+            .filter_map(|(a, b)| monitor.update(a, b))
+            .collect();
+
+        assert_eq!(diagnostics, vec![Diagnostic::SampleRateTooLow; 2]);
+    }
+
+    #[test]
+    fn jitter() {
+        let mut monitor = JitterMonitor::new();
+
+        monitor.update(0, Some(Change::Positive));
+        assert_eq!(monitor.stats(), None);
+
+        for (timestamp, change) in [
+            (5, None),
+            (10, Some(Change::Positive)),
+            (16, Some(Change::Positive)),
+            (30, Some(Change::Negative)),
+            (34, Some(Change::Negative)),
+        ] {
+            monitor.update(timestamp, change);
+        }
+
+        // Intervals: 10, 6, 14, 4
+        let stats = monitor.stats().unwrap();
+        assert_eq!(stats.count, 4);
+        assert_eq!((stats.min, stats.max), (4, 14));
+        assert_eq!(stats.jitter(), 10);
+        assert_eq!(stats.mean, 8.5);
+        assert_eq!(stats.variance, 14.75);
+
+        monitor.reset();
+        assert_eq!(monitor.stats(), None);
+    }
+}
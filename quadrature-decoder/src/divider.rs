@@ -0,0 +1,149 @@
+//! Division of a decoder's position into periodic events.
+
+use core::ops::Neg;
+
+use num_traits::{One, WrappingAdd, WrappingSub, Zero};
+
+use crate::Change;
+
+/// An event emitted by a [`Divider`] every `N` net counts of travel.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Tick {
+    /// The direction of travel that caused the tick.
+    pub change: Change,
+}
+
+/// A divider, emitting a [`Tick`] every `N` net counts of travel
+/// (e.g. for triggering cameras, strobes, or sampling synchronized to travel distance).
+///
+/// Ticks are emitted relative to the first observed position, every time
+/// the position moved `N` counts away from the previous tick's position.
+/// Jittering back and forth within those `N` counts thus does not emit any ticks.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Divider<T = i32> {
+    divisor: T,
+    anchor: Option<T>,
+}
+
+impl<T> Divider<T>
+where
+    T: Copy + PartialOrd + Zero + One + Neg<Output = T> + WrappingAdd + WrappingSub,
+{
+    /// Creates a divider emitting a tick every `divisor` net counts.
+    ///
+    /// Divisors below `1` are clamped to `1`.
+    pub fn new(divisor: T) -> Self {
+        let divisor = if divisor < T::one() {
+            T::one()
+        } else {
+            divisor
+        };
+
+        Self {
+            divisor,
+            anchor: None,
+        }
+    }
+
+    /// Returns the divider's divisor.
+    pub fn divisor(&self) -> T {
+        self.divisor
+    }
+
+    /// Updates the divider with the given `position`,
+    /// returning a tick if the position moved at least `N` counts away
+    /// from the previous tick's position, otherwise `None`.
+    ///
+    /// At most a single tick is emitted per update, with any further ticks
+    /// (e.g. caused by the position jumping by more than `N` counts)
+    /// being emitted by subsequent updates.
+    pub fn update(&mut self, position: T) -> Option<Tick> {
+        let anchor = *self.anchor.get_or_insert(position);
+        let delta = position.wrapping_sub(&anchor);
+
+        let change = if delta >= self.divisor {
+            Change::Positive
+        } else if delta <= -self.divisor {
+            Change::Negative
+        } else {
+            return None;
+        };
+
+        self.anchor = Some(match change {
+            Change::Positive => anchor.wrapping_add(&self.divisor),
+            Change::Negative => anchor.wrapping_sub(&self.divisor),
+        });
+
+        Some(Tick { change })
+    }
+
+    /// Resets the divider, making the next observed position the new reference.
+    pub fn reset(&mut self) {
+        self.anchor = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ticks() {
+        let mut divider: Divider<i32> = Divider::new(3);
+
+        let positions = [0, 1, 2, 3, 2, 3, 4, 5, 6, 5, 4, 3, 2, 1, 0];
+        let ticks: Vec<_> = positions
+            .into_iter()
+            .map(|position| divider.update(position).map(|tick| tick.change))
+            .collect();
+
+        assert_eq!(
+            ticks,
+            [
+                None,
+                None,
+                None,
+                Some(Change::Positive),
+                None,
+                None,
+                None,
+                None,
+                Some(Change::Positive),
+                None,
+                None,
+                Some(Change::Negative),
+                None,
+                None,
+                Some(Change::Negative),
+            ]
+        );
+    }
+
+    #[test]
+    fn jump() {
+        let mut divider: Divider<i32> = Divider::new(2);
+
+        assert_eq!(divider.update(0), None);
+        assert_eq!(
+            divider.update(5).map(|tick| tick.change),
+            Some(Change::Positive)
+        );
+        assert_eq!(
+            divider.update(5).map(|tick| tick.change),
+            Some(Change::Positive)
+        );
+        assert_eq!(divider.update(5), None);
+    }
+
+    #[test]
+    fn wrapping() {
+        let mut divider: Divider<i8> = Divider::new(2);
+
+        assert_eq!(divider.update(i8::MAX), None);
+        assert_eq!(divider.update(i8::MIN), None);
+        assert_eq!(
+            divider.update(i8::MIN + 1).map(|tick| tick.change),
+            Some(Change::Positive)
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! Glitch filtering of samples (e.g. of bouncy mechanical encoders).
+
+/// A glitch filter, only passing on a changed sample once it has been stable
+/// for a number of consecutive samples, or for a duration,
+/// rejecting noisy edges before they reach a decoder's state machine.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GlitchFilter {
+    /// Stable after the given number of consecutive identical samples.
+    Samples(u16),
+    /// Stable after the given duration (in ticks, as provided with the samples) without changes.
+    ///
+    /// Samples provided without a timestamp pass the filter right away.
+    Duration(u64),
+}
+
+/// The pending state of a glitch filter (excluding its configuration),
+/// as captured by [`Deglitcher::state()`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct DeglitcherState<S> {
+    stable: Option<S>,
+    candidate: Option<(S, u16, Option<u64>)>,
+    glitches: u32,
+}
+
+/// The state of a glitch filter.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct Deglitcher<S> {
+    filter: GlitchFilter,
+    stable: Option<S>,
+    // The pending sample, with its number of occurrences and first timestamp:
+    candidate: Option<(S, u16, Option<u64>)>,
+    glitches: u32,
+}
+
+impl<S> Deglitcher<S>
+where
+    S: Copy + PartialEq,
+{
+    pub(crate) fn new(filter: GlitchFilter) -> Self {
+        Self {
+            filter,
+            stable: None,
+            candidate: None,
+            glitches: 0,
+        }
+    }
+
+    pub(crate) fn filter(&self) -> GlitchFilter {
+        self.filter
+    }
+
+    /// Returns the number of rejected glitches (i.e. abandoned pending samples).
+    pub(crate) fn glitches(&self) -> u32 {
+        self.glitches
+    }
+
+    pub(crate) fn clear_glitches(&mut self) {
+        self.glitches = 0;
+    }
+
+    pub(crate) fn state(&self) -> DeglitcherState<S> {
+        DeglitcherState {
+            stable: self.stable,
+            candidate: self.candidate,
+            glitches: self.glitches,
+        }
+    }
+
+    pub(crate) fn set_state(&mut self, state: DeglitcherState<S>) {
+        self.stable = state.stable;
+        self.candidate = state.candidate;
+        self.glitches = state.glitches;
+    }
+
+    /// Filters the given `sample`, taken at timestamp `now` (if provided),
+    /// returning the stable sample, or `None` if the sample is pending.
+    pub(crate) fn update(&mut self, sample: S, now: Option<u64>) -> Option<S> {
+        let Some(stable) = self.stable else {
+            self.stable = Some(sample);
+            return Some(sample);
+        };
+
+        if sample == stable {
+            if self.candidate.take().is_some() {
+                self.glitches = self.glitches.saturating_add(1);
+            }
+            return Some(stable);
+        }
+
+        let (count, since) = match self.candidate {
+            Some((candidate, count, since)) if candidate == sample => {
+                (count.saturating_add(1), since)
+            }
+            Some(_) => {
+                self.glitches = self.glitches.saturating_add(1);
+                (1, now)
+            }
+            None => (1, now),
+        };
+
+        let is_stable = match (self.filter, now, since) {
+            (GlitchFilter::Samples(required), _, _) => count >= required,
+            (GlitchFilter::Duration(required), Some(now), Some(since)) => {
+                now.wrapping_sub(since) >= required
+            }
+            (GlitchFilter::Duration(_), _, _) => now.is_none(),
+        };
+
+        if is_stable {
+            self.stable = Some(sample);
+            self.candidate = None;
+            Some(sample)
+        } else {
+            self.candidate = Some((sample, count, since));
+            None
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.stable = None;
+        self.candidate = None;
+        self.glitches = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples() {
+        let mut deglitcher = Deglitcher::new(GlitchFilter::Samples(3));
+
+        assert_eq!(deglitcher.update(false, None), Some(false));
+
+        // A short glitch gets rejected:
+        assert_eq!(deglitcher.update(true, None), None);
+        assert_eq!(deglitcher.update(true, None), None);
+        assert_eq!(deglitcher.update(false, None), Some(false));
+        assert_eq!(deglitcher.glitches(), 1);
+
+        // A stable change passes:
+        assert_eq!(deglitcher.update(true, None), None);
+        assert_eq!(deglitcher.update(true, None), None);
+        assert_eq!(deglitcher.update(true, None), Some(true));
+        assert_eq!(deglitcher.update(true, None), Some(true));
+    }
+
+    #[test]
+    fn duration() {
+        let mut deglitcher = Deglitcher::new(GlitchFilter::Duration(10));
+
+        assert_eq!(deglitcher.update(false, Some(0)), Some(false));
+
+        assert_eq!(deglitcher.update(true, Some(5)), None);
+        assert_eq!(deglitcher.update(false, Some(8)), Some(false));
+
+        assert_eq!(deglitcher.update(true, Some(20)), None);
+        assert_eq!(deglitcher.update(true, Some(25)), None);
+        assert_eq!(deglitcher.update(true, Some(30)), Some(true));
+
+        // Samples without timestamps pass right away:
+        assert_eq!(deglitcher.update(false, None), Some(false));
+
+        deglitcher.reset();
+        assert_eq!(deglitcher.update(true, Some(40)), Some(true));
+    }
+
+    #[test]
+    fn decoder() {
+        use crate::{Change, IncrementalDecoder, QuadStep};
+
+        let mut decoder: IncrementalDecoder<QuadStep> =
+            IncrementalDecoder::default().with_glitch_filter(GlitchFilter::Samples(2));
+        assert_eq!(decoder.glitch_filter(), Some(GlitchFilter::Samples(2)));
+
+        assert_eq!(decoder.update(true, true), Ok(None));
+
+        // A bouncing edge gets rejected:
+        assert_eq!(decoder.update(false, true), Ok(None));
+        assert_eq!(decoder.update(true, true), Ok(None));
+        assert_eq!(decoder.counter(), 0);
+        assert_eq!(decoder.glitch_count(), 1);
+
+        // A stable edge passes:
+        assert_eq!(decoder.update(false, true), Ok(None));
+        assert_eq!(decoder.update(false, true), Ok(Some(Change::Positive)));
+        assert_eq!(decoder.counter(), 1);
+    }
+
+    #[test]
+    fn checkpoint() {
+        use crate::{Change, IncrementalDecoder, QuadStep};
+
+        let mut decoder: IncrementalDecoder<QuadStep> =
+            IncrementalDecoder::default().with_glitch_filter(GlitchFilter::Samples(2));
+
+        assert_eq!(decoder.update(true, true), Ok(None));
+        assert_eq!(decoder.update(false, true), Ok(None));
+
+        let checkpoint = decoder.checkpoint();
+
+        // Speculatively bouncing back, rejecting the pending edge:
+        assert_eq!(decoder.update(true, true), Ok(None));
+        assert_eq!(decoder.glitch_count(), 1);
+
+        decoder.rollback(checkpoint);
+        assert_eq!(decoder.glitch_count(), 0);
+
+        // The pending edge passes, as if the bounce never happened:
+        assert_eq!(decoder.update(false, true), Ok(Some(Change::Positive)));
+        assert_eq!(decoder.counter(), 1);
+
+        // A snapshot without glitch filter state resets the filter:
+        decoder.set_state(Default::default());
+        assert_eq!(decoder.update(true, true), Ok(None));
+        assert_eq!(decoder.update(false, true), Ok(None));
+        assert_eq!(decoder.counter(), 0);
+    }
+}
@@ -0,0 +1,116 @@
+//! Emulation of hall-sensor (i.e. UVW) commutation signals.
+
+use core::ops::{Div, Mul};
+
+use num_traits::Euclid;
+
+/// The number of distinct hall states per electrical revolution.
+const SECTORS_PER_ELECTRICAL_REVOLUTION: i8 = 6;
+
+/// The hall states of a 120°-spaced sensor arrangement, per 60° sector.
+const SECTORS: [HallState; 6] = [
+    HallState::new(true, false, true),
+    HallState::new(true, false, false),
+    HallState::new(true, true, false),
+    HallState::new(false, true, false),
+    HallState::new(false, true, true),
+    HallState::new(false, false, true),
+];
+
+/// The state of the three hall-sensor signals `u`, `v` and `w`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct HallState {
+    /// The `u` signal.
+    pub u: bool,
+    /// The `v` signal.
+    pub v: bool,
+    /// The `w` signal.
+    pub w: bool,
+}
+
+impl HallState {
+    /// Creates a hall state from the given `u`, `v` and `w` signals.
+    pub const fn new(u: bool, v: bool, w: bool) -> Self {
+        Self { u, v, w }
+    }
+
+    /// Returns the hall state of the given 60° sector (i.e. `0..6`) of an electrical revolution.
+    ///
+    /// Sectors beyond `5` wrap around.
+    pub const fn from_sector(sector: u8) -> Self {
+        SECTORS[(sector % SECTORS_PER_ELECTRICAL_REVOLUTION as u8) as usize]
+    }
+
+    /// Returns the hall state for the given `position` of a motor
+    /// with `pole_pairs` pole pairs, the position being relative to the index
+    /// (i.e. mechanical angle zero coinciding with electrical angle zero).
+    ///
+    /// The position and number of pulses per revolution (PPR) are expected to be
+    /// relative to the decoder's step-mode (i.e. `cycles_per_revolution * Mode::PULSES_PER_CYCLE`).
+    pub fn from_position<T>(position: T, pulses_per_revolution: T, pole_pairs: u8) -> Self
+    where
+        T: Copy + PartialOrd + Euclid + Mul<Output = T> + Div<Output = T> + From<i8>,
+    {
+        let pole_pairs: T = (pole_pairs.min(i8::MAX as u8) as i8).into();
+        let sectors: T = SECTORS_PER_ELECTRICAL_REVOLUTION.into();
+
+        let position = position.rem_euclid(&pulses_per_revolution);
+        let sector = (position * pole_pairs * sectors / pulses_per_revolution).rem_euclid(&sectors);
+
+        let sector = (0..SECTORS_PER_ELECTRICAL_REVOLUTION)
+            .find(|&candidate| T::from(candidate) >= sector)
+            .unwrap_or_default();
+
+        Self::from_sector(sector as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sectors() {
+        for sector in 0..6 {
+            let state = HallState::from_sector(sector);
+            let next = HallState::from_sector(sector + 1);
+
+            // Exactly one signal changes between neighbouring sectors:
+            let changes = [state.u != next.u, state.v != next.v, state.w != next.w];
+            assert_eq!(changes.iter().filter(|&&changed| changed).count(), 1);
+        }
+    }
+
+    #[test]
+    fn position() {
+        // Two pole pairs, 120 pulses per revolution: 10 pulses per sector.
+        assert_eq!(
+            HallState::from_position(0, 120, 2),
+            HallState::from_sector(0)
+        );
+        assert_eq!(
+            HallState::from_position(9, 120, 2),
+            HallState::from_sector(0)
+        );
+        assert_eq!(
+            HallState::from_position(10, 120, 2),
+            HallState::from_sector(1)
+        );
+        assert_eq!(
+            HallState::from_position(59, 120, 2),
+            HallState::from_sector(5)
+        );
+        assert_eq!(
+            HallState::from_position(60, 120, 2),
+            HallState::from_sector(0)
+        );
+        assert_eq!(
+            HallState::from_position(-1, 120, 2),
+            HallState::from_sector(5)
+        );
+        assert_eq!(
+            HallState::from_position(130, 120, 2),
+            HallState::from_sector(1)
+        );
+    }
+}
@@ -0,0 +1,182 @@
+//! Fixed-capacity history of recent samples (e.g. velocities).
+
+/// Summary statistics of the samples of a [`HistoryBuffer`],
+/// as returned by [`HistoryBuffer::summary()`].
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct HistorySummary {
+    /// The number of samples.
+    pub count: usize,
+    /// The mean of the samples.
+    pub mean: f32,
+    /// The sample of largest magnitude (retaining its sign).
+    pub peak: f32,
+    /// The (population) standard deviation of the samples.
+    pub std_dev: f32,
+}
+
+/// A fixed-capacity (i.e. heap-free) ring buffer of the `N` most recent samples
+/// (e.g. velocities, as estimated by a [`VelocityEstimator`](crate::VelocityEstimator)),
+/// for tuning filters and surfacing vibration metrics without external tooling.
+#[derive(Clone, Debug)]
+pub struct HistoryBuffer<const N: usize> {
+    samples: [f32; N],
+    len: usize,
+    next: usize,
+}
+
+impl<const N: usize> Default for HistoryBuffer<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> HistoryBuffer<N> {
+    /// Creates an empty buffer.
+    pub const fn new() -> Self {
+        Self {
+            samples: [0.0; N],
+            len: 0,
+            next: 0,
+        }
+    }
+
+    /// Returns the buffer's capacity.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of samples in the buffer.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if the buffer contains no samples, otherwise `false`.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Pushes the given `sample`, evicting the oldest sample if the buffer is full.
+    pub fn push(&mut self, sample: f32) {
+        if N == 0 {
+            return;
+        }
+
+        self.samples[self.next] = sample;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+    }
+
+    /// Returns the most recently pushed sample, if any.
+    pub fn latest(&self) -> Option<f32> {
+        self.iter().last()
+    }
+
+    /// Returns an iterator over the buffer's samples, from oldest to newest.
+    pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+        let start = (self.next + N - self.len) % N.max(1);
+
+        (0..self.len).map(move |offset| self.samples[(start + offset) % N])
+    }
+
+    /// Returns the summary statistics of the buffer's samples,
+    /// or `None` if the buffer contains no samples.
+    pub fn summary(&self) -> Option<HistorySummary> {
+        if self.is_empty() {
+            return None;
+        }
+
+        let count = self.len;
+        let mean = self.iter().sum::<f32>() / count as f32;
+
+        let magnitude = |sample: f32| if sample < 0.0 { -sample } else { sample };
+        let peak = self.iter().fold(0.0, |peak: f32, sample| {
+            match magnitude(sample) > magnitude(peak) {
+                true => sample,
+                false => peak,
+            }
+        });
+
+        let variance = self
+            .iter()
+            .map(|sample| (sample - mean) * (sample - mean))
+            .sum::<f32>()
+            / count as f32;
+
+        Some(HistorySummary {
+            count,
+            mean,
+            peak,
+            std_dev: sqrt(variance),
+        })
+    }
+
+    /// Removes all samples from the buffer.
+    pub fn clear(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+}
+
+/// Returns the square root of `x` (or `0.0` for non-positive `x`),
+/// approximated via Newton's method, since `f32::sqrt()` requires `std`.
+fn sqrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    // Initial guess from halving the exponent of `x`'s bit representation:
+    let mut y = f32::from_bits((x.to_bits() >> 1) + 0x1FBD_1DF5);
+
+    for _ in 0..4 {
+        y = 0.5 * (y + x / y);
+    }
+
+    y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ring() {
+        let mut buffer: HistoryBuffer<3> = HistoryBuffer::new();
+        assert_eq!(buffer.summary(), None);
+        assert_eq!(buffer.latest(), None);
+
+        for sample in [1.0, 2.0, 3.0, 4.0] {
+            buffer.push(sample);
+        }
+
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.iter().collect::<Vec<_>>(), [2.0, 3.0, 4.0]);
+        assert_eq!(buffer.latest(), Some(4.0));
+
+        buffer.clear();
+        assert!(buffer.is_empty());
+    }
+
+    #[test]
+    fn summary() {
+        let mut buffer: HistoryBuffer<8> = HistoryBuffer::new();
+
+        for sample in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, -9.0] {
+            buffer.push(sample);
+        }
+
+        let summary = buffer.summary().unwrap();
+
+        assert_eq!(summary.count, 8);
+        assert_eq!(summary.mean, 2.75);
+        assert_eq!(summary.peak, -9.0);
+        assert!((summary.std_dev - 21.4375_f32.sqrt()).abs() < 1e-4);
+    }
+
+    #[test]
+    fn sqrt() {
+        for x in [0.25_f32, 2.0, 100.0, 12_345.0] {
+            assert!((super::sqrt(x) - x.sqrt()).abs() < x.sqrt() * 1e-6);
+        }
+        assert_eq!(super::sqrt(-1.0), 0.0);
+    }
+}
@@ -18,6 +18,16 @@ impl IndexDecoder {
         self.z = false;
     }
 
+    /// Returns the most recent reading of the z channel.
+    pub fn z(&self) -> bool {
+        self.z
+    }
+
+    /// Sets the most recent reading of the z channel.
+    pub fn set_z(&mut self, z: bool) {
+        self.z = z;
+    }
+
     /// Updates the internal state and returns `true` iff it
     /// detects a raising edge on the z channel, otherwise `false`.
     pub fn update(&mut self, z: bool) -> bool {
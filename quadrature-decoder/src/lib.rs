@@ -3,12 +3,54 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(test), no_std)]
 
+mod absolute;
+mod acceleration;
+mod adaptive;
+mod batch;
+pub mod capture;
+mod config;
+mod convert;
 mod decoder;
+mod diagnostics;
+mod divider;
+mod glitch;
+mod hall;
+mod history;
 mod index_decoder;
+mod multi;
+mod scale;
+pub mod simulator;
+mod snapshot;
 mod state_transducer;
+mod timing;
 mod validator;
+mod velocity;
+mod wiring;
 
-pub use self::decoder::{IncrementalDecoder, IndexedIncrementalDecoder};
+pub use self::{
+    absolute::{binary_to_gray, gray_to_binary, AbsoluteDecoder, Coding},
+    acceleration::{AccelProfile, Accelerator},
+    adaptive::AdaptiveResolution,
+    batch::{BatchReport, BufferDecoder},
+    config::Config,
+    convert::{convert_pulses, detents_to_pulses, pulses_to_detents},
+    decoder::{
+        DecoderState, DynIncrementalDecoder, IncrementalDecoder, IndexAction, IndexBehavior,
+        IndexEvent, IndexMode, IndexedDecoderState, IndexedIncrementalDecoder, StepDirDecoder,
+        UpDownPulseDecoder,
+    },
+    diagnostics::{Diagnostic, IntervalStats, JitterMonitor, SampleRateMonitor},
+    divider::{Divider, Tick},
+    glitch::GlitchFilter,
+    hall::HallState,
+    history::{HistoryBuffer, HistorySummary},
+    multi::MultiEncoderDecoder,
+    scale::{LinearScale, RotaryScale},
+    snapshot::{Snapshot, SnapshotError},
+    timing::max_poll_interval,
+    velocity::{Resolution, VelocityEstimator, VelocityTimeout, VelocityUnit},
+    wiring::{LineState, WiringCheck, WiringReport},
+};
 
 use self::state_transducer::StateTransducer;
 
@@ -41,10 +83,75 @@ pub enum Change {
     Negative = -1,
 }
 
+/// A runtime identifier of a quadrature-based decoder's step mode
+/// (e.g. for recording the step mode in logs, or serialized snapshots).
+///
+/// Each kind's discriminant (i.e. its id) equals its number of pulses per (quadrature) cycle (PPC).
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StepModeKind {
+    /// Full-step mode (see [`FullStep`]).
+    Full = 1,
+    /// Half-step mode (see [`HalfStep`]).
+    Half = 2,
+    /// Quad-step mode (see [`QuadStep`]).
+    Quad = 4,
+}
+
+impl StepModeKind {
+    /// Returns the kind corresponding to the given id, or `None` if the id is unknown.
+    pub const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            1 => Some(Self::Full),
+            2 => Some(Self::Half),
+            4 => Some(Self::Quad),
+            _ => None,
+        }
+    }
+
+    /// Returns the kind's id.
+    pub const fn id(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the kind's human-readable name.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Full => "full-step",
+            Self::Half => "half-step",
+            Self::Quad => "quad-step",
+        }
+    }
+
+    /// Returns the kind's number of pulses per (quadrature) cycle (PPC).
+    pub const fn pulses_per_cycle(self) -> usize {
+        self as usize
+    }
+}
+
 /// A quadrature-based decoder's step mode.
 pub trait StepMode: sealed::Sealed {
     /// The step-mode's number of pulses per (quadrature) cycle (PPC).
     const PULSES_PER_CYCLE: usize;
+
+    /// The step-mode's runtime identifier.
+    const KIND: StepModeKind;
+
+    /// Returns the step-mode's runtime identifier.
+    fn kind() -> StepModeKind {
+        Self::KIND
+    }
+
+    /// Returns the step-mode's id (see [`StepModeKind::id()`]).
+    fn id() -> u8 {
+        Self::KIND.id()
+    }
+
+    /// Returns the step-mode's human-readable name (see [`StepModeKind::name()`]).
+    fn name() -> &'static str {
+        Self::KIND.name()
+    }
 }
 
 /// A step mode that is able to detect a "change" (e.g. movement)
@@ -53,6 +160,7 @@ pub trait StepMode: sealed::Sealed {
 /// Full-step mode provides:
 /// - high noise-resistance (factor 4× relative to naïve decoding)
 /// - low resolution (factor 1× relative to native resolution)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct FullStep;
 
 impl sealed::Sealed for FullStep {}
@@ -62,6 +170,8 @@ impl StepMode for FullStep {
     /// As an example, consider the effective pulses per revolution (PPR)
     /// of a rotary encoder with 100 cycles per revolution (CPR): 100 PPR.
     const PULSES_PER_CYCLE: usize = 1;
+
+    const KIND: StepModeKind = StepModeKind::Full;
 }
 
 /// A step mode that is able to detect a "change" (e.g. movement) (e.g. movement)
@@ -73,6 +183,7 @@ impl StepMode for FullStep {
 /// Half-step mode provides:
 /// - medium noise-resistance (factor 2× relative to naïve decoding)
 /// - medium resolution (factor 1× relative to native resolution)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct HalfStep;
 
 impl sealed::Sealed for HalfStep {}
@@ -82,6 +193,8 @@ impl StepMode for HalfStep {
     /// As an example, consider the effective pulses per revolution (PPR)
     /// of a rotary encoder with 100 cycles per revolution (CPR): 200 PPR.
     const PULSES_PER_CYCLE: usize = 2;
+
+    const KIND: StepModeKind = StepModeKind::Half;
 }
 
 /// A step mode that is able to detect a "change" (e.g. movement)
@@ -93,6 +206,7 @@ impl StepMode for HalfStep {
 /// Quad-step mode provides:
 /// - low noise-resistance (factor 1× relative to naïve decoding)
 /// - high resolution (factor 1× relative to native resolution)
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct QuadStep;
 
 impl sealed::Sealed for QuadStep {}
@@ -102,4 +216,25 @@ impl StepMode for QuadStep {
     /// As an example, consider the effective pulses per revolution (PPR)
     /// of a rotary encoder with 100 cycles per revolution (CPR): 400 PPR.
     const PULSES_PER_CYCLE: usize = 4;
+
+    const KIND: StepModeKind = StepModeKind::Quad;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn step_mode_kinds() {
+        assert_eq!(FullStep::kind(), StepModeKind::Full);
+        assert_eq!(HalfStep::name(), "half-step");
+        assert_eq!(QuadStep::id(), 4);
+
+        for kind in [StepModeKind::Full, StepModeKind::Half, StepModeKind::Quad] {
+            assert_eq!(StepModeKind::from_id(kind.id()), Some(kind));
+            assert_eq!(kind.pulses_per_cycle(), kind.id() as usize);
+        }
+
+        assert_eq!(StepModeKind::from_id(3), None);
+    }
 }
@@ -0,0 +1,126 @@
+//! Decoding of multiple encoders sharing a single sampled port (e.g. of an I2C port expander).
+
+use num_traits::{One, SaturatingAdd, Zero};
+
+use crate::{Change, Error, FullStep, IncrementalDecoder, StepMode};
+
+/// A bank of quadrature decoders, decoding `N` channel pairs from a single sampled word
+/// (e.g. the GPIO register of an MCP23017, or PCF8574 port expander) per update,
+/// rather than reading every channel individually.
+///
+/// Each encoder is identified by the bit positions of its `a` and `b` channels within the word.
+#[derive(Debug)]
+pub struct MultiEncoderDecoder<const N: usize, Mode = FullStep, T = i32> {
+    decoders: [IncrementalDecoder<Mode, T>; N],
+    channels: [(u8, u8); N],
+}
+
+impl<const N: usize, Mode, T> MultiEncoderDecoder<N, Mode, T>
+where
+    Mode: StepMode,
+    IncrementalDecoder<Mode, T>: Default,
+{
+    /// Creates a bank of decoders for the given `(a, b)` bit positions per encoder,
+    /// or `None` if any of the bit positions exceeds the word (i.e. is `32`, or above).
+    pub fn new(channels: [(u8, u8); N]) -> Option<Self> {
+        if channels.iter().any(|&(a, b)| a >= 32 || b >= 32) {
+            return None;
+        }
+
+        Some(Self {
+            decoders: core::array::from_fn(|_| Default::default()),
+            channels,
+        })
+    }
+}
+
+impl<const N: usize, Mode, T> MultiEncoderDecoder<N, Mode, T>
+where
+    Mode: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + From<i8>,
+{
+    /// Returns the `(a, b)` bit positions per encoder.
+    pub fn channels(&self) -> [(u8, u8); N] {
+        self.channels
+    }
+
+    /// Updates all decoders based on the given sampled `word` (e.g. a `u8`, or `u16` port register),
+    /// returning the per-encoder results, as returned by [`IncrementalDecoder::update()`].
+    pub fn update(&mut self, word: impl Into<u32>) -> [Result<Option<Change>, Error>; N] {
+        let word: u32 = word.into();
+        let is_set = |bit: u8| word & (1 << bit) != 0;
+
+        let mut results = [Ok(None); N];
+
+        for ((decoder, &(a, b)), result) in self
+            .decoders
+            .iter_mut()
+            .zip(self.channels.iter())
+            .zip(results.iter_mut())
+        {
+            *result = decoder.update(is_set(a), is_set(b));
+        }
+
+        results
+    }
+
+    /// Returns the decoder of the encoder at the given `index`, if any.
+    pub fn decoder(&self, index: usize) -> Option<&IncrementalDecoder<Mode, T>> {
+        self.decoders.get(index)
+    }
+
+    /// Returns the mutable decoder of the encoder at the given `index`, if any
+    /// (e.g. for setting its counter).
+    pub fn decoder_mut(&mut self, index: usize) -> Option<&mut IncrementalDecoder<Mode, T>> {
+        self.decoders.get_mut(index)
+    }
+
+    /// Returns the counters of all encoders.
+    pub fn counters(&self) -> [T; N] {
+        core::array::from_fn(|index| self.decoders[index].counter())
+    }
+
+    /// Resets all decoders to their initial state and their counters back to `0`.
+    pub fn reset(&mut self) {
+        for decoder in &mut self.decoders {
+            decoder.reset();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::QuadStep;
+
+    use super::*;
+
+    #[test]
+    fn update() {
+        // Encoder 0 on bits 0 & 1, encoder 1 on bits 7 & 4:
+        let mut decoders: MultiEncoderDecoder<2, QuadStep> =
+            MultiEncoderDecoder::new([(0, 1), (7, 4)]).unwrap();
+
+        assert_eq!(decoders.update(0b1001_0011_u8), [Ok(None), Ok(None)]);
+
+        // Encoder 0 moves forwards, while encoder 1 moves backwards:
+        assert_eq!(
+            decoders.update(0b1000_0010_u8),
+            [Ok(Some(Change::Positive)), Ok(Some(Change::Negative))]
+        );
+        assert_eq!(decoders.counters(), [1, -1]);
+
+        // Encoder 1 jumps:
+        assert_eq!(
+            decoders.update(0b0001_0010_u8),
+            [Ok(None), Err(Error::E10_01)]
+        );
+
+        decoders.reset();
+        assert_eq!(decoders.counters(), [0, 0]);
+    }
+
+    #[test]
+    fn invalid_channels() {
+        assert!(MultiEncoderDecoder::<1>::new([(0, 32)]).is_none());
+    }
+}
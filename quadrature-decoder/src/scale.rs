@@ -0,0 +1,168 @@
+//! Scaling of counters into physical units (e.g. degrees, or millimeters).
+
+use core::{
+    f64::consts::TAU,
+    ops::{Add, Div, Mul},
+};
+
+use num_traits::{FromPrimitive, ToPrimitive};
+
+/// The scale of a rotary encoder, used for converting counters into angles.
+///
+/// The scale is generic over its output type `U`, which may be a float (e.g. `f32`),
+/// or a fixed-point type (e.g. from the `fixed` crate, with its `num-traits` feature enabled).
+///
+/// The number of pulses per revolution is relative to the decoder's step-mode
+/// (i.e. `cycles_per_revolution * Mode::PULSES_PER_CYCLE`).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct RotaryScale<U> {
+    pulses_per_revolution: u32,
+    gear_ratio: U,
+}
+
+impl<U> RotaryScale<U>
+where
+    U: Copy + FromPrimitive + Add<Output = U> + Mul<Output = U> + Div<Output = U>,
+{
+    /// Creates a scale for the given number of pulses per revolution,
+    /// returning `None` if it is zero, or not representable by `U`.
+    pub fn new(pulses_per_revolution: u32) -> Option<Self> {
+        if pulses_per_revolution == 0 {
+            return None;
+        }
+        U::from_u32(pulses_per_revolution)?;
+
+        Some(Self {
+            pulses_per_revolution,
+            gear_ratio: U::from_u8(1)?,
+        })
+    }
+
+    /// Sets the scale's gear ratio (i.e. encoder revolutions per output revolution),
+    /// returning the modified scale.
+    pub fn with_gear_ratio(mut self, gear_ratio: U) -> Self {
+        self.gear_ratio = gear_ratio;
+        self
+    }
+
+    /// Returns the scale's gear ratio.
+    pub fn gear_ratio(&self) -> U {
+        self.gear_ratio
+    }
+
+    /// Converts the given `counter` into output revolutions,
+    /// returning `None` if it is not representable by `U`.
+    pub fn revolutions<T>(&self, counter: T) -> Option<U>
+    where
+        T: ToPrimitive,
+    {
+        Some(self.per_revolution(counter.to_i64()?)? / self.gear_ratio)
+    }
+
+    /// Converts the given `counter` into an output angle in degrees,
+    /// returning `None` if it is not representable by `U`.
+    pub fn degrees<T>(&self, counter: T) -> Option<U>
+    where
+        T: ToPrimitive,
+    {
+        let counter = counter.to_i64()?.checked_mul(360)?;
+
+        Some(self.per_revolution(counter)? / self.gear_ratio)
+    }
+
+    /// Converts the given `counter` into an output angle in radians,
+    /// returning `None` if it is not representable by `U`.
+    pub fn radians<T>(&self, counter: T) -> Option<U>
+    where
+        T: ToPrimitive,
+    {
+        Some(self.revolutions(counter)? * U::from_f64(TAU)?)
+    }
+
+    /// Returns the given `value` divided by the scale's number of pulses per revolution.
+    fn per_revolution(&self, value: i64) -> Option<U> {
+        // Adding up the whole and the fractional part (rounding towards negative infinity for integers),
+        // rather than dividing the whole value, keeps intermediate values small for fixed-point outputs:
+        let pulses_per_revolution = i64::from(self.pulses_per_revolution);
+        let whole = U::from_i64(value.div_euclid(pulses_per_revolution))?;
+        let fraction = U::from_i64(value.rem_euclid(pulses_per_revolution))?
+            / U::from_i64(pulses_per_revolution)?;
+
+        Some(whole + fraction)
+    }
+}
+
+/// The scale of a linear encoder, used for converting counters into distances.
+///
+/// The scale is generic over its output type `U`, which may be a float (e.g. `f32`),
+/// or a fixed-point type (e.g. from the `fixed` crate, with its `num-traits` feature enabled).
+///
+/// The number of pulses per millimeter is relative to the decoder's step-mode
+/// (i.e. `cycles_per_millimeter * Mode::PULSES_PER_CYCLE`).
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct LinearScale<U> {
+    pulses_per_millimeter: U,
+}
+
+impl<U> LinearScale<U>
+where
+    U: Copy + FromPrimitive + Div<Output = U>,
+{
+    /// Creates a scale for the given number of pulses per millimeter.
+    pub fn new(pulses_per_millimeter: U) -> Self {
+        Self {
+            pulses_per_millimeter,
+        }
+    }
+
+    /// Returns the scale's number of pulses per millimeter.
+    pub fn pulses_per_millimeter(&self) -> U {
+        self.pulses_per_millimeter
+    }
+
+    /// Converts the given `counter` into a distance in millimeters,
+    /// returning `None` if it is not representable by `U`.
+    pub fn millimeters<T>(&self, counter: T) -> Option<U>
+    where
+        T: ToPrimitive,
+    {
+        Some(U::from_i64(counter.to_i64()?)? / self.pulses_per_millimeter)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotary() {
+        let scale: RotaryScale<f32> = RotaryScale::new(400).unwrap();
+
+        assert_eq!(scale.revolutions(200), Some(0.5));
+        assert_eq!(scale.degrees(-100), Some(-90.0));
+        assert_eq!(scale.radians(400), Some(core::f32::consts::TAU));
+
+        let geared = scale.with_gear_ratio(4.0);
+        assert_eq!(geared.degrees(400), Some(90.0));
+
+        // Integer outputs act as a (coarse) fixed-point type, rounding towards negative infinity:
+        let scale: RotaryScale<i32> = RotaryScale::new(4).unwrap();
+        assert_eq!(scale.degrees(8), Some(720));
+        assert_eq!(scale.revolutions(2), Some(0));
+
+        let scale: RotaryScale<i16> = RotaryScale::new(4096).unwrap();
+        assert_eq!(scale.degrees(1024), Some(90));
+        assert_eq!(scale.degrees(4095), Some(359));
+        assert_eq!(scale.degrees(-1), Some(-1));
+
+        assert_eq!(RotaryScale::<f32>::new(0), None);
+    }
+
+    #[test]
+    fn linear() {
+        let scale = LinearScale::new(20.0_f64);
+
+        assert_eq!(scale.millimeters(50), Some(2.5));
+        assert_eq!(scale.millimeters(-10_i64), Some(-0.5));
+    }
+}
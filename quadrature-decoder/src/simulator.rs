@@ -0,0 +1,598 @@
+//! A deterministic quadrature signal simulator, for exercising decoders in tests.
+//!
+//! The simulator emits the `a`, `b` and `z` pulse trains of an encoder being moved
+//! a quarter-cycle at a time, optionally distorted by injected faults
+//! and contact bounce (see [`Simulator::transition()`]):
+//!
+//! ```
+//! use quadrature_decoder::{
+//!     simulator::{Fault, ScriptedFault, Simulator},
+//!     Change, IncrementalDecoder, QuadStep,
+//! };
+//!
+//! // Drop the edge of the 2nd step (i.e. step index `1`):
+//! let script = [
+//!     ScriptedFault::inject(1, Fault::DroppedEdges),
+//!     ScriptedFault::clear(2, Fault::DroppedEdges),
+//! ];
+//!
+//! let mut simulator = Simulator::new().with_script(&script);
+//! let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+//!
+//! let mut results = Vec::new();
+//! for _ in 0..3 {
+//!     let sample = simulator.step(Change::Positive);
+//!     results.push(decoder.update(sample.a, sample.b));
+//! }
+//!
+//! assert!(results[0].is_ok());
+//! assert!(results[2].is_err());
+//! ```
+
+use crate::Change;
+
+/// The quadrature states of a single cycle, in order of positive movement.
+const PHASES: [(bool, bool); 4] = [(true, true), (false, true), (false, false), (true, false)];
+
+/// A pulse train of a simulated encoder.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Channel {
+    /// The `a` pulse train.
+    A,
+    /// The `b` pulse train.
+    B,
+    /// The `z` (i.e. index) pulse train.
+    Z,
+}
+
+/// A fault distorting a simulated encoder's output.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Fault {
+    /// The channel is stuck high (e.g. shorted to supply).
+    StuckHigh(Channel),
+    /// The channel is stuck low (e.g. open line with pull-down, or shorted to ground).
+    StuckLow(Channel),
+    /// The `a` and `b` channels are swapped (e.g. miswired).
+    SwappedPhases,
+    /// The `a` and `b` channels hold their levels, dropping any edges,
+    /// until the fault gets cleared (e.g. due to undersampling).
+    DroppedEdges,
+    /// The `z` channel never pulses (e.g. a dirty index mark).
+    IndexDropout,
+}
+
+impl Fault {
+    fn mask(self) -> u16 {
+        let bit = match self {
+            Fault::StuckHigh(channel) => channel as u16,
+            Fault::StuckLow(channel) => 3 + channel as u16,
+            Fault::SwappedPhases => 6,
+            Fault::DroppedEdges => 7,
+            Fault::IndexDropout => 8,
+        };
+
+        1 << bit
+    }
+}
+
+/// A fault getting injected or cleared from a given step onwards.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct ScriptedFault {
+    /// The (zero-based) index of the step from which on the fault gets injected or cleared.
+    pub step: u64,
+    /// The fault.
+    pub fault: Fault,
+    /// Whether the fault gets injected (`true`) or cleared (`false`).
+    pub is_active: bool,
+}
+
+impl ScriptedFault {
+    /// Creates a scripted injection of `fault`, from the step at index `step` onwards.
+    pub const fn inject(step: u64, fault: Fault) -> Self {
+        Self {
+            step,
+            fault,
+            is_active: true,
+        }
+    }
+
+    /// Creates a scripted clearing of `fault`, from the step at index `step` onwards.
+    pub const fn clear(step: u64, fault: Fault) -> Self {
+        Self {
+            step,
+            fault,
+            is_active: false,
+        }
+    }
+}
+
+/// A distribution of values, sampled deterministically by the simulator.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Distribution {
+    /// Always the given value.
+    Fixed(u32),
+    /// Uniformly distributed within `min..=max`.
+    Uniform {
+        /// The minimum value (inclusive).
+        min: u32,
+        /// The maximum value (inclusive).
+        max: u32,
+    },
+}
+
+impl Distribution {
+    fn sample(self, rng: &mut u32) -> u32 {
+        match self {
+            Distribution::Fixed(value) => value,
+            Distribution::Uniform { min, max } => {
+                let (min, max) = (min.min(max), min.max(max));
+                let span = (max - min) as u64 + 1;
+                min + (xorshift(rng) as u64 % span) as u32
+            }
+        }
+    }
+}
+
+/// A model of contact bounce, as exhibited by mechanical encoders.
+///
+/// On every transition the changing channel bounces back and forth
+/// a number of times (drawn from `count`) before settling, with each of
+/// the resulting levels being held for a number of samples (drawn from `spacing`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Bounce {
+    /// The distribution of bounces per transition.
+    pub count: Distribution,
+    /// The distribution of samples (at least `1`) between consecutive bounce edges.
+    pub spacing: Distribution,
+    /// The seed of the pseudo-random number generator.
+    pub seed: u32,
+}
+
+impl Bounce {
+    /// Creates a bounce model with the given `count` and `spacing` distributions.
+    pub const fn new(count: Distribution, spacing: Distribution) -> Self {
+        Self {
+            count,
+            spacing,
+            seed: 0x9E37_79B9,
+        }
+    }
+
+    /// Sets the model's seed, returning the modified model.
+    pub const fn with_seed(mut self, seed: u32) -> Self {
+        self.seed = seed;
+        self
+    }
+}
+
+/// A sample of a simulated encoder's pulse trains.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Sample {
+    /// The `a` pulse train.
+    pub a: bool,
+    /// The `b` pulse train.
+    pub b: bool,
+    /// The `z` (i.e. index) pulse train.
+    pub z: bool,
+}
+
+/// A deterministic simulator of an (optionally indexed) quadrature encoder.
+///
+/// The simulator's position is tracked in quarter-cycles
+/// (i.e. relative to `QuadStep`), starting at zero.
+#[derive(Clone, Debug)]
+pub struct Simulator<'a> {
+    position: i64,
+    steps: u64,
+    cycles_per_revolution: Option<u32>,
+    faults: u16,
+    script: &'a [ScriptedFault],
+    cursor: usize,
+    held: Option<(bool, bool)>,
+    bounce: Option<Bounce>,
+    rng: u32,
+}
+
+impl Default for Simulator<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Simulator<'a> {
+    /// Creates a fault-free simulator without index.
+    pub const fn new() -> Self {
+        Self {
+            position: 0,
+            steps: 0,
+            cycles_per_revolution: None,
+            faults: 0,
+            script: &[],
+            cursor: 0,
+            held: None,
+            bounce: None,
+            rng: 0,
+        }
+    }
+
+    /// Sets the simulator's number of cycles per revolution (CPR),
+    /// enabling an index pulse at every revolution's origin, returning the modified simulator.
+    pub fn with_cycles_per_revolution(mut self, cycles_per_revolution: u32) -> Self {
+        self.cycles_per_revolution = (cycles_per_revolution > 0).then_some(cycles_per_revolution);
+        self
+    }
+
+    /// Sets the simulator's fault script, returning the modified simulator.
+    ///
+    /// The script is expected to be sorted by `step`, with faults getting applied
+    /// right before taking the step at their index (i.e. a fault scripted for step `0`
+    /// is active from the start).
+    pub fn with_script(mut self, script: &'a [ScriptedFault]) -> Self {
+        self.script = script;
+        self.cursor = 0;
+        self.apply_script();
+        self
+    }
+
+    /// Sets the simulator's contact bounce model, returning the modified simulator.
+    pub fn with_bounce(mut self, bounce: Bounce) -> Self {
+        self.bounce = Some(bounce);
+        self.rng = seed(bounce.seed);
+        self
+    }
+
+    /// Injects the given fault, effective immediately.
+    pub fn inject(&mut self, fault: Fault) {
+        if fault == Fault::DroppedEdges && !self.is_active(fault) {
+            self.held = Some(PHASES[self.phase()]);
+        }
+
+        self.faults |= fault.mask();
+    }
+
+    /// Clears the given fault, effective immediately.
+    pub fn clear(&mut self, fault: Fault) {
+        if fault == Fault::DroppedEdges {
+            self.held = None;
+        }
+
+        self.faults &= !fault.mask();
+    }
+
+    /// Clears all faults, effective immediately.
+    pub fn clear_all(&mut self) {
+        self.faults = 0;
+        self.held = None;
+    }
+
+    /// Returns `true` if the given fault is active, otherwise `false`.
+    pub fn is_active(&self, fault: Fault) -> bool {
+        self.faults & fault.mask() != 0
+    }
+
+    /// Returns the simulator's true position in quarter-cycles.
+    pub fn position(&self) -> i64 {
+        self.position
+    }
+
+    /// Returns the number of steps taken so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    /// Moves the simulated encoder by a quarter-cycle in the direction of `change`,
+    /// applying any faults scripted for the step, returning the resulting sample.
+    pub fn step(&mut self, change: Change) -> Sample {
+        self.apply_script();
+
+        self.position += change as i64;
+        self.steps += 1;
+
+        self.sample()
+    }
+
+    /// Moves the simulated encoder by a quarter-cycle in the direction of `change`,
+    /// like [`step()`](Self::step), returning the burst of samples of the transition,
+    /// subject to the simulator's contact bounce model (if any).
+    ///
+    /// The burst's final sample is the settled sample. Without a bounce model
+    /// (or with a bounce count of `0`) the settled sample is the only sample.
+    pub fn transition(&mut self, change: Change) -> Transition<'_, 'a> {
+        let from = self.sample();
+        let to = self.step(change);
+
+        let bounces = match self.bounce {
+            Some(bounce) => bounce.count.sample(&mut self.rng).min(u16::MAX as u32 / 2),
+            None => 0,
+        };
+
+        Transition {
+            simulator: self,
+            from,
+            to,
+            segments: bounces as u16 * 2,
+            segment: 0,
+            held: 0,
+            is_settled: false,
+        }
+    }
+
+    /// Returns the simulated encoder's current sample, subject to any active faults.
+    pub fn sample(&self) -> Sample {
+        let (mut a, mut b) = self.held.unwrap_or(PHASES[self.phase()]);
+        let mut z = self.is_at_index() && !self.is_active(Fault::IndexDropout);
+
+        if self.is_active(Fault::SwappedPhases) {
+            core::mem::swap(&mut a, &mut b);
+        }
+
+        for (channel, level) in [
+            (Channel::A, &mut a),
+            (Channel::B, &mut b),
+            (Channel::Z, &mut z),
+        ] {
+            if self.is_active(Fault::StuckHigh(channel)) {
+                *level = true;
+            } else if self.is_active(Fault::StuckLow(channel)) {
+                *level = false;
+            }
+        }
+
+        Sample { a, b, z }
+    }
+
+    /// Resets the simulator to its initial state, rewinding its script.
+    pub fn reset(&mut self) {
+        self.position = 0;
+        self.steps = 0;
+        self.clear_all();
+        self.cursor = 0;
+        self.apply_script();
+
+        if let Some(bounce) = self.bounce {
+            self.rng = seed(bounce.seed);
+        }
+    }
+
+    fn phase(&self) -> usize {
+        self.position.rem_euclid(PHASES.len() as i64) as usize
+    }
+
+    fn is_at_index(&self) -> bool {
+        self.cycles_per_revolution.is_some_and(|cycles| {
+            let pulses_per_revolution = cycles as i64 * PHASES.len() as i64;
+            self.position.rem_euclid(pulses_per_revolution) == 0
+        })
+    }
+
+    fn apply_script(&mut self) {
+        while let Some(scripted) = self.script.get(self.cursor) {
+            if scripted.step > self.steps {
+                break;
+            }
+
+            match scripted.is_active {
+                true => self.inject(scripted.fault),
+                false => self.clear(scripted.fault),
+            }
+
+            self.cursor += 1;
+        }
+    }
+}
+
+/// The burst of samples of a simulated transition, as returned by [`Simulator::transition()`].
+#[derive(Debug)]
+pub struct Transition<'s, 'a> {
+    simulator: &'s mut Simulator<'a>,
+    from: Sample,
+    to: Sample,
+    segments: u16,
+    segment: u16,
+    held: u32,
+    is_settled: bool,
+}
+
+impl Iterator for Transition<'_, '_> {
+    type Item = Sample;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.held == 0 && self.segment < self.segments {
+            let spacing = match self.simulator.bounce {
+                Some(bounce) => bounce.spacing.sample(&mut self.simulator.rng),
+                None => 1,
+            };
+
+            self.held = spacing.max(1);
+            self.segment += 1;
+        }
+
+        if self.held > 0 {
+            self.held -= 1;
+
+            // Segments alternate between the new and old levels, starting with the new one:
+            let is_bounced_back = self.segment % 2 == 0;
+
+            return Some(match is_bounced_back {
+                true => Sample {
+                    a: self.from.a,
+                    b: self.from.b,
+                    z: self.to.z,
+                },
+                false => self.to,
+            });
+        }
+
+        if self.is_settled {
+            return None;
+        }
+
+        self.is_settled = true;
+
+        Some(self.to)
+    }
+}
+
+fn seed(seed: u32) -> u32 {
+    // Xorshift gets stuck on a zero state:
+    seed.max(1)
+}
+
+fn xorshift(state: &mut u32) -> u32 {
+    let mut x = *state;
+    x ^= x << 13;
+    x ^= x >> 17;
+    x ^= x << 5;
+    *state = x;
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Error, FullStep, IncrementalDecoder, IndexedIncrementalDecoder, QuadStep};
+
+    use super::*;
+
+    fn decode(
+        simulator: &mut Simulator,
+        changes: impl IntoIterator<Item = Change>,
+    ) -> (IncrementalDecoder<QuadStep>, Vec<Error>) {
+        let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+        let mut errors = vec![];
+
+        for change in changes {
+            let sample = simulator.step(change);
+            if let Err(error) = decoder.update(sample.a, sample.b) {
+                errors.push(error);
+            }
+        }
+
+        (decoder, errors)
+    }
+
+    #[test]
+    fn clean() {
+        let mut simulator = Simulator::new();
+
+        let changes = [Change::Positive; 10]
+            .into_iter()
+            .chain([Change::Negative; 3]);
+        let (decoder, errors) = decode(&mut simulator, changes);
+
+        assert_eq!(errors, vec![]);
+        assert_eq!(simulator.position(), 7);
+        assert_eq!(decoder.counter(), 7);
+    }
+
+    #[test]
+    fn stuck_channel() {
+        let mut simulator = Simulator::new();
+        simulator.inject(Fault::StuckLow(Channel::A));
+
+        let mut decoder: IncrementalDecoder<FullStep> = Default::default();
+        for _ in 0..16 {
+            let sample = simulator.step(Change::Positive);
+            assert!(!sample.a);
+            let _ = decoder.update(sample.a, sample.b);
+        }
+
+        assert_eq!(decoder.counter(), 0);
+    }
+
+    #[test]
+    fn swapped_phases() {
+        let script = [ScriptedFault::inject(0, Fault::SwappedPhases)];
+        let mut simulator = Simulator::new().with_script(&script);
+
+        let (decoder, _) = decode(&mut simulator, [Change::Positive; 9]);
+
+        assert!(decoder.counter() < 0);
+    }
+
+    #[test]
+    fn dropped_edges() {
+        let script = [
+            ScriptedFault::inject(2, Fault::DroppedEdges),
+            ScriptedFault::clear(3, Fault::DroppedEdges),
+        ];
+        let mut simulator = Simulator::new().with_script(&script);
+
+        let (_, errors) = decode(&mut simulator, [Change::Positive; 8]);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(simulator.position(), 8);
+    }
+
+    #[test]
+    fn index_dropout() {
+        let script = [ScriptedFault::inject(5, Fault::IndexDropout)];
+        let mut simulator = Simulator::new()
+            .with_cycles_per_revolution(1)
+            .with_script(&script);
+
+        let mut decoder: IndexedIncrementalDecoder<QuadStep> = Default::default();
+        let mut indices = 0;
+
+        for _ in 0..12 {
+            let sample = simulator.step(Change::Positive);
+            decoder.update(sample.a, sample.b, sample.z).unwrap();
+            indices += decoder.is_at_index() as usize;
+        }
+
+        // Only the index at position 4 survives, the ones at positions 8 and 12 are dropped:
+        assert_eq!(indices, 1);
+        assert_eq!(decoder.counter(), 8);
+    }
+
+    #[test]
+    fn bounce() {
+        let bounce = Bounce::new(Distribution::Fixed(2), Distribution::Fixed(3));
+        let mut simulator = Simulator::new().with_bounce(bounce);
+
+        let samples: Vec<(bool, bool)> = simulator
+            .transition(Change::Positive)
+            .map(|sample| (sample.a, sample.b))
+            .collect();
+
+        let (old, new) = ((true, true), (false, true));
+        let expected: Vec<(bool, bool)> = [new, old, new, old]
+            .into_iter()
+            .flat_map(|level| [level; 3])
+            .chain([new])
+            .collect();
+
+        assert_eq!(samples, expected);
+    }
+
+    #[test]
+    fn bounce_is_deterministic() {
+        let bounce = Bounce::new(
+            Distribution::Uniform { min: 0, max: 5 },
+            Distribution::Uniform { min: 1, max: 4 },
+        )
+        .with_seed(42);
+
+        let mut first = Simulator::new().with_bounce(bounce);
+        let mut second = Simulator::new().with_bounce(bounce);
+
+        for _ in 0..16 {
+            let first: Vec<Sample> = first.transition(Change::Positive).collect();
+            let second: Vec<Sample> = second.transition(Change::Positive).collect();
+
+            assert_eq!(first, second);
+        }
+
+        assert_eq!(first.position(), 16);
+    }
+
+    #[test]
+    fn no_bounce() {
+        let mut simulator = Simulator::new();
+
+        let samples: Vec<Sample> = simulator.transition(Change::Negative).collect();
+
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0], simulator.sample());
+    }
+}
@@ -0,0 +1,100 @@
+//! Compact snapshots of decoders' positions (e.g. for persisting them in backup RAM, or EEPROM).
+
+use crate::StepModeKind;
+
+/// A compact plain-data snapshot of a decoder's position, as returned by `snapshot()`,
+/// for persisting positions across resets (e.g. in battery-backed RAM, or EEPROM)
+/// and rehydrating them on boot via `restore()`.
+///
+/// Unlike [`DecoderState`](crate::DecoderState) the snapshot does not carry the decoder's full decoding state,
+/// but merely its counter, its phase within the quadrature cycle (i.e. its reading of channels `a` and `b`)
+/// and its step-mode, all of which are independent of the decoder's internal state machines.
+///
+/// With the `serde` crate feature enabled, snapshots implement `Serialize` and `Deserialize`
+/// (e.g. for encoding them via `postcard`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Snapshot<T = i32> {
+    /// The decoder's counter (in pulses of the snapshot's step-mode).
+    pub counter: T,
+    /// The decoder's phase within the quadrature cycle,
+    /// i.e. its most recent reading of channels `a` and `b` (packed as `0b_ab`).
+    pub phase: u8,
+    /// The decoder's step-mode.
+    pub step_mode: StepModeKind,
+}
+
+/// An error indicating a snapshot not being restorable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SnapshotError {
+    /// The snapshot was taken of a decoder of another step-mode.
+    StepMode(StepModeKind),
+    /// The snapshot's phase is invalid for the decoder's step-mode (e.g. due to corrupted storage).
+    Phase(u8),
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{DynIncrementalDecoder, FullStep, IncrementalDecoder, QuadStep};
+
+    use super::*;
+
+    #[test]
+    fn restore() {
+        let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+
+        assert!(decoder.update(false, true).is_ok());
+        assert!(decoder.update(false, false).is_ok());
+
+        let snapshot = decoder.snapshot();
+        assert_eq!(
+            snapshot,
+            Snapshot {
+                counter: 2,
+                phase: 0b_00,
+                step_mode: StepModeKind::Quad,
+            }
+        );
+
+        let mut restored: IncrementalDecoder<QuadStep> = Default::default();
+        assert_eq!(restored.restore(snapshot), Ok(()));
+        assert_eq!(restored.counter(), 2);
+
+        // Decoding resumes from the snapshot's phase:
+        assert_eq!(restored.update(true, false), decoder.update(true, false));
+        assert_eq!(restored.counter(), 3);
+
+        let mut other: IncrementalDecoder<FullStep> = Default::default();
+        assert_eq!(
+            other.restore(snapshot),
+            Err(SnapshotError::StepMode(StepModeKind::Quad))
+        );
+
+        // Phases beyond a reading of channels `a` and `b` are invalid:
+        let corrupted = Snapshot {
+            phase: 0b1_00,
+            ..snapshot
+        };
+        assert_eq!(
+            restored.restore(corrupted),
+            Err(SnapshotError::Phase(0b1_00))
+        );
+
+        // Every reading is restorable, regardless of step-mode:
+        for phase in 0b_00..=0b_11 {
+            let mut full_step: IncrementalDecoder<FullStep> = Default::default();
+            let snapshot = Snapshot {
+                counter: 0,
+                phase,
+                step_mode: StepModeKind::Full,
+            };
+            assert_eq!(full_step.restore(snapshot), Ok(()));
+            assert_eq!(full_step.snapshot(), snapshot);
+        }
+
+        let mut dynamic = DynIncrementalDecoder::new(StepModeKind::Full);
+        assert_eq!(dynamic.restore(snapshot), Ok(()));
+        assert_eq!(dynamic.step_mode(), StepModeKind::Quad);
+        assert_eq!(dynamic.counter(), 2);
+    }
+}
@@ -15,17 +15,18 @@ pub(crate) enum Input {
     A1B1,
 }
 
+/// The inputs, indexed by their bits (i.e. `a` as high bit, `b` as low bit).
+#[cfg_attr(
+    all(feature = "ram-lut", target_os = "none"),
+    link_section = ".data.quadrature_decoder.inputs"
+)]
+static INPUT_LUT: [Input; 4] = [Input::A0B0, Input::A0B1, Input::A1B0, Input::A1B1];
+
 impl Input {
-    pub(crate) const fn new(a: bool, b: bool) -> Self {
-        match (a, b) {
-            (false, false) => Self::A0B0,
-            (false, true) => Self::A0B1,
-            (true, false) => Self::A1B0,
-            (true, true) => Self::A1B1,
-        }
+    pub(crate) fn new(a: bool, b: bool) -> Self {
+        INPUT_LUT[((a as usize) << 1) | (b as usize)]
     }
 
-    #[cfg_attr(not(test), allow(dead_code))]
     pub(crate) const fn a(&self) -> bool {
         match self {
             Input::A0B0 | Input::A0B1 => false,
@@ -33,7 +34,6 @@ impl Input {
         }
     }
 
-    #[cfg_attr(not(test), allow(dead_code))]
     pub(crate) const fn b(&self) -> bool {
         match self {
             Input::A0B0 | Input::A1B0 => false,
@@ -60,22 +60,19 @@ pub(crate) enum Output {
     E = 0b_11,
 }
 
+/// The outputs, indexed by their bits.
+#[cfg_attr(
+    all(feature = "ram-lut", target_os = "none"),
+    link_section = ".data.quadrature_decoder.outputs"
+)]
+static OUTPUT_LUT: [Output; 4] = [Output::N, Output::AB, Output::BA, Output::E];
+
 impl Output {
     const BITS: usize = 2;
     const MASK: u8 = (1 << Self::BITS) - 1;
 
-    pub(crate) const fn from_bits(bits: u8) -> Option<Self> {
-        match bits {
-            x if x == (Output::N as u8) => Some(Output::N),
-            x if x == (Output::AB as u8) => Some(Output::AB),
-            x if x == (Output::BA as u8) => Some(Output::BA),
-            x if x == (Output::E as u8) => Some(Output::E),
-            _ => None,
-        }
-    }
-
-    pub(crate) unsafe fn from_bits_unchecked(bits: u8) -> Self {
-        Self::from_bits(bits).unwrap()
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        OUTPUT_LUT[(bits & Self::MASK) as usize]
     }
 
     pub(crate) const fn bits(&self) -> u8 {
@@ -83,6 +80,22 @@ impl Output {
     }
 }
 
+/// The states, indexed by their bits.
+#[cfg_attr(
+    all(feature = "ram-lut", target_os = "none"),
+    link_section = ".data.quadrature_decoder.states"
+)]
+static STATE_LUT: [State; 8] = [
+    State::N0,
+    State::F1,
+    State::F2,
+    State::F3,
+    State::R1,
+    State::R2,
+    State::R3,
+    State::N2,
+];
+
 /// A type defining the FST's states.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
@@ -101,22 +114,8 @@ impl State {
     const BITS: usize = 3;
     const MASK: u8 = (1 << Self::BITS) - 1;
 
-    pub(crate) const fn from_bits(bits: u8) -> Option<Self> {
-        match bits {
-            x if x == (State::N0 as u8) => Some(State::N0),
-            x if x == (State::F1 as u8) => Some(State::F1),
-            x if x == (State::F2 as u8) => Some(State::F2),
-            x if x == (State::F3 as u8) => Some(State::F3),
-            x if x == (State::R1 as u8) => Some(State::R1),
-            x if x == (State::R2 as u8) => Some(State::R2),
-            x if x == (State::R3 as u8) => Some(State::R3),
-            x if x == (State::N2 as u8) => Some(State::N2),
-            _ => None,
-        }
-    }
-
-    pub(crate) unsafe fn from_bits_unchecked(bits: u8) -> Self {
-        Self::from_bits(bits).unwrap()
+    pub(crate) fn from_bits(bits: u8) -> Self {
+        STATE_LUT[(bits & Self::MASK) as usize]
     }
 
     pub(crate) const fn bits(&self) -> u8 {
@@ -135,7 +134,7 @@ impl State {
 ///         │          └── Output bits
 ///         └── Unused bits
 /// ```
-#[repr(packed)]
+#[repr(C, packed)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub(crate) struct Transition {
     bits: u8,
@@ -153,13 +152,11 @@ impl Transition {
     }
 
     pub(crate) fn state(&self) -> State {
-        let bits = self.bits & State::MASK;
-        unsafe { State::from_bits_unchecked(bits) }
+        State::from_bits(self.bits)
     }
 
     pub(crate) fn output(&self) -> Output {
-        let bits = (self.bits >> Self::OUTPUT_OFFSET) & Output::MASK;
-        unsafe { Output::from_bits_unchecked(bits) }
+        Output::from_bits(self.bits >> Self::OUTPUT_OFFSET)
     }
 }
 
@@ -187,12 +184,10 @@ impl<'a, const STATES: usize, const INPUTS: usize> StateTransducer<'a, STATES, I
         }
     }
 
-    #[cfg_attr(not(test), allow(dead_code))]
     pub(crate) fn state(&self) -> State {
         self.state
     }
 
-    #[cfg_attr(not(test), allow(dead_code))]
     pub(crate) fn set_state(&mut self, state: State) {
         self.state = state;
     }
@@ -201,6 +196,20 @@ impl<'a, const STATES: usize, const INPUTS: usize> StateTransducer<'a, STATES, I
         self.state = State::N0;
     }
 
+    /// Returns `true` if the given `input` leaves the given `state` as is, without any output
+    /// (i.e. if the state is a valid state to be in, given the most recent input), otherwise `false`.
+    pub(crate) fn is_stable(&self, state: State, input: Input) -> bool {
+        let transition = self.transitions[state.bits() as usize][input.bits() as usize];
+        transition == Transition::new(state, Output::N)
+    }
+
+    /// Returns the first state that is stable for the given `input` (see [`is_stable()`](Self::is_stable)).
+    pub(crate) fn stable_state(&self, input: Input) -> Option<State> {
+        STATE_LUT
+            .into_iter()
+            .find(|&state| self.is_stable(state, input))
+    }
+
     pub(crate) fn step(&mut self, input: Input) -> Output {
         let state_index = self.state.bits() as usize;
         let input_index = input.bits() as usize;
@@ -32,6 +32,10 @@ use crate::state_transducer::{Output, State, Transition, Transitions};
 /// with the integer value of the state indicating the row index.
 /// Columns correspond to individual transitions per state,
 /// with the integer value of the input indicating the column index.
+#[cfg_attr(
+    all(feature = "ram-lut", target_os = "none"),
+    link_section = ".data.quadrature_decoder.full_step"
+)]
 pub(crate) static TRANSITIONS: Transitions<8, 4> = {
     use self::{Output::*, State::*};
 
@@ -104,6 +108,96 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sub_steps() {
+        let mut decoder = Decoder::default();
+
+        // Sub-steps are opt-in:
+        assert_eq!(update(&mut decoder, A0B1), Ok(None));
+        assert_eq!(decoder.sub_step(), None);
+
+        let mut decoder = Decoder::default().with_sub_steps();
+
+        let scenarios = [
+            (A0B1, Ok(None), Some(Positive)),
+            (A0B1, Ok(None), None), // Redundant input
+            (A0B0, Ok(None), Some(Positive)),
+            (A1B0, Ok(None), Some(Positive)),
+            (A1B1, Ok(Some(Positive)), Some(Positive)),
+            (A1B0, Ok(None), Some(Negative)),
+            (A0B1, Err(Error::E10_01), None), // Noise input
+        ];
+
+        for (input, output, sub_step) in scenarios {
+            assert_eq!(update(&mut decoder, input), output);
+            assert_eq!(decoder.sub_step(), sub_step);
+        }
+
+        decoder.reset();
+
+        assert_eq!(decoder.sub_step(), None);
+    }
+
+    #[test]
+    fn checkpoint() {
+        let mut decoder = Decoder::default();
+
+        assert_eq!(update(&mut decoder, A0B1), Ok(None));
+        assert_eq!(update(&mut decoder, A0B0), Ok(None));
+
+        let checkpoint = decoder.checkpoint();
+
+        // Speculatively decode the remainder of a forward cycle:
+        assert_eq!(update(&mut decoder, A1B0), Ok(None));
+        assert_eq!(update(&mut decoder, A1B1), Ok(Some(Positive)));
+        assert_eq!(decoder.counter(), 1);
+
+        decoder.rollback(checkpoint);
+
+        assert_eq!(decoder.counter(), 0);
+        assert_eq!(decoder.checkpoint(), checkpoint);
+
+        // Decoding continues from the checkpoint, not the speculative state:
+        assert_eq!(update(&mut decoder, A1B1), Err(Error::E00_11));
+        assert_eq!(update(&mut decoder, A0B0), Err(Error::E11_00));
+    }
+
+    #[test]
+    fn saturation() {
+        let mut decoder: IncrementalDecoder<FullStep, i8> = Default::default();
+        decoder.set_counter(i8::MAX - 1);
+
+        let forwards = [A0B1, A0B0, A1B0, A1B1];
+        let backwards = [A1B0, A0B0, A0B1, A1B1];
+
+        for input in forwards {
+            decoder.update(input.a(), input.b()).unwrap();
+        }
+
+        assert_eq!(decoder.counter(), i8::MAX);
+        assert!(!decoder.is_saturated());
+
+        for input in forwards {
+            decoder.update(input.a(), input.b()).unwrap();
+        }
+
+        assert_eq!(decoder.counter(), i8::MAX);
+        assert!(decoder.is_saturated());
+
+        // The flag is sticky, even when moving away from the bound:
+        for input in backwards {
+            decoder.update(input.a(), input.b()).unwrap();
+        }
+
+        assert_eq!(decoder.counter(), i8::MAX - 1);
+        assert!(decoder.is_saturated());
+        assert!(decoder.checkpoint().is_saturated());
+
+        decoder.clear_saturated();
+
+        assert!(!decoder.is_saturated());
+    }
+
     mod clean {
 
         use super::*;
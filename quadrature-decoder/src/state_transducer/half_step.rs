@@ -30,6 +30,10 @@ use crate::state_transducer::{Output, State, Transition, Transitions};
 /// with the integer value of the state indicating the row index.
 /// Columns correspond to individual transitions per state,
 /// with the integer value of the input indicating the column index.
+#[cfg_attr(
+    all(feature = "ram-lut", target_os = "none"),
+    link_section = ".data.quadrature_decoder.half_step"
+)]
 pub(crate) static TRANSITIONS: Transitions<8, 4> = {
     use self::{Output::*, State::*};
 
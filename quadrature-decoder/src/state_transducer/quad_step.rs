@@ -38,6 +38,10 @@ use crate::state_transducer::{Output, State, Transition, Transitions};
 /// with the integer value of the state indicating the row index.
 /// Columns correspond to individual transitions per state,
 /// with the integer value of the input indicating the column index.
+#[cfg_attr(
+    all(feature = "ram-lut", target_os = "none"),
+    link_section = ".data.quadrature_decoder.quad_step"
+)]
 pub(crate) static TRANSITIONS: Transitions<8, 4> = {
     use self::{Output::*, State::*};
 
@@ -89,6 +93,46 @@ mod tests {
         assert_eq!(transducer.state(), State::N0);
     }
 
+    #[test]
+    fn sub_steps() {
+        let mut decoder = Decoder::default().with_sub_steps();
+
+        let scenarios = [
+            (A0B1, Ok(Some(Positive)), Some(Positive)),
+            (A0B1, Ok(None), None), // Redundant input
+            (A0B0, Ok(Some(Positive)), Some(Positive)),
+            (A0B1, Ok(Some(Negative)), Some(Negative)),
+            (A1B0, Err(Error::E01_10), None), // Noise input
+        ];
+
+        for (input, output, sub_step) in scenarios {
+            assert_eq!(update(&mut decoder, input), output);
+            assert_eq!(decoder.sub_step(), sub_step);
+        }
+
+        decoder.reset();
+        decoder.set_sub_steps(false);
+
+        assert_eq!(update(&mut decoder, A0B1), Ok(Some(Positive)));
+        assert_eq!(decoder.sub_step(), None);
+    }
+
+    #[test]
+    fn reset_state() {
+        let mut decoder = Decoder::default().with_sub_steps();
+
+        assert_eq!(update(&mut decoder, A0B1), Ok(Some(Positive)));
+        assert_eq!(update(&mut decoder, A0B0), Ok(Some(Positive)));
+
+        // Decoding resumes from the most recent reading:
+        decoder.reset_state();
+
+        assert_eq!(update(&mut decoder, A1B0), Ok(Some(Positive)));
+        assert_eq!(decoder.sub_step(), Some(Positive));
+        assert_eq!(update(&mut decoder, A1B1), Ok(Some(Positive)));
+        assert_eq!(decoder.counter(), 4);
+    }
+
     #[test]
     fn identity() {
         let mut transducer = StateTransducer::new(&TRANSITIONS);
@@ -0,0 +1,65 @@
+//! Timing requirements of quadrature decoding.
+
+use crate::StepMode;
+
+const NANOS_PER_MINUTE: u64 = 60 * 1_000_000_000;
+
+/// The number of distinct states per quadrature cycle.
+const STATES_PER_CYCLE: u64 = 4;
+
+/// Returns the worst-case safe polling interval (in nanoseconds) for a rotary encoder
+/// with the given number of pulses per revolution (PPR) spinning at up to `max_rpm`.
+///
+/// The number of pulses is expected to be relative to the decoder's step-mode
+/// (i.e. `cycles_per_revolution * Steps::PULSES_PER_CYCLE`).
+///
+/// Regardless of step-mode, a decoder has to observe every single state of a quadrature cycle,
+/// so the interval corresponds to the duration of a single state at maximum speed.
+/// Polling any slower risks missing states, resulting in `Err(_)`s and lost movement.
+/// It is generally advisable to leave a healthy margin for jitter.
+///
+/// Being a `const fn` it allows for asserting timer configurations at compile time:
+///
+/// ```
+/// use quadrature_decoder::{max_poll_interval, QuadStep};
+///
+/// const POLL_INTERVAL_NS: u64 = 10_000;
+///
+/// const _: () = assert!(POLL_INTERVAL_NS <= max_poll_interval::<QuadStep>(3_000, 400));
+/// ```
+///
+/// Returns `u64::MAX` if either of `max_rpm` or `pulses_per_revolution` is zero.
+pub const fn max_poll_interval<Steps>(max_rpm: u32, pulses_per_revolution: u32) -> u64
+where
+    Steps: StepMode,
+{
+    let cycles_per_revolution = pulses_per_revolution as u64 / Steps::PULSES_PER_CYCLE as u64;
+    let states_per_minute = max_rpm as u64 * cycles_per_revolution * STATES_PER_CYCLE;
+
+    if states_per_minute == 0 {
+        return u64::MAX;
+    }
+
+    NANOS_PER_MINUTE / states_per_minute
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FullStep, HalfStep, QuadStep};
+
+    use super::*;
+
+    #[test]
+    fn interval() {
+        // 100 cycles per revolution at 1500 RPM: 25 rev/s, 10_000 states/s:
+        assert_eq!(max_poll_interval::<FullStep>(1_500, 100), 100_000);
+        assert_eq!(max_poll_interval::<HalfStep>(1_500, 200), 100_000);
+        assert_eq!(max_poll_interval::<QuadStep>(1_500, 400), 100_000);
+    }
+
+    #[test]
+    fn zero() {
+        assert_eq!(max_poll_interval::<FullStep>(0, 100), u64::MAX);
+        assert_eq!(max_poll_interval::<FullStep>(1_500, 0), u64::MAX);
+    }
+}
@@ -1,5 +1,21 @@
 use crate::{state_transducer::Input, Error};
 
+/// The errors of transitions between inputs (i.e. jumps skipping an input),
+/// indexed by the previous input's bits followed by the current input's bits,
+/// matching the discriminants of the corresponding errors.
+#[cfg_attr(
+    all(feature = "ram-lut", target_os = "none"),
+    link_section = ".data.quadrature_decoder.errors"
+)]
+static ERROR_LUT: [Option<Error>; 16] = {
+    let mut lut = [None; 16];
+    lut[Error::E00_11 as usize] = Some(Error::E00_11);
+    lut[Error::E01_10 as usize] = Some(Error::E01_10);
+    lut[Error::E10_01 as usize] = Some(Error::E10_01);
+    lut[Error::E11_00 as usize] = Some(Error::E11_00);
+    lut
+};
+
 /// A validator for checking conformance of inputs against quadrature protocol.
 #[derive(Debug)]
 pub(crate) struct InputValidator {
@@ -11,15 +27,23 @@ impl InputValidator {
 
     pub(crate) fn validate(&mut self, input: Input) -> Result<(), Error> {
         let last_input = core::mem::replace(&mut self.input, input);
-        match (last_input, input) {
-            (Input::A0B0, Input::A1B1) => Err(Error::E00_11),
-            (Input::A0B1, Input::A1B0) => Err(Error::E01_10),
-            (Input::A1B0, Input::A0B1) => Err(Error::E10_01),
-            (Input::A1B1, Input::A0B0) => Err(Error::E11_00),
-            _ => Ok(()),
+        let index = (last_input.bits() << 2) | input.bits();
+        match ERROR_LUT[index as usize] {
+            Some(error) => Err(error),
+            None => Ok(()),
         }
     }
 
+    /// Returns the most recently validated input.
+    pub(crate) fn input(&self) -> Input {
+        self.input
+    }
+
+    /// Sets the most recently validated input.
+    pub(crate) fn set_input(&mut self, input: Input) {
+        self.input = input;
+    }
+
     /// Resets the validator to its initial state.
     pub(crate) fn reset(&mut self) {
         self.input = Self::INITIAL_INPUT;
@@ -0,0 +1,356 @@
+//! Period-based velocity estimation.
+
+use crate::Change;
+
+/// The behavior of a velocity estimator when no changes arrive for a while.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VelocityTimeout {
+    /// Snaps the velocity to zero once no change
+    /// has been detected for the given number of ticks.
+    Snap(u64),
+    /// Decays the velocity towards zero once no change
+    /// has been detected for the given number of ticks.
+    ///
+    /// While decaying the velocity is limited to one pulse per elapsed time,
+    /// which is the highest velocity still consistent with not having seen another change.
+    Decay(u64),
+}
+
+/// The unit of a velocity, as returned by [`VelocityEstimator::velocity_in`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VelocityUnit {
+    /// Pulses (i.e. counts) per second.
+    PulsesPerSecond,
+    /// Detents per second (requires `Resolution::pulses_per_detent`).
+    DetentsPerSecond,
+    /// Revolutions per minute (requires `Resolution::pulses_per_revolution`).
+    RevolutionsPerMinute,
+    /// Millimeters per second (requires `Resolution::pulses_per_millimeter`).
+    MillimetersPerSecond,
+}
+
+/// The physical resolution of an encoder, used for converting velocities between units.
+///
+/// All values are relative to the decoder's step-mode
+/// (i.e. `cycles_per_… * Mode::PULSES_PER_CYCLE`).
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct Resolution {
+    /// The number of pulses per detent, if any.
+    pub pulses_per_detent: Option<u32>,
+    /// The number of pulses per revolution, if any.
+    pub pulses_per_revolution: Option<u32>,
+    /// The number of pulses per millimeter, if any.
+    pub pulses_per_millimeter: Option<f32>,
+}
+
+impl Resolution {
+    /// Sets the number of pulses per detent, returning the modified resolution.
+    pub fn with_pulses_per_detent(mut self, pulses_per_detent: u32) -> Self {
+        self.pulses_per_detent = Some(pulses_per_detent);
+        self
+    }
+
+    /// Sets the number of pulses per revolution, returning the modified resolution.
+    pub fn with_pulses_per_revolution(mut self, pulses_per_revolution: u32) -> Self {
+        self.pulses_per_revolution = Some(pulses_per_revolution);
+        self
+    }
+
+    /// Sets the number of pulses per millimeter, returning the modified resolution.
+    pub fn with_pulses_per_millimeter(mut self, pulses_per_millimeter: f32) -> Self {
+        self.pulses_per_millimeter = Some(pulses_per_millimeter);
+        self
+    }
+
+    /// Converts the given velocity from pulses per second into the given unit,
+    /// returning `None` if the resolution required by the unit has not been provided.
+    pub fn convert(&self, pulses_per_second: f32, unit: VelocityUnit) -> Option<f32> {
+        match unit {
+            VelocityUnit::PulsesPerSecond => Some(pulses_per_second),
+            VelocityUnit::DetentsPerSecond => self
+                .pulses_per_detent
+                .map(|pulses| pulses_per_second / (pulses as f32)),
+            VelocityUnit::RevolutionsPerMinute => self
+                .pulses_per_revolution
+                .map(|pulses| 60.0 * pulses_per_second / (pulses as f32)),
+            VelocityUnit::MillimetersPerSecond => self
+                .pulses_per_millimeter
+                .map(|pulses| pulses_per_second / pulses),
+        }
+    }
+}
+
+/// A period-based velocity estimator, deriving the velocity
+/// from the time elapsed between consecutive changes.
+///
+/// Timestamps are provided as monotonic ticks of arbitrary resolution,
+/// as specified by the estimator's ticks per second.
+///
+/// Without a timeout the most recently estimated velocity is held indefinitely,
+/// even if the encoder has long since stopped.
+///
+/// Without smoothing the velocity is derived from the most recent period alone,
+/// which tends to be noisy for encoders with uneven spacing of their edges.
+#[derive(Clone, Debug)]
+pub struct VelocityEstimator {
+    ticks_per_second: u32,
+    timeout: Option<VelocityTimeout>,
+    resolution: Resolution,
+    smoothing: Option<f32>,
+    last_change: Option<(u64, Change)>,
+    period: Option<u64>,
+    smoothed: Option<f32>,
+}
+
+impl VelocityEstimator {
+    /// Creates a velocity estimator for timestamps with the given number of ticks per second.
+    pub fn new(ticks_per_second: u32) -> Self {
+        Self {
+            ticks_per_second,
+            timeout: None,
+            resolution: Resolution::default(),
+            smoothing: None,
+            last_change: None,
+            period: None,
+            smoothed: None,
+        }
+    }
+
+    /// Sets the estimator's timeout, returning the modified estimator.
+    pub fn with_timeout(mut self, timeout: VelocityTimeout) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the estimator's timeout, if any.
+    pub fn timeout(&self) -> Option<VelocityTimeout> {
+        self.timeout
+    }
+
+    /// Sets the estimator's timeout.
+    pub fn set_timeout(&mut self, timeout: Option<VelocityTimeout>) {
+        self.timeout = timeout;
+    }
+
+    /// Sets the estimator's resolution, returning the modified estimator.
+    pub fn with_resolution(mut self, resolution: Resolution) -> Self {
+        self.resolution = resolution;
+        self
+    }
+
+    /// Returns the estimator's resolution.
+    pub fn resolution(&self) -> Resolution {
+        self.resolution
+    }
+
+    /// Sets the estimator's resolution.
+    pub fn set_resolution(&mut self, resolution: Resolution) {
+        self.resolution = resolution;
+    }
+
+    /// Sets the estimator's smoothing factor (clamped to `0.0..=1.0`), returning the modified estimator.
+    ///
+    /// Smoothing applies an IIR (i.e. exponential moving average) filter to the velocities
+    /// derived from consecutive periods, with every new period contributing
+    /// to the estimate by the given `factor` (i.e. `1.0` disabling smoothing).
+    pub fn with_smoothing(mut self, factor: f32) -> Self {
+        self.set_smoothing(Some(factor));
+        self
+    }
+
+    /// Returns the estimator's smoothing factor, if any.
+    pub fn smoothing(&self) -> Option<f32> {
+        self.smoothing
+    }
+
+    /// Sets the estimator's smoothing factor (clamped to `0.0..=1.0`).
+    pub fn set_smoothing(&mut self, factor: Option<f32>) {
+        self.smoothing = factor.map(|factor| factor.clamp(0.0, 1.0));
+        self.smoothed = None;
+    }
+
+    /// Returns the estimator's number of ticks per second.
+    pub fn ticks_per_second(&self) -> u32 {
+        self.ticks_per_second
+    }
+
+    /// Updates the estimator with the change (if any) detected at the given `timestamp`.
+    pub fn update(&mut self, timestamp: u64, change: Option<Change>) {
+        let Some(change) = change else {
+            return;
+        };
+
+        if let Some((last_timestamp, _)) = self.last_change {
+            let period = timestamp.wrapping_sub(last_timestamp);
+            self.period = Some(period);
+
+            if let (Some(factor), true) = (self.smoothing, period > 0) {
+                let sign = (change as i8) as f32;
+                let velocity = sign * (self.ticks_per_second as f32) / (period as f32);
+
+                self.smoothed = Some(match self.smoothed {
+                    Some(smoothed) => smoothed + factor * (velocity - smoothed),
+                    None => velocity,
+                });
+            }
+        }
+
+        self.last_change = Some((timestamp, change));
+    }
+
+    /// Returns the estimated velocity at the given timestamp in pulses per second,
+    /// with positive values corresponding to `Change::Positive`.
+    ///
+    /// Returns `0.0` until at least two changes have been detected.
+    pub fn velocity(&self, now: u64) -> f32 {
+        let (Some((last_timestamp, change)), Some(period)) = (self.last_change, self.period) else {
+            return 0.0;
+        };
+
+        if period == 0 {
+            return 0.0;
+        }
+
+        let elapsed = now.wrapping_sub(last_timestamp);
+        let sign = (change as i8) as f32;
+        let ticks_per_second = self.ticks_per_second as f32;
+
+        let velocity = match self.smoothed {
+            Some(smoothed) => smoothed,
+            None => sign * ticks_per_second / (period as f32),
+        };
+
+        match self.timeout {
+            Some(VelocityTimeout::Snap(timeout)) if elapsed > timeout => 0.0,
+            Some(VelocityTimeout::Decay(timeout)) if elapsed > timeout => {
+                let limit = ticks_per_second / (elapsed as f32);
+                velocity.clamp(-limit, limit)
+            }
+            _ => velocity,
+        }
+    }
+
+    /// Returns the estimated velocity at the given timestamp in the given unit,
+    /// or `None` if the resolution required by the unit has not been provided.
+    pub fn velocity_in(&self, now: u64, unit: VelocityUnit) -> Option<f32> {
+        self.resolution.convert(self.velocity(now), unit)
+    }
+
+    /// Returns the number of ticks elapsed between the most recent change and the given timestamp,
+    /// or `None` if no change has been detected yet.
+    ///
+    /// Useful for implementing custom stall/idle logic, or "no signal" indicators.
+    pub fn staleness(&self, now: u64) -> Option<u64> {
+        let (last_timestamp, _) = self.last_change?;
+
+        Some(now.wrapping_sub(last_timestamp))
+    }
+
+    /// Resets the estimator to its initial state.
+    pub fn reset(&mut self) {
+        self.last_change = None;
+        self.period = None;
+        self.smoothed = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn velocity() {
+        let mut estimator = VelocityEstimator::new(1_000);
+
+        assert_eq!(estimator.velocity(0), 0.0);
+
+        estimator.update(0, Some(Change::Positive));
+        assert_eq!(estimator.velocity(0), 0.0);
+
+        estimator.update(5, None);
+        estimator.update(10, Some(Change::Positive));
+        assert_eq!(estimator.velocity(10), 100.0);
+
+        estimator.update(30, Some(Change::Negative));
+        assert_eq!(estimator.velocity(30), -50.0);
+
+        // Without a timeout the velocity is held indefinitely:
+        assert_eq!(estimator.velocity(1_000_000), -50.0);
+
+        estimator.reset();
+        assert_eq!(estimator.velocity(30), 0.0);
+    }
+
+    #[test]
+    fn staleness() {
+        let mut estimator = VelocityEstimator::new(1_000);
+
+        assert_eq!(estimator.staleness(10), None);
+
+        estimator.update(10, Some(Change::Positive));
+        estimator.update(20, None);
+        assert_eq!(estimator.staleness(25), Some(15));
+
+        estimator.reset();
+        assert_eq!(estimator.staleness(25), None);
+    }
+
+    #[test]
+    fn velocity_in() {
+        let resolution = Resolution::default()
+            .with_pulses_per_detent(4)
+            .with_pulses_per_revolution(80);
+        let mut estimator = VelocityEstimator::new(1_000).with_resolution(resolution);
+
+        estimator.update(0, Some(Change::Positive));
+        estimator.update(10, Some(Change::Positive));
+
+        let velocity_in = |unit| estimator.velocity_in(10, unit);
+
+        assert_eq!(velocity_in(VelocityUnit::PulsesPerSecond), Some(100.0));
+        assert_eq!(velocity_in(VelocityUnit::DetentsPerSecond), Some(25.0));
+        assert_eq!(velocity_in(VelocityUnit::RevolutionsPerMinute), Some(75.0));
+        assert_eq!(velocity_in(VelocityUnit::MillimetersPerSecond), None);
+    }
+
+    #[test]
+    fn snap_timeout() {
+        let mut estimator = VelocityEstimator::new(1_000).with_timeout(VelocityTimeout::Snap(100));
+
+        estimator.update(0, Some(Change::Positive));
+        estimator.update(10, Some(Change::Positive));
+
+        assert_eq!(estimator.velocity(110), 100.0);
+        assert_eq!(estimator.velocity(111), 0.0);
+    }
+
+    #[test]
+    fn decay_timeout() {
+        let mut estimator = VelocityEstimator::new(1_000).with_timeout(VelocityTimeout::Decay(20));
+
+        estimator.update(0, Some(Change::Negative));
+        estimator.update(10, Some(Change::Negative));
+
+        assert_eq!(estimator.velocity(30), -100.0);
+        assert_eq!(estimator.velocity(50), -25.0);
+        assert_eq!(estimator.velocity(1_010), -1.0);
+    }
+
+    #[test]
+    fn smoothing() {
+        let mut estimator = VelocityEstimator::new(1_000).with_smoothing(0.5);
+
+        estimator.update(0, Some(Change::Positive));
+        estimator.update(10, Some(Change::Positive));
+        assert_eq!(estimator.velocity(10), 100.0);
+
+        estimator.update(30, Some(Change::Positive));
+        assert_eq!(estimator.velocity(30), 75.0);
+
+        estimator.update(35, Some(Change::Positive));
+        assert_eq!(estimator.velocity(35), 137.5);
+
+        estimator.reset();
+        assert_eq!(estimator.velocity(35), 0.0);
+    }
+}
@@ -0,0 +1,195 @@
+//! Wiring self-tests for bring-up.
+
+use crate::{Change, IncrementalDecoder, QuadStep};
+
+/// The observed state of a single pulse train.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LineState {
+    /// The line toggled at least once.
+    Toggling,
+    /// The line never toggled, staying high (e.g. open with pull-up, or shorted to supply).
+    StuckHigh,
+    /// The line never toggled, staying low (e.g. open with pull-down, or shorted to ground).
+    StuckLow,
+}
+
+/// The report of a wiring check, as returned by [`WiringCheck::report()`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WiringReport {
+    /// The state of the `a` pulse train.
+    pub a: LineState,
+    /// The state of the `b` pulse train.
+    pub b: LineState,
+    /// Whether both pulse trains toggled, but always had equal levels
+    /// (i.e. the channels are likely shorted to one another).
+    pub is_shorted: bool,
+    /// Whether the observed direction of movement opposed the expected one
+    /// (i.e. the channels are likely swapped), or `None` if no direction was expected,
+    /// or no movement was observed.
+    pub is_swapped: Option<bool>,
+    /// The number of invalid transitions (i.e. "jumps") observed.
+    pub errors: u32,
+}
+
+impl WiringReport {
+    /// Returns `true` if no wiring issues were detected, otherwise `false`.
+    ///
+    /// The absence of issues is only meaningful if the encoder was moved during the check.
+    pub fn is_ok(&self) -> bool {
+        self.a == LineState::Toggling
+            && self.b == LineState::Toggling
+            && !self.is_shorted
+            && self.is_swapped != Some(true)
+    }
+}
+
+/// A wiring self-test, observing an encoder's `a` and `b` pulse trains
+/// over a short window (ideally while moving it), detecting open (i.e. never toggling),
+/// shorted and (given an expected direction of movement) swapped channels.
+#[derive(Debug)]
+pub struct WiringCheck {
+    expected: Option<Change>,
+    decoder: IncrementalDecoder<QuadStep, i32>,
+    first: Option<(bool, bool)>,
+    toggled: (bool, bool),
+    is_always_equal: bool,
+    errors: u32,
+}
+
+impl Default for WiringCheck {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WiringCheck {
+    /// Creates a wiring check without expected direction of movement.
+    pub fn new() -> Self {
+        Self {
+            expected: None,
+            decoder: Default::default(),
+            first: None,
+            toggled: (false, false),
+            is_always_equal: true,
+            errors: 0,
+        }
+    }
+
+    /// Sets the direction of movement commanded during the check,
+    /// enabling the detection of swapped channels, returning the modified check.
+    pub fn with_expected(mut self, change: Change) -> Self {
+        self.expected = Some(change);
+        self
+    }
+
+    /// Updates the check with the given `a` and `b` pulse train readings.
+    pub fn update(&mut self, a: bool, b: bool) {
+        let (first_a, first_b) = *self.first.get_or_insert((a, b));
+
+        self.toggled.0 |= a != first_a;
+        self.toggled.1 |= b != first_b;
+        self.is_always_equal &= a == b;
+
+        if self.decoder.update(a, b).is_err() {
+            self.errors = self.errors.saturating_add(1);
+        }
+    }
+
+    /// Returns the report of the readings observed so far.
+    pub fn report(&self) -> WiringReport {
+        let (first_a, first_b) = self.first.unwrap_or_default();
+
+        let line_state = |is_toggled: bool, level: bool| match (is_toggled, level) {
+            (true, _) => LineState::Toggling,
+            (false, true) => LineState::StuckHigh,
+            (false, false) => LineState::StuckLow,
+        };
+
+        let observed = match self.decoder.counter() {
+            0 => None,
+            counter if counter > 0 => Some(Change::Positive),
+            _ => Some(Change::Negative),
+        };
+
+        let is_swapped = match (self.expected, observed) {
+            (Some(expected), Some(observed)) => Some(expected != observed),
+            _ => None,
+        };
+
+        WiringReport {
+            a: line_state(self.toggled.0, first_a),
+            b: line_state(self.toggled.1, first_b),
+            is_shorted: self.toggled.0 && self.toggled.1 && self.is_always_equal,
+            is_swapped,
+            errors: self.errors,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::simulator::{Channel, Fault, Simulator};
+
+    use super::*;
+
+    fn check(simulator: &mut Simulator, expected: Change) -> WiringReport {
+        let mut check = WiringCheck::new().with_expected(expected);
+
+        let sample = simulator.sample();
+        check.update(sample.a, sample.b);
+
+        for _ in 0..16 {
+            let sample = simulator.step(Change::Positive);
+            check.update(sample.a, sample.b);
+        }
+
+        check.report()
+    }
+
+    #[test]
+    fn ok() {
+        let report = check(&mut Simulator::new(), Change::Positive);
+
+        assert!(report.is_ok());
+        assert_eq!(report.is_swapped, Some(false));
+        assert_eq!(report.errors, 0);
+    }
+
+    #[test]
+    fn open() {
+        let mut simulator = Simulator::new();
+        simulator.inject(Fault::StuckHigh(Channel::B));
+
+        let report = check(&mut simulator, Change::Positive);
+
+        assert!(!report.is_ok());
+        assert_eq!(report.a, LineState::Toggling);
+        assert_eq!(report.b, LineState::StuckHigh);
+    }
+
+    #[test]
+    fn swapped() {
+        let mut simulator = Simulator::new();
+        simulator.inject(Fault::SwappedPhases);
+
+        let report = check(&mut simulator, Change::Positive);
+
+        assert!(!report.is_ok());
+        assert_eq!(report.is_swapped, Some(true));
+    }
+
+    #[test]
+    fn shorted() {
+        let mut check = WiringCheck::new();
+
+        for level in [true, false, true, false] {
+            check.update(level, level);
+        }
+
+        let report = check.report();
+
+        assert!(!report.is_ok());
+        assert!(report.is_shorted);
+        assert_eq!(report.is_swapped, None);
+    }
+}
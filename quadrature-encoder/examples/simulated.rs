@@ -0,0 +1,46 @@
+use quadrature_encoder::{
+    simulator::{Bounce, Distribution, Motion, Simulator, VirtualEncoder},
+    QuadStep, RotaryEncoder, RotaryMovement,
+};
+
+fn main() {
+    // A mechanical encoder bouncing up to twice per transition:
+    let simulator = Simulator::new().with_bounce(Bounce::new(
+        Distribution::Uniform { min: 0, max: 2 },
+        Distribution::Fixed(1),
+    ));
+
+    let simulated = VirtualEncoder::with_simulator(
+        simulator,
+        &[
+            Motion::Clockwise(10),
+            Motion::Bounce(2),
+            Motion::Hold(5),
+            Motion::CounterClockwise(3),
+            Motion::MissedSteps(-2),
+        ],
+    );
+
+    let mut encoder: RotaryEncoder<_, _, QuadStep> =
+        RotaryEncoder::new(simulated.clk(), simulated.dt());
+
+    while !simulated.is_finished() {
+        match encoder.poll() {
+            Ok(Some(movement)) => {
+                let direction = match movement {
+                    RotaryMovement::Clockwise => "clockwise",
+                    RotaryMovement::CounterClockwise => "counter-clockwise",
+                };
+                println!("Movement detected in {:?} direction.", direction)
+            }
+            Ok(_) => {}
+            Err(error) => println!("Error detected: {:?}.", error),
+        }
+    }
+
+    println!(
+        "Encoder is at position: {:?} (actual position: {:?}).",
+        encoder.position(),
+        simulated.position()
+    );
+}
@@ -0,0 +1,243 @@
+//! Absolute encoder drivers (e.g. of Gray-coded parallel, or SSI encoders).
+
+use quadrature_decoder::{AbsoluteDecoder, Coding};
+
+use crate::traits::*;
+
+/// An error indicating an absolute encoder's pin issue.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum AbsoluteError {
+    /// Failed reading the data pin of the given bit (or the SSI data pin, for bit `0`).
+    PinData(u8),
+    /// Failed writing the SSI clock pin.
+    PinClk,
+}
+
+/// An absolute encoder driver for Gray-coded words on `N` parallel input pins
+/// (e.g. of an optical, or magnetic absolute encoder with parallel outputs).
+///
+/// Pins are ordered from the least significant bit to the most significant one.
+#[derive(Debug)]
+pub struct GrayCodeEncoder<P, const N: usize> {
+    pins: [P; N],
+    decoder: AbsoluteDecoder,
+}
+
+impl<P, const N: usize> GrayCodeEncoder<P, N>
+where
+    P: InputPin,
+{
+    /// Creates an absolute encoder driver for the given data pins,
+    /// or `None` if the number of pins is not within `1..=31`.
+    pub fn new(pins: [P; N]) -> Option<Self> {
+        let decoder = AbsoluteDecoder::new(u8::try_from(N).ok()?)?;
+
+        Some(Self { pins, decoder })
+    }
+
+    /// Sets the encoder's reversed mode, making it report flipped positions.
+    pub fn reversed(mut self) -> Self {
+        self.decoder = self.decoder.reversed();
+        self
+    }
+
+    /// Returns `true` if the encoder is reversed, otherwise `false`.
+    pub fn is_reversed(&self) -> bool {
+        self.decoder.is_reversed()
+    }
+
+    /// Sets the encoder's zero offset (i.e. the raw position reported as `0`),
+    /// returning the modified encoder.
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.decoder = self.decoder.with_offset(offset);
+        self
+    }
+
+    /// Returns the encoder's number of positions (i.e. `2^N`).
+    pub fn resolution(&self) -> u32 {
+        self.decoder.resolution()
+    }
+
+    /// Reads the data pins, returning the encoder's absolute position within `[0, resolution)`,
+    /// subject to the encoder's offset and reversal.
+    pub fn absolute_position(&mut self) -> Result<u32, AbsoluteError> {
+        let mut word = 0;
+
+        for (bit, pin) in self.pins.iter_mut().enumerate() {
+            let is_high = pin
+                .is_high()
+                .map_err(|_| AbsoluteError::PinData(bit as u8))?;
+            word |= (is_high as u32) << bit;
+        }
+
+        Ok(self.decoder.decode(word))
+    }
+
+    /// Returns mutable borrows for the data pins.
+    pub fn pins_mut(&mut self) -> &mut [P; N] {
+        &mut self.pins
+    }
+
+    /// Consumes self, returning the data pins.
+    pub fn release(self) -> [P; N] {
+        self.pins
+    }
+}
+
+/// An absolute encoder driver for the synchronous serial interface (SSI),
+/// clocking the encoder's position word out (most significant bit first) via a clock output pin.
+///
+/// The clock idles high. Consecutive reads must be spaced by at least the encoder's monoflop time
+/// (typically 15 – 30 µs).
+#[derive(Debug)]
+pub struct SsiEncoder<Clk, Data> {
+    pins: (Clk, Data),
+    decoder: AbsoluteDecoder,
+}
+
+impl<Clk, Data> SsiEncoder<Clk, Data>
+where
+    Clk: OutputPin,
+    Data: InputPin,
+{
+    /// Creates an absolute SSI encoder driver for the given **clock** and **data** pins
+    /// and words of the given number of `bits` (binary-coded, by default),
+    /// or `None` if the number of bits is not within `1..=31`.
+    pub fn new(pin_clk: Clk, pin_data: Data, bits: u8) -> Option<Self> {
+        let decoder = AbsoluteDecoder::new(bits)?.with_coding(Coding::Binary);
+
+        Some(Self {
+            pins: (pin_clk, pin_data),
+            decoder,
+        })
+    }
+
+    /// Sets the encoder's coding to Gray-coded words, returning the modified encoder.
+    pub fn with_gray_code(mut self) -> Self {
+        self.decoder = self.decoder.with_coding(Coding::Gray);
+        self
+    }
+
+    /// Sets the encoder's reversed mode, making it report flipped positions.
+    pub fn reversed(mut self) -> Self {
+        self.decoder = self.decoder.reversed();
+        self
+    }
+
+    /// Returns `true` if the encoder is reversed, otherwise `false`.
+    pub fn is_reversed(&self) -> bool {
+        self.decoder.is_reversed()
+    }
+
+    /// Sets the encoder's zero offset (i.e. the raw position reported as `0`),
+    /// returning the modified encoder.
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        self.decoder = self.decoder.with_offset(offset);
+        self
+    }
+
+    /// Returns the encoder's number of positions (i.e. `2^bits`).
+    pub fn resolution(&self) -> u32 {
+        self.decoder.resolution()
+    }
+
+    /// Clocks the encoder's position word out, calling `half_period` after every clock edge
+    /// (e.g. `|| delay.delay_us(1)`, for a clock rate of 500 kHz), returning the encoder's
+    /// absolute position within `[0, resolution)`, subject to the encoder's offset and reversal.
+    ///
+    /// Every bit is read once its rising clock edge has been held for half a period.
+    pub fn absolute_position(
+        &mut self,
+        mut half_period: impl FnMut(),
+    ) -> Result<u32, AbsoluteError> {
+        let mut word = 0;
+
+        for _ in 0..self.decoder.bits() {
+            self.pins.0.set_low().map_err(|_| AbsoluteError::PinClk)?;
+            half_period();
+            self.pins.0.set_high().map_err(|_| AbsoluteError::PinClk)?;
+            half_period();
+
+            let is_high = self
+                .pins
+                .1
+                .is_high()
+                .map_err(|_| AbsoluteError::PinData(0))?;
+            word = (word << 1) | is_high as u32;
+        }
+
+        Ok(self.decoder.decode(word))
+    }
+
+    /// Returns mutable borrows for the **clock** and **data** pins.
+    pub fn pins_mut(&mut self) -> (&mut Clk, &mut Data) {
+        (&mut self.pins.0, &mut self.pins.1)
+    }
+
+    /// Consumes self, returning the **clock** and **data** pins.
+    pub fn release(self) -> (Clk, Data) {
+        self.pins
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+    use quadrature_decoder::binary_to_gray;
+
+    use super::*;
+
+    #[test]
+    fn gray_code() {
+        use PinState::*;
+
+        // Position 5 is Gray-coded as 0b111:
+        assert_eq!(binary_to_gray(5), 0b111);
+
+        let pins = [
+            PinMock::new(&[PinTransaction::get(High)]),
+            PinMock::new(&[PinTransaction::get(High)]),
+            PinMock::new(&[PinTransaction::get(High)]),
+        ];
+
+        let mut encoder = GrayCodeEncoder::new(pins).unwrap().reversed();
+        assert_eq!(encoder.absolute_position(), Ok(3));
+
+        for mut pin in encoder.release() {
+            pin.done();
+        }
+    }
+
+    #[test]
+    fn ssi() {
+        use PinState::*;
+
+        let pin_clk = PinMock::new(&[
+            PinTransaction::set(Low),
+            PinTransaction::set(High),
+            PinTransaction::set(Low),
+            PinTransaction::set(High),
+            PinTransaction::set(Low),
+            PinTransaction::set(High),
+        ]);
+        let pin_data = PinMock::new(&[
+            PinTransaction::get(High),
+            PinTransaction::get(Low),
+            PinTransaction::get(Low),
+        ]);
+
+        let mut encoder = SsiEncoder::new(pin_clk, pin_data, 3)
+            .unwrap()
+            .with_offset(1);
+
+        let mut edges = 0;
+        assert_eq!(encoder.absolute_position(|| edges += 1), Ok(3));
+        assert_eq!(edges, 6);
+
+        let (mut pin_clk, mut pin_data) = encoder.release();
+        pin_clk.done();
+        pin_data.done();
+    }
+}
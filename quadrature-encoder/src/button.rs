@@ -0,0 +1,258 @@
+//! Push-buttons (e.g. the integrated switch of EC11-style rotary encoders).
+
+use crate::{traits::*, Debouncer, PinSource};
+
+/// An event detected by a [`Button`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ButtonEvent {
+    /// The button has been pressed and released once.
+    Click,
+    /// The button has been pressed and released twice within the double-click window.
+    DoubleClick,
+    /// The button has been held down for the long-press duration.
+    LongPress,
+}
+
+/// The state of a button's event detection.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum State {
+    /// Released, with no click pending.
+    Idle,
+    /// Pressed since the given timestamp (as the second press of a double-click, or not).
+    Pressed { since: u64, is_second: bool },
+    /// Released at the given timestamp, waiting for a second press.
+    Released { since: u64 },
+    /// Held beyond a reported long-press (or since the first update), waiting for a release.
+    Held,
+}
+
+/// A push-button, detecting clicks, double-clicks and long-presses
+/// from a debounced input pin (e.g. the integrated switch of EC11-style rotary encoders).
+///
+/// Buttons are expected to be active-low (i.e. pulled up and shorted to ground when pressed),
+/// unless configured via [`active_high()`](Self::active_high).
+///
+/// All durations are in ticks, as measured by the timestamps passed to [`update()`](Self::update).
+#[derive(Debug)]
+pub struct Button<P> {
+    debouncer: Debouncer<P>,
+    is_active_high: bool,
+    long_press: u64,
+    double_click: u64,
+    state: State,
+}
+
+impl<P> Button<P>
+where
+    P: InputPin,
+{
+    /// Creates a button reading the given debounced pin,
+    /// reporting a long-press once held down for `long_press` ticks.
+    ///
+    /// Double-clicks are not detected, unless configured via [`with_double_click()`](Self::with_double_click).
+    pub fn new(debouncer: Debouncer<P>, long_press: u64) -> Self {
+        Self {
+            debouncer,
+            is_active_high: false,
+            long_press,
+            double_click: 0,
+            state: State::Idle,
+        }
+    }
+
+    /// Sets the button's double-click window, returning the modified button.
+    ///
+    /// A second press within `window` ticks of the first release reports a double-click,
+    /// which delays reporting single clicks by the window's duration.
+    pub fn with_double_click(mut self, window: u64) -> Self {
+        self.double_click = window;
+        self
+    }
+
+    /// Sets the button to be active-high (i.e. the pin being high when pressed),
+    /// returning the modified button.
+    pub fn active_high(mut self) -> Self {
+        self.is_active_high = true;
+        self
+    }
+
+    /// Returns `true` if the button is (debounced) pressed, otherwise `false`.
+    pub fn is_pressed(&self) -> bool {
+        self.debouncer.level() == Some(self.is_active_high)
+    }
+
+    /// Samples the button at the given timestamp `now`,
+    /// returning the detected event, if any.
+    ///
+    /// Click and long-press detection relies on timeouts, so the button must keep being updated
+    /// while pressed, or while a click is pending, even if the pin does not change.
+    ///
+    /// A button already pressed on the first update does not report any events until released.
+    pub fn update(&mut self, now: u64) -> Result<Option<ButtonEvent>, P::Error> {
+        let is_first = self.debouncer.level().is_none();
+        let change = self.debouncer.update(now)?;
+
+        if is_first {
+            if self.is_pressed() {
+                self.state = State::Held;
+            }
+            return Ok(None);
+        }
+
+        let is_pressed = change.map(|level| level == self.is_active_high);
+
+        let (state, event) = match (self.state, is_pressed) {
+            (State::Idle, Some(true)) => (
+                State::Pressed {
+                    since: now,
+                    is_second: false,
+                },
+                None,
+            ),
+            (
+                State::Pressed {
+                    is_second: true, ..
+                },
+                Some(false),
+            ) => (State::Idle, Some(ButtonEvent::DoubleClick)),
+            (State::Pressed { .. }, Some(false)) if self.double_click == 0 => {
+                (State::Idle, Some(ButtonEvent::Click))
+            }
+            (State::Pressed { .. }, Some(false)) => (State::Released { since: now }, None),
+            (State::Pressed { since, .. }, None) if now.wrapping_sub(since) >= self.long_press => {
+                (State::Held, Some(ButtonEvent::LongPress))
+            }
+            (State::Released { .. }, Some(true)) => (
+                State::Pressed {
+                    since: now,
+                    is_second: true,
+                },
+                None,
+            ),
+            (State::Released { since }, None) if now.wrapping_sub(since) > self.double_click => {
+                (State::Idle, Some(ButtonEvent::Click))
+            }
+            (State::Held, Some(false)) => (State::Idle, None),
+            (state, _) => (state, None),
+        };
+
+        self.state = state;
+
+        Ok(event)
+    }
+
+    /// Returns a mutable reference to the button's debouncer.
+    pub fn debouncer_mut(&mut self) -> &mut Debouncer<P> {
+        &mut self.debouncer
+    }
+
+    /// Consumes self, returning the button's debouncer.
+    pub fn release(self) -> Debouncer<P> {
+        self.debouncer
+    }
+}
+
+impl<P> Button<P>
+where
+    P: PinSource,
+{
+    /// Waits for the button's pin to leave its debounced level (e.g. for sleeping in between
+    /// presses), returning right away if the pin has not been updated yet.
+    ///
+    /// As pending clicks and long-presses rely on timeouts, this is meant to be raced
+    /// against a timer while a click is pending, or while pressed, calling `update()` afterwards.
+    pub async fn wait(&mut self) -> Result<(), P::Error> {
+        let Some(level) = self.debouncer.level() else {
+            return Ok(());
+        };
+
+        self.debouncer.pin_mut().wait_edge(level).await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use super::*;
+
+    use PinState::{High, Low};
+
+    fn events(
+        levels: &[(u64, PinState)],
+        configure: impl FnOnce(Button<PinMock>) -> Button<PinMock>,
+    ) -> Vec<(u64, ButtonEvent)> {
+        let pin = PinMock::new(
+            &levels
+                .iter()
+                .map(|&(_, level)| PinTransaction::get(level))
+                .collect::<Vec<_>>(),
+        );
+        let mut button = configure(Button::new(Debouncer::with_samples(pin, 1), 100));
+
+        let events = levels
+            .iter()
+            .filter_map(|&(now, _)| Some((now, button.update(now).unwrap()?)))
+            .collect();
+
+        button.release().release().done();
+
+        events
+    }
+
+    #[test]
+    fn click() {
+        let levels = [(0, High), (10, Low), (20, High), (30, High)];
+
+        assert_eq!(events(&levels, |button| button), [(20, ButtonEvent::Click)]);
+    }
+
+    #[test]
+    fn long_press() {
+        let levels = [
+            (0, High),
+            (10, Low),
+            (50, Low),
+            (110, Low),
+            (150, Low),
+            (160, High),
+        ];
+
+        assert_eq!(
+            events(&levels, |button| button),
+            [(110, ButtonEvent::LongPress)]
+        );
+    }
+
+    #[test]
+    fn double_click() {
+        let levels = [
+            (0, High),
+            (10, Low),
+            (20, High),
+            (30, Low),
+            (40, High),
+            // Single click, reported once the double-click window passed:
+            (100, Low),
+            (110, High),
+            (140, High),
+            (200, High),
+        ];
+
+        assert_eq!(
+            events(&levels, |button| button.with_double_click(50)),
+            [(40, ButtonEvent::DoubleClick), (200, ButtonEvent::Click)]
+        );
+    }
+
+    #[test]
+    fn initially_pressed() {
+        let levels = [(0, High), (10, Low), (20, High)];
+
+        assert_eq!(events(&levels, |button| button.active_high()), []);
+    }
+}
@@ -0,0 +1,18 @@
+//! Thin adapters mimicking the public APIs of other popular encoder crates.
+//!
+//! These are meant to ease incremental migration of existing projects:
+//! swap the import, keep the update loop, then move over to
+//! [`IncrementalEncoder`](crate::IncrementalEncoder) at your own pace.
+
+//! With the `rotary-encoder-hal`, or `rotary-encoder-embedded` feature enabled,
+//! [`RotaryMovement`](crate::RotaryMovement) and [`LinearMovement`](crate::LinearMovement)
+//! (as well as the adapters' direction types) further convert to/from the original crate's `Direction`,
+//! easing interop in mixed codebases.
+
+pub mod rotary_encoder_embedded;
+pub mod rotary_encoder_hal;
+
+/// An error indicating that a direction describes no movement (i.e. `Direction::None`),
+/// as returned when converting foreign directions into movements.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NoMovementError;
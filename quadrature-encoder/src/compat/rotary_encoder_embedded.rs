@@ -0,0 +1,389 @@
+//! An adapter mimicking the public API of the [`rotary-encoder-embedded`](https://crates.io/crates/rotary-encoder-embedded) crate.
+//!
+//! ```plain
+//! // before:
+//! use rotary_encoder_embedded::{Direction, RotaryEncoder};
+//! // after:
+//! use quadrature_encoder::compat::rotary_encoder_embedded::{Direction, RotaryEncoder};
+//!
+//! let mut rotary_encoder = RotaryEncoder::new(rotary_dt, rotary_clk).into_standard_mode();
+//!
+//! match rotary_encoder.update() {
+//!     Direction::Clockwise => { /* ... */ }
+//!     Direction::Anticlockwise => { /* ... */ }
+//!     Direction::None => {}
+//! }
+//! ```
+//!
+//! Note that (just like the original crate) the **data** pin comes first.
+
+use quadrature_decoder::{FullStep, IncrementalDecoder, QuadStep, StepMode};
+
+use crate::{traits::InputPin, RotaryEncoder as Encoder, RotaryMovement};
+
+/// The direction of rotation reported by [`RotaryEncoder::update`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    /// No direction is determined.
+    None,
+    /// Clockwise direction.
+    Clockwise,
+    /// Anti-clockwise direction.
+    Anticlockwise,
+}
+
+impl From<Option<RotaryMovement>> for Direction {
+    fn from(movement: Option<RotaryMovement>) -> Self {
+        match movement {
+            Some(RotaryMovement::Clockwise) => Self::Clockwise,
+            Some(RotaryMovement::CounterClockwise) => Self::Anticlockwise,
+            None => Self::None,
+        }
+    }
+}
+
+/// A rotary encoder's mode, determining its step-mode.
+pub trait Mode {
+    /// The mode's step-mode.
+    type Steps: StepMode;
+}
+
+/// Mode of a [`RotaryEncoder`] that has not been configured yet.
+#[derive(Debug)]
+pub struct InitializeMode;
+
+impl Mode for InitializeMode {
+    type Steps = FullStep;
+}
+
+/// Standard mode, reporting one direction per detent (i.e. full-step).
+#[derive(Debug)]
+pub struct StandardMode;
+
+impl Mode for StandardMode {
+    type Steps = FullStep;
+}
+
+/// Quadrature-table mode, reporting one direction per edge (i.e. quad-step).
+#[derive(Debug)]
+pub struct QuadratureTableMode;
+
+impl Mode for QuadratureTableMode {
+    type Steps = QuadStep;
+}
+
+/// A rotary encoder, backed by a (reversed) [`RotaryEncoder`](crate::RotaryEncoder).
+///
+/// Just like the original crate, it reports clockwise turns for the **data** pin leading the **clock** pin.
+/// Unlike the original crate's standard mode (reporting once both pins are low),
+/// the standard mode reports once both pins are high again (i.e. at the detent of common encoders).
+pub struct RotaryEncoder<M, Dt, Clk>
+where
+    M: Mode,
+{
+    encoder: Encoder<Clk, Dt, M::Steps>,
+}
+
+impl<Dt, Clk> RotaryEncoder<InitializeMode, Dt, Clk>
+where
+    Dt: InputPin,
+    Clk: InputPin,
+{
+    /// Initiates a new rotary encoder from the given **data** and **clock** pins.
+    pub fn new(pin_dt: Dt, pin_clk: Clk) -> Self {
+        Self {
+            encoder: Encoder::new(pin_clk, pin_dt).reversed(),
+        }
+    }
+
+    /// Configures the encoder to report one direction per detent.
+    pub fn into_standard_mode(self) -> RotaryEncoder<StandardMode, Dt, Clk> {
+        let (pin_dt, pin_clk) = self.release();
+        RotaryEncoder {
+            encoder: Encoder::new(pin_clk, pin_dt).reversed(),
+        }
+    }
+
+    /// Configures the encoder to report one direction per edge.
+    pub fn into_quadrature_table_mode(self) -> RotaryEncoder<QuadratureTableMode, Dt, Clk> {
+        let (pin_dt, pin_clk) = self.release();
+        RotaryEncoder {
+            encoder: Encoder::new(pin_clk, pin_dt).reversed(),
+        }
+    }
+}
+
+impl<M, Dt, Clk> RotaryEncoder<M, Dt, Clk>
+where
+    M: Mode,
+    Dt: InputPin,
+    Clk: InputPin,
+    IncrementalDecoder<M::Steps, i32>: Default,
+{
+    /// Updates the encoder from its pins, returning the detected direction.
+    ///
+    /// Invalid transitions and pin read errors are reported as `Direction::None`.
+    pub fn update(&mut self) -> Direction {
+        self.encoder.poll().unwrap_or_default().into()
+    }
+
+    /// Returns mutable borrows for the **data** and **clock** pins.
+    pub fn pins_mut(&mut self) -> (&mut Dt, &mut Clk) {
+        let (pin_clk, pin_dt) = self.encoder.pins_mut();
+        (pin_dt, pin_clk)
+    }
+
+    /// Consumes this encoder, returning the **data** and **clock** pins.
+    pub fn release(self) -> (Dt, Clk) {
+        let (pin_clk, pin_dt) = self.encoder.release();
+        (pin_dt, pin_clk)
+    }
+
+    /// Returns the wrapped encoder, for access to the full API.
+    pub fn encoder(&self) -> &Encoder<Clk, Dt, M::Steps> {
+        &self.encoder
+    }
+
+    /// Returns the wrapped encoder mutably, for access to the full API.
+    pub fn encoder_mut(&mut self) -> &mut Encoder<Clk, Dt, M::Steps> {
+        &mut self.encoder
+    }
+}
+
+#[cfg(feature = "rotary-encoder-embedded")]
+mod interop {
+    use ::rotary_encoder_embedded::Direction as Foreign;
+
+    use crate::{compat::NoMovementError, LinearMovement, RotaryMovement};
+
+    use super::Direction;
+
+    impl From<Direction> for Foreign {
+        fn from(direction: Direction) -> Self {
+            match direction {
+                Direction::Clockwise => Self::Clockwise,
+                Direction::Anticlockwise => Self::Anticlockwise,
+                Direction::None => Self::None,
+            }
+        }
+    }
+
+    impl From<Foreign> for Direction {
+        fn from(direction: Foreign) -> Self {
+            match direction {
+                Foreign::Clockwise => Self::Clockwise,
+                Foreign::Anticlockwise => Self::Anticlockwise,
+                Foreign::None => Self::None,
+            }
+        }
+    }
+
+    impl From<RotaryMovement> for Foreign {
+        fn from(movement: RotaryMovement) -> Self {
+            match movement {
+                RotaryMovement::Clockwise => Self::Clockwise,
+                RotaryMovement::CounterClockwise => Self::Anticlockwise,
+            }
+        }
+    }
+
+    impl TryFrom<Foreign> for RotaryMovement {
+        type Error = NoMovementError;
+
+        fn try_from(direction: Foreign) -> Result<Self, Self::Error> {
+            match direction {
+                Foreign::Clockwise => Ok(Self::Clockwise),
+                Foreign::Anticlockwise => Ok(Self::CounterClockwise),
+                Foreign::None => Err(NoMovementError),
+            }
+        }
+    }
+
+    impl From<LinearMovement> for Foreign {
+        /// Interprets linear movement as a rotary direction with the following mapping:
+        ///
+        /// - `LinearMovement::Forward => Direction::Clockwise`
+        /// - `LinearMovement::Backward => Direction::Anticlockwise`
+        fn from(movement: LinearMovement) -> Self {
+            match movement {
+                LinearMovement::Forward => Self::Clockwise,
+                LinearMovement::Backward => Self::Anticlockwise,
+            }
+        }
+    }
+
+    impl TryFrom<Foreign> for LinearMovement {
+        type Error = NoMovementError;
+
+        /// Interprets a rotary direction as linear movement with the following mapping:
+        ///
+        /// - `Direction::Clockwise => LinearMovement::Forward`
+        /// - `Direction::Anticlockwise => LinearMovement::Backward`
+        fn try_from(direction: Foreign) -> Result<Self, Self::Error> {
+            match direction {
+                Foreign::Clockwise => Ok(Self::Forward),
+                Foreign::Anticlockwise => Ok(Self::Backward),
+                Foreign::None => Err(NoMovementError),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use embedded_hal_mock::eh0::digital::{Mock, State, Transaction};
+
+        use crate::compat::rotary_encoder_embedded::{
+            tests::{pin_mock, SAMPLES},
+            RotaryEncoder,
+        };
+
+        use super::*;
+
+        #[test]
+        fn update_matches_original() {
+            let original_pin_mock = |level: fn(&(bool, bool)) -> bool| {
+                let transactions: Vec<_> = SAMPLES
+                    .iter()
+                    .map(|sample| match level(sample) {
+                        true => Transaction::get(State::High),
+                        false => Transaction::get(State::Low),
+                    })
+                    .collect();
+                Mock::new(&transactions)
+            };
+
+            let mut rotary_encoder = RotaryEncoder::new(
+                pin_mock(&SAMPLES, |sample| sample.1),
+                pin_mock(&SAMPLES, |sample| sample.0),
+            )
+            .into_standard_mode();
+            let mut original = ::rotary_encoder_embedded::RotaryEncoder::new(
+                original_pin_mock(|sample| sample.1),
+                original_pin_mock(|sample| sample.0),
+            )
+            .into_standard_mode();
+
+            // The directions get reported at different phases, but once per cycle:
+            for cycle in SAMPLES.chunks(4) {
+                let directions: Vec<_> = cycle
+                    .iter()
+                    .map(|_| Foreign::from(rotary_encoder.update()))
+                    .filter(|direction| *direction != Foreign::None)
+                    .collect();
+                let original_directions: Vec<_> = cycle
+                    .iter()
+                    .map(|_| original.update())
+                    .filter(|direction| *direction != Foreign::None)
+                    .collect();
+                assert_eq!(directions, original_directions);
+            }
+
+            let (mut pin_dt, mut pin_clk) = rotary_encoder.release();
+            pin_dt.done();
+            pin_clk.done();
+
+            let (mut pin_dt, mut pin_clk) = original.release();
+            pin_dt.done();
+            pin_clk.done();
+        }
+
+        #[test]
+        fn conversions() {
+            for movement in [RotaryMovement::Clockwise, RotaryMovement::CounterClockwise] {
+                assert_eq!(
+                    RotaryMovement::try_from(Foreign::from(movement)),
+                    Ok(movement)
+                );
+            }
+
+            for movement in [LinearMovement::Forward, LinearMovement::Backward] {
+                assert_eq!(
+                    LinearMovement::try_from(Foreign::from(movement)),
+                    Ok(movement)
+                );
+            }
+
+            assert_eq!(
+                RotaryMovement::try_from(Foreign::None),
+                Err(NoMovementError)
+            );
+            assert_eq!(Direction::from(Foreign::None), Direction::None);
+            assert_eq!(
+                Foreign::from(Direction::Anticlockwise),
+                Foreign::Anticlockwise
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Samples of the **clock** and **data** pins (starting from both being high),
+    /// turning two cycles clockwise, followed by two cycles anti-clockwise.
+    pub(super) const SAMPLES: [(bool, bool); 16] = [
+        (true, false),
+        (false, false),
+        (false, true),
+        (true, true),
+        (true, false),
+        (false, false),
+        (false, true),
+        (true, true),
+        (false, true),
+        (false, false),
+        (true, false),
+        (true, true),
+        (false, true),
+        (false, false),
+        (true, false),
+        (true, true),
+    ];
+
+    pub(super) fn pin_mock(
+        samples: &[(bool, bool)],
+        level: fn(&(bool, bool)) -> bool,
+    ) -> embedded_hal_mock::eh1::digital::Mock {
+        use embedded_hal_mock::eh1::digital::{Mock, State, Transaction};
+
+        // The adapter reads the pins' initial levels on creation, as well as on changing its mode:
+        let transactions: Vec<_> = [(true, true), (true, true)]
+            .iter()
+            .chain(samples)
+            .map(|sample| match level(sample) {
+                true => Transaction::get(State::High),
+                false => Transaction::get(State::Low),
+            })
+            .collect();
+
+        Mock::new(&transactions)
+    }
+
+    #[test]
+    fn update() {
+        use Direction::*;
+
+        let mut rotary_encoder = RotaryEncoder::new(
+            pin_mock(&SAMPLES, |sample| sample.1),
+            pin_mock(&SAMPLES, |sample| sample.0),
+        )
+        .into_standard_mode();
+
+        let directions: Vec<_> = SAMPLES.iter().map(|_| rotary_encoder.update()).collect();
+        assert_eq!(
+            directions,
+            [
+                [None, None, None, Clockwise],
+                [None, None, None, Clockwise],
+                [None, None, None, Anticlockwise],
+                [None, None, None, Anticlockwise],
+            ]
+            .concat()
+        );
+
+        let (mut pin_dt, mut pin_clk) = rotary_encoder.release();
+        pin_dt.done();
+        pin_clk.done();
+    }
+}
@@ -0,0 +1,298 @@
+//! An adapter mimicking the public API of the [`rotary-encoder-hal`](https://crates.io/crates/rotary-encoder-hal) crate.
+//!
+//! ```plain
+//! // before:
+//! use rotary_encoder_hal::{Direction, Rotary};
+//! // after:
+//! use quadrature_encoder::compat::rotary_encoder_hal::{Direction, Rotary};
+//!
+//! let mut enc = Rotary::new(pin_a, pin_b);
+//!
+//! match enc.update().unwrap() {
+//!     Direction::Clockwise => pos += 1,
+//!     Direction::CounterClockwise => pos -= 1,
+//!     Direction::None => {}
+//! }
+//! ```
+
+use quadrature_decoder::{IncrementalDecoder, QuadStep, StepMode};
+
+use crate::{traits::InputPin, Error, RotaryEncoder, RotaryMovement};
+
+/// The direction of rotation reported by [`Rotary::update`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Direction {
+    /// A clockwise turn.
+    Clockwise,
+    /// A counter-clockwise turn.
+    CounterClockwise,
+    /// No change.
+    None,
+}
+
+impl From<Option<RotaryMovement>> for Direction {
+    fn from(movement: Option<RotaryMovement>) -> Self {
+        match movement {
+            Some(RotaryMovement::Clockwise) => Self::Clockwise,
+            Some(RotaryMovement::CounterClockwise) => Self::CounterClockwise,
+            None => Self::None,
+        }
+    }
+}
+
+/// A rotary encoder, backed by a (reversed) [`RotaryEncoder`].
+///
+/// Just like the original crate, it reports a direction for every edge (i.e. quad-step),
+/// with pin `B` leading pin `A` when turning clockwise.
+pub struct Rotary<A, B, Steps = QuadStep> {
+    encoder: RotaryEncoder<A, B, Steps>,
+}
+
+impl<A, B, Steps> Rotary<A, B, Steps>
+where
+    A: InputPin,
+    B: InputPin,
+    Steps: StepMode,
+    IncrementalDecoder<Steps, i32>: Default,
+{
+    /// Accepts two input pins `pin_a` and `pin_b`.
+    pub fn new(pin_a: A, pin_b: B) -> Self {
+        Self {
+            encoder: RotaryEncoder::new(pin_a, pin_b).reversed(),
+        }
+    }
+
+    /// Returns mutable borrows for the two input pins.
+    pub fn pins(&mut self) -> (&mut A, &mut B) {
+        self.encoder.pins_mut()
+    }
+
+    /// Consumes this `Rotary`, returning the underlying pins `A` and `B`.
+    pub fn into_inner(self) -> (A, B) {
+        self.encoder.release()
+    }
+
+    /// Updates the `Rotary`, returning the `Direction` of the detected turn, if any.
+    pub fn update(&mut self) -> Result<Direction, Error> {
+        self.encoder.poll().map(Direction::from)
+    }
+
+    /// Returns the wrapped encoder, for access to the full API.
+    pub fn encoder(&self) -> &RotaryEncoder<A, B, Steps> {
+        &self.encoder
+    }
+
+    /// Returns the wrapped encoder mutably, for access to the full API.
+    pub fn encoder_mut(&mut self) -> &mut RotaryEncoder<A, B, Steps> {
+        &mut self.encoder
+    }
+}
+
+#[cfg(feature = "rotary-encoder-hal")]
+mod interop {
+    use ::rotary_encoder_hal::Direction as Foreign;
+
+    use crate::{compat::NoMovementError, LinearMovement, RotaryMovement};
+
+    use super::Direction;
+
+    impl From<Direction> for Foreign {
+        fn from(direction: Direction) -> Self {
+            match direction {
+                Direction::Clockwise => Self::Clockwise,
+                Direction::CounterClockwise => Self::CounterClockwise,
+                Direction::None => Self::None,
+            }
+        }
+    }
+
+    impl From<Foreign> for Direction {
+        fn from(direction: Foreign) -> Self {
+            match direction {
+                Foreign::Clockwise => Self::Clockwise,
+                Foreign::CounterClockwise => Self::CounterClockwise,
+                Foreign::None => Self::None,
+            }
+        }
+    }
+
+    impl From<RotaryMovement> for Foreign {
+        fn from(movement: RotaryMovement) -> Self {
+            match movement {
+                RotaryMovement::Clockwise => Self::Clockwise,
+                RotaryMovement::CounterClockwise => Self::CounterClockwise,
+            }
+        }
+    }
+
+    impl TryFrom<Foreign> for RotaryMovement {
+        type Error = NoMovementError;
+
+        fn try_from(direction: Foreign) -> Result<Self, Self::Error> {
+            match direction {
+                Foreign::Clockwise => Ok(Self::Clockwise),
+                Foreign::CounterClockwise => Ok(Self::CounterClockwise),
+                Foreign::None => Err(NoMovementError),
+            }
+        }
+    }
+
+    impl From<LinearMovement> for Foreign {
+        /// Interprets linear movement as a rotary direction with the following mapping:
+        ///
+        /// - `LinearMovement::Forward => Direction::Clockwise`
+        /// - `LinearMovement::Backward => Direction::CounterClockwise`
+        fn from(movement: LinearMovement) -> Self {
+            match movement {
+                LinearMovement::Forward => Self::Clockwise,
+                LinearMovement::Backward => Self::CounterClockwise,
+            }
+        }
+    }
+
+    impl TryFrom<Foreign> for LinearMovement {
+        type Error = NoMovementError;
+
+        /// Interprets a rotary direction as linear movement with the following mapping:
+        ///
+        /// - `Direction::Clockwise => LinearMovement::Forward`
+        /// - `Direction::CounterClockwise => LinearMovement::Backward`
+        fn try_from(direction: Foreign) -> Result<Self, Self::Error> {
+            match direction {
+                Foreign::Clockwise => Ok(Self::Forward),
+                Foreign::CounterClockwise => Ok(Self::Backward),
+                Foreign::None => Err(NoMovementError),
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::compat::rotary_encoder_hal::{
+            tests::{pin_mock, SAMPLES},
+            Rotary,
+        };
+
+        use super::*;
+
+        #[test]
+        fn update_matches_original() {
+            let samples: Vec<_> = [(true, true)].into_iter().chain(SAMPLES).collect();
+            let mut rotary: Rotary<_, _> = Rotary::new(
+                pin_mock(&samples, |sample| sample.0),
+                pin_mock(&samples, |sample| sample.1),
+            );
+            let mut original = ::rotary_encoder_hal::Rotary::new(
+                pin_mock(&SAMPLES, |sample| sample.0),
+                pin_mock(&SAMPLES, |sample| sample.1),
+            );
+
+            for _ in SAMPLES {
+                assert_eq!(
+                    Foreign::from(rotary.update().unwrap()),
+                    original.update().unwrap()
+                );
+            }
+
+            for (mut pin_a, mut pin_b) in [rotary.into_inner(), original.into_inner()] {
+                pin_a.done();
+                pin_b.done();
+            }
+        }
+
+        #[test]
+        fn conversions() {
+            for movement in [RotaryMovement::Clockwise, RotaryMovement::CounterClockwise] {
+                assert_eq!(
+                    RotaryMovement::try_from(Foreign::from(movement)),
+                    Ok(movement)
+                );
+            }
+
+            for movement in [LinearMovement::Forward, LinearMovement::Backward] {
+                assert_eq!(
+                    LinearMovement::try_from(Foreign::from(movement)),
+                    Ok(movement)
+                );
+            }
+
+            assert_eq!(
+                RotaryMovement::try_from(Foreign::None),
+                Err(NoMovementError)
+            );
+            assert_eq!(Direction::from(Foreign::None), Direction::None);
+            assert_eq!(
+                Foreign::from(Direction::CounterClockwise),
+                Foreign::CounterClockwise
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Samples of pins `A` and `B` (starting from both being high),
+    /// turning counter-clockwise and changing direction halfway through the second cycle.
+    pub(super) const SAMPLES: [(bool, bool); 14] = [
+        (false, true),
+        (false, false),
+        (true, false),
+        (true, true),
+        (false, true),
+        (false, false),
+        (false, true),
+        (true, true),
+        (true, false),
+        (false, false),
+        (false, true),
+        (true, true),
+        (true, false),
+        (true, true),
+    ];
+
+    pub(super) fn pin_mock(
+        samples: &[(bool, bool)],
+        level: fn(&(bool, bool)) -> bool,
+    ) -> embedded_hal_mock::eh1::digital::Mock {
+        use embedded_hal_mock::eh1::digital::{Mock, State, Transaction};
+
+        let transactions: Vec<_> = samples
+            .iter()
+            .map(|sample| match level(sample) {
+                true => Transaction::get(State::High),
+                false => Transaction::get(State::Low),
+            })
+            .collect();
+
+        Mock::new(&transactions)
+    }
+
+    #[test]
+    fn update() {
+        use Direction::*;
+
+        // The adapter reads the pins' initial levels on creation:
+        let samples: Vec<_> = [(true, true)].into_iter().chain(SAMPLES).collect();
+        let mut rotary: Rotary<_, _> = Rotary::new(
+            pin_mock(&samples, |sample| sample.0),
+            pin_mock(&samples, |sample| sample.1),
+        );
+
+        let directions: Vec<_> = SAMPLES.iter().map(|_| rotary.update().unwrap()).collect();
+        assert_eq!(
+            directions,
+            [
+                [CounterClockwise; 6].as_slice(),
+                [Clockwise; 7].as_slice(),
+                [CounterClockwise].as_slice()
+            ]
+            .concat()
+        );
+
+        let (mut pin_a, mut pin_b) = rotary.into_inner();
+        pin_a.done();
+        pin_b.done();
+    }
+}
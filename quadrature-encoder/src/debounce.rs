@@ -0,0 +1,180 @@
+//! Debounced input pins (e.g. an encoder's push button).
+
+use crate::traits::*;
+
+/// The criterion for a pin's level to be considered stable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Stability {
+    /// Stable after a number of consecutive samples.
+    Samples { required: u16, count: u16 },
+    /// Stable after a duration (in ticks) without changes.
+    Duration { required: u64, since: Option<u64> },
+}
+
+/// A debounced input pin, only reporting a new level once it has been stable
+/// for a number of consecutive samples, or for a duration,
+/// e.g. for reading an encoder's push button.
+#[derive(Debug)]
+pub struct Debouncer<P> {
+    pin: P,
+    stability: Stability,
+    level: Option<bool>,
+}
+
+impl<P> Debouncer<P>
+where
+    P: InputPin,
+{
+    /// Creates a debouncer for the given `pin`, accepting a new level
+    /// once it has been read for `samples` consecutive updates.
+    ///
+    /// Sample counts below `1` are clamped to `1`.
+    pub fn with_samples(pin: P, samples: u16) -> Self {
+        Self {
+            pin,
+            stability: Stability::Samples {
+                required: samples.max(1),
+                count: 0,
+            },
+            level: None,
+        }
+    }
+
+    /// Creates a debouncer for the given `pin`, accepting a new level
+    /// once it has been read for at least `duration` ticks,
+    /// as measured by the timestamps passed to [`update()`](Self::update).
+    pub fn with_duration(pin: P, duration: u64) -> Self {
+        Self {
+            pin,
+            stability: Stability::Duration {
+                required: duration,
+                since: None,
+            },
+            level: None,
+        }
+    }
+
+    /// Returns the debounced level (i.e. `true` if high),
+    /// or `None` if the pin has not been updated yet.
+    pub fn level(&self) -> Option<bool> {
+        self.level
+    }
+
+    /// Samples the pin at the given timestamp `now` (in ticks, as provided by the caller's clock,
+    /// and ignored by sample-count debouncers), returning the new debounced level if it changed,
+    /// otherwise `None`.
+    ///
+    /// The first update adopts the pin's level right away.
+    pub fn update(&mut self, now: u64) -> Result<Option<bool>, P::Error> {
+        let level = self.pin.is_high()?;
+
+        let Some(debounced) = self.level else {
+            self.level = Some(level);
+            return Ok(Some(level));
+        };
+
+        let is_stable = match &mut self.stability {
+            Stability::Samples { required, count } => match level == debounced {
+                true => {
+                    *count = 0;
+                    false
+                }
+                false => {
+                    *count += 1;
+                    *count >= *required
+                }
+            },
+            Stability::Duration { required, since } => match level == debounced {
+                true => {
+                    *since = None;
+                    false
+                }
+                false => now.wrapping_sub(*since.get_or_insert(now)) >= *required,
+            },
+        };
+
+        if !is_stable {
+            return Ok(None);
+        }
+
+        self.reset();
+        self.level = Some(level);
+
+        Ok(Some(level))
+    }
+
+    /// Resets the debouncer's pending level change, if any.
+    fn reset(&mut self) {
+        match &mut self.stability {
+            Stability::Samples { count, .. } => *count = 0,
+            Stability::Duration { since, .. } => *since = None,
+        }
+    }
+
+    /// Returns a mutable reference to the debounced pin.
+    pub fn pin_mut(&mut self) -> &mut P {
+        &mut self.pin
+    }
+
+    /// Consumes self, returning the debounced pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use super::*;
+
+    #[test]
+    fn samples() {
+        use PinState::{High, Low};
+
+        let levels = [Low, High, Low, High, High, High, Low];
+        let pin = PinMock::new(&levels.map(PinTransaction::get));
+
+        let mut debouncer = Debouncer::with_samples(pin, 3);
+
+        let changes: Vec<_> = (0..levels.len())
+            .map(|_| debouncer.update(0).unwrap())
+            .collect();
+
+        assert_eq!(
+            changes,
+            [Some(false), None, None, None, None, Some(true), None]
+        );
+        assert_eq!(debouncer.level(), Some(true));
+
+        debouncer.release().done();
+    }
+
+    #[test]
+    fn duration() {
+        use PinState::{High, Low};
+
+        let levels = [
+            (0, Low),
+            (10, High),
+            (12, Low),
+            (20, High),
+            (25, High),
+            (30, High),
+        ];
+        let pin = PinMock::new(&levels.map(|(_, level)| PinTransaction::get(level)));
+
+        let mut debouncer = Debouncer::with_duration(pin, 10);
+
+        let changes: Vec<_> = levels
+            .iter()
+            .map(|&(now, _)| debouncer.update(now).unwrap())
+            .collect();
+
+        assert_eq!(changes, [Some(false), None, None, None, None, Some(true)]);
+
+        debouncer.release().done();
+    }
+}
@@ -0,0 +1,124 @@
+//! Divider outputs, toggling a pin every `N` net counts of an encoder's travel.
+
+use core::ops::Neg;
+
+use num_traits::{One, WrappingAdd, WrappingSub, Zero};
+use quadrature_decoder::{Divider, Tick};
+
+use crate::{traits::*, OutputPinError};
+
+/// A driver of an output pin, toggling it for every [`Tick`]
+/// emitted by a [`Divider`] (i.e. every `N` net counts of travel),
+/// for triggering cameras, strobes, or sampling synchronized to travel distance.
+///
+/// The output thus is a square wave with a period of `2 * N` counts.
+#[derive(Debug)]
+pub struct DividerOutput<P, T = i32> {
+    pin: P,
+    divider: Divider<T>,
+    level: bool,
+}
+
+impl<P, T> DividerOutput<P, T>
+where
+    P: OutputPin,
+    T: Copy + PartialOrd + Zero + One + Neg<Output = T> + WrappingAdd + WrappingSub,
+{
+    /// Creates a divider output driving the given pin, toggling it every `divisor` net counts.
+    ///
+    /// The pin's initial level is assumed to be low.
+    pub fn new(pin: P, divisor: T) -> Self {
+        Self {
+            pin,
+            divider: Divider::new(divisor),
+            level: false,
+        }
+    }
+
+    /// Returns the output's divider.
+    pub fn divider(&self) -> &Divider<T> {
+        &self.divider
+    }
+
+    /// Returns the most recently output level.
+    pub fn level(&self) -> bool {
+        self.level
+    }
+
+    /// Updates the output for the given encoder `position`,
+    /// toggling the pin and returning the tick if one was emitted, otherwise `None`.
+    pub fn update(&mut self, position: T) -> Result<Option<Tick>, OutputPinError> {
+        let Some(tick) = self.divider.update(position) else {
+            return Ok(None);
+        };
+
+        let level = !self.level;
+
+        match level {
+            true => self.pin.set_high(),
+            false => self.pin.set_low(),
+        }
+        .map_err(|_| OutputPinError::PinTick)?;
+
+        self.level = level;
+
+        Ok(Some(tick))
+    }
+
+    /// Resets the output's divider, making the next observed position the new reference.
+    ///
+    /// The pin's level is left as is.
+    pub fn reset(&mut self) {
+        self.divider.reset();
+    }
+
+    /// Returns a mutable reference to the output's pin.
+    pub fn pin_mut(&mut self) -> &mut P {
+        &mut self.pin
+    }
+
+    /// Consumes self, returning the output's pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+    use quadrature_decoder::Change;
+
+    use super::*;
+
+    #[test]
+    fn update() {
+        let pin = PinMock::new(&[
+            PinTransaction::set(PinState::High),
+            PinTransaction::set(PinState::Low),
+        ]);
+
+        let mut output = DividerOutput::new(pin, 2);
+
+        assert_eq!(output.update(0), Ok(None));
+        assert_eq!(output.update(1), Ok(None));
+        assert_eq!(
+            output.update(2),
+            Ok(Some(Tick {
+                change: Change::Positive
+            }))
+        );
+        assert!(output.level());
+        assert_eq!(output.update(1), Ok(None));
+        assert_eq!(
+            output.update(0),
+            Ok(Some(Tick {
+                change: Change::Negative
+            }))
+        );
+        assert!(!output.level());
+
+        output.release().done();
+    }
+}
@@ -0,0 +1,62 @@
+//! Timestamps backed by the Cortex-M DWT cycle counter.
+
+use cortex_m::peripheral::{DCB, DWT};
+
+/// A monotonic clock backed by the Cortex-M DWT cycle counter (i.e. `CYCCNT`),
+/// providing core-clock resolution timestamps without occupying a timer peripheral.
+///
+/// The 32-bit cycle counter gets extended to 64 bits by counting its overflows,
+/// which requires [`now()`](Self::now) to be called at least once per overflow period
+/// (e.g. every ~26 s at a core clock of 160 MHz).
+///
+/// Timestamps are in core clock cycles, hence the clock's number of ticks per second
+/// equals the core clock frequency, as expected by `VelocityEstimator::new()`:
+///
+/// ```ignore
+/// let mut clock = DwtClock::new(&mut peripherals.DCB, &mut peripherals.DWT, CORE_CLOCK_HZ);
+/// let mut estimator = VelocityEstimator::new(clock.ticks_per_second());
+///
+/// let PollResult { movement, timestamp, .. } = encoder.poll_full(|| clock.now())?;
+/// ```
+#[derive(Debug)]
+pub struct DwtClock {
+    ticks_per_second: u32,
+    overflows: u32,
+    last_count: u32,
+}
+
+impl DwtClock {
+    /// Creates a clock, enabling the DWT cycle counter, running at the given core clock frequency.
+    ///
+    /// Not all Cortex-M cores provide a cycle counter (e.g. Cortex-M0/M0+ do not).
+    /// Use `DWT::has_cycle_counter()` to check for support, if in doubt.
+    pub fn new(dcb: &mut DCB, dwt: &mut DWT, ticks_per_second: u32) -> Self {
+        dcb.enable_trace();
+        DWT::unlock();
+        dwt.enable_cycle_counter();
+
+        Self {
+            ticks_per_second,
+            overflows: 0,
+            last_count: DWT::cycle_count(),
+        }
+    }
+
+    /// Returns the clock's number of ticks per second (i.e. the core clock frequency).
+    pub fn ticks_per_second(&self) -> u32 {
+        self.ticks_per_second
+    }
+
+    /// Returns the current timestamp in core clock cycles.
+    pub fn now(&mut self) -> u64 {
+        let count = DWT::cycle_count();
+
+        if count < self.last_count {
+            self.overflows = self.overflows.wrapping_add(1);
+        }
+
+        self.last_count = count;
+
+        ((self.overflows as u64) << 32) | (count as u64)
+    }
+}
@@ -0,0 +1,66 @@
+//! Edge flags for non-blocking (i.e. `nb`-style) polling.
+
+#[cfg(target_has_atomic = "8")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// A flag indicating whether any of an encoder's pins has changed state,
+/// such as an interrupt-pending flag set by a pin-change interrupt.
+///
+/// The trait is implemented for:
+///
+/// - `&AtomicBool` (on targets supporting atomic swaps), as commonly set from an interrupt handler.
+/// - any `FnMut() -> bool` closure, e.g. reading and clearing a peripheral's pending flag.
+pub trait EdgeFlag {
+    /// Returns `true` if an edge has occurred since the previous call, clearing the flag.
+    fn take(&mut self) -> bool;
+}
+
+#[cfg(target_has_atomic = "8")]
+impl EdgeFlag for &AtomicBool {
+    fn take(&mut self) -> bool {
+        self.swap(false, Ordering::AcqRel)
+    }
+}
+
+impl<F> EdgeFlag for F
+where
+    F: FnMut() -> bool,
+{
+    fn take(&mut self) -> bool {
+        self()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{QuadStep, RotaryEncoder, RotaryMovement};
+
+    use super::*;
+
+    #[test]
+    fn poll_nb() {
+        use PinState::{High, Low};
+
+        let pin_clk = PinMock::new(&[High, Low, Low].map(PinTransaction::get));
+        let pin_dt = PinMock::new(&[High, High, Low].map(PinTransaction::get));
+
+        let mut encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(pin_clk, pin_dt);
+        let edge = AtomicBool::new(false);
+
+        assert_eq!(encoder.poll_nb(&mut &edge), Err(nb::Error::WouldBlock));
+
+        edge.store(true, Ordering::Release);
+        assert_eq!(encoder.poll_nb(&mut &edge), Ok(RotaryMovement::Clockwise));
+        assert_eq!(encoder.poll_nb(&mut &edge), Err(nb::Error::WouldBlock));
+
+        assert_eq!(encoder.poll_nb(&mut || true), Ok(RotaryMovement::Clockwise));
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+}
@@ -0,0 +1,259 @@
+//! Emitted quadrature signals (e.g. for hardware-in-the-loop testing).
+
+use core::{cmp::Ordering, convert::Infallible};
+
+use quadrature_decoder::{
+    simulator::{Sample, Simulator},
+    Change,
+};
+
+use crate::{traits::*, OutputPinError};
+
+/// A placeholder for an absent output pin (e.g. of an emitter without index).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct NoPin;
+
+impl ErrorType for NoPin {
+    type Error = Infallible;
+}
+
+impl OutputPin for NoPin {
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+/// A driver of two (or three) output pins with emitted quadrature signals,
+/// emulating an (optionally indexed) encoder being moved, e.g. for hardware-in-the-loop testing.
+///
+/// The emitter's position is tracked in quarter-cycles (i.e. relative to `QuadStep`), starting at zero,
+/// where the **clock** and **data** pins are both high.
+///
+/// Without output pins, use the re-exported [`Simulator`] directly.
+#[derive(Debug)]
+pub struct QuadratureEmitter<Clk, Dt, Idx = NoPin> {
+    pins: (Clk, Dt, Idx),
+    simulator: Simulator<'static>,
+    sample: Option<Sample>,
+}
+
+impl<Clk, Dt> QuadratureEmitter<Clk, Dt, NoPin>
+where
+    Clk: OutputPin,
+    Dt: OutputPin,
+{
+    /// Creates an emitter driving the given **clock** and **data** pins.
+    ///
+    /// The pins' initial levels are left as is, until the first step (or [`sync()`](Self::sync)).
+    pub fn new(pin_clk: Clk, pin_dt: Dt) -> Self {
+        Self {
+            pins: (pin_clk, pin_dt, NoPin),
+            simulator: Simulator::new(),
+            sample: None,
+        }
+    }
+
+    /// Sets the emitter's **index** pin, pulsing it at every revolution's origin
+    /// for the given number of cycles per revolution (CPR), returning the modified emitter.
+    pub fn with_index<Idx>(
+        self,
+        pin_idx: Idx,
+        cycles_per_revolution: u32,
+    ) -> QuadratureEmitter<Clk, Dt, Idx>
+    where
+        Idx: OutputPin,
+    {
+        let (pin_clk, pin_dt, _) = self.pins;
+
+        QuadratureEmitter {
+            pins: (pin_clk, pin_dt, pin_idx),
+            simulator: self
+                .simulator
+                .with_cycles_per_revolution(cycles_per_revolution),
+            sample: None,
+        }
+    }
+}
+
+impl<Clk, Dt, Idx> QuadratureEmitter<Clk, Dt, Idx>
+where
+    Clk: OutputPin,
+    Dt: OutputPin,
+    Idx: OutputPin,
+{
+    /// Returns the emitter's position in quarter-cycles.
+    pub fn position(&self) -> i64 {
+        self.simulator.position()
+    }
+
+    /// Returns the emitter's current sample.
+    pub fn sample(&self) -> Sample {
+        self.simulator.sample()
+    }
+
+    /// Writes the emitter's current sample to all of its pins.
+    pub fn sync(&mut self) -> Result<Sample, OutputPinError> {
+        self.sample = None;
+        self.write(self.simulator.sample())
+    }
+
+    /// Moves the emitted signals by a quarter-cycle in the direction of `change`,
+    /// returning the resulting sample.
+    ///
+    /// Pins only get written to if their state changed.
+    pub fn step(&mut self, change: Change) -> Result<Sample, OutputPinError> {
+        let sample = self.simulator.step(change);
+        self.write(sample)
+    }
+
+    /// Moves the emitted signals by a quarter-cycle towards the given `target` position,
+    /// returning the direction moved, or `None` if the target has been reached.
+    pub fn step_towards(&mut self, target: i64) -> Result<Option<Change>, OutputPinError> {
+        let change = match target.cmp(&self.position()) {
+            Ordering::Less => Change::Negative,
+            Ordering::Equal => return Ok(None),
+            Ordering::Greater => Change::Positive,
+        };
+
+        self.step(change)?;
+
+        Ok(Some(change))
+    }
+
+    /// Moves the emitted signals to the given `target` position, a quarter-cycle at a time,
+    /// calling `wait` after every step (e.g. `|| delay.delay_us(100)`, for commanding the velocity).
+    pub fn move_to(&mut self, target: i64, mut wait: impl FnMut()) -> Result<(), OutputPinError> {
+        while self.step_towards(target)?.is_some() {
+            wait();
+        }
+
+        Ok(())
+    }
+
+    /// Resets the emitter's position back to zero, without writing to its pins.
+    pub fn reset(&mut self) {
+        self.simulator.reset();
+        self.sample = None;
+    }
+
+    /// Returns mutable borrows for the **clock**, **data** and **index** pins.
+    pub fn pins_mut(&mut self) -> (&mut Clk, &mut Dt, &mut Idx) {
+        (&mut self.pins.0, &mut self.pins.1, &mut self.pins.2)
+    }
+
+    /// Consumes self, returning the **clock**, **data** and **index** pins.
+    pub fn release(self) -> (Clk, Dt, Idx) {
+        self.pins
+    }
+
+    fn write(&mut self, sample: Sample) -> Result<Sample, OutputPinError> {
+        let previous = self.sample;
+
+        // Invalidate the sample until all pins have been written successfully:
+        self.sample = None;
+
+        let is_changed =
+            |level: fn(&Sample) -> bool| previous.map_or(true, |p| level(&p) != level(&sample));
+
+        if is_changed(|sample| sample.a) {
+            set_level(&mut self.pins.0, sample.a).map_err(|_| OutputPinError::PinClk)?;
+        }
+        if is_changed(|sample| sample.b) {
+            set_level(&mut self.pins.1, sample.b).map_err(|_| OutputPinError::PinDt)?;
+        }
+        if is_changed(|sample| sample.z) {
+            set_level(&mut self.pins.2, sample.z).map_err(|_| OutputPinError::PinIdx)?;
+        }
+
+        self.sample = Some(sample);
+
+        Ok(sample)
+    }
+}
+
+#[cfg(feature = "embassy-time")]
+impl<Clk, Dt, Idx> QuadratureEmitter<Clk, Dt, Idx>
+where
+    Clk: OutputPin,
+    Dt: OutputPin,
+    Idx: OutputPin,
+{
+    /// Moves the emitted signals to the given `target` position, a quarter-cycle every `period`
+    /// (e.g. `Duration::from_hz(4000)`), like [`move_to()`](Self::move_to), but waiting asynchronously.
+    pub async fn move_to_async(
+        &mut self,
+        target: i64,
+        period: embassy_time::Duration,
+    ) -> Result<(), OutputPinError> {
+        let mut ticker = embassy_time::Ticker::every(period);
+
+        while self.step_towards(target)?.is_some() {
+            ticker.next().await;
+        }
+
+        Ok(())
+    }
+}
+
+fn set_level<P: OutputPin>(pin: &mut P, is_high: bool) -> Result<(), P::Error> {
+    match is_high {
+        true => pin.set_high(),
+        false => pin.set_low(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+    use quadrature_decoder::{IncrementalDecoder, QuadStep};
+
+    use super::*;
+
+    #[test]
+    fn move_to() {
+        use PinState::*;
+
+        let pin_clk = PinMock::new(&[PinTransaction::set(Low), PinTransaction::set(High)]);
+        let pin_dt = PinMock::new(&[
+            PinTransaction::set(High),
+            PinTransaction::set(Low),
+            PinTransaction::set(High),
+            PinTransaction::set(Low),
+        ]);
+        let pin_idx = PinMock::new(&[
+            PinTransaction::set(Low),
+            PinTransaction::set(High),
+            PinTransaction::set(Low),
+        ]);
+
+        let mut emitter = QuadratureEmitter::new(pin_clk, pin_dt).with_index(pin_idx, 1);
+        let mut decoder: IncrementalDecoder<QuadStep> = Default::default();
+
+        // The emitted signals decode as a full cycle forwards, ending on the index:
+        while emitter.step_towards(4).unwrap().is_some() {
+            let sample = emitter.sample();
+            assert_eq!(
+                decoder.update(sample.a, sample.b),
+                Ok(Some(Change::Positive))
+            );
+        }
+        assert_eq!(decoder.counter(), 4);
+        assert!(emitter.sample().z);
+
+        let mut waits = 0;
+        emitter.move_to(3, || waits += 1).unwrap();
+        assert_eq!(waits, 1);
+        assert_eq!(emitter.position(), 3);
+
+        let (mut pin_clk, mut pin_dt, mut pin_idx) = emitter.release();
+        pin_clk.done();
+        pin_dt.done();
+        pin_idx.done();
+    }
+}
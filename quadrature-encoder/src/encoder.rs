@@ -1,9 +1,18 @@
 //! Quadrature-based encoder drivers.
 
+mod detent;
+mod dynamic;
 mod incremental;
 mod indexed;
+mod pins;
+mod step_dir;
+mod turn;
+mod up_down;
 
 pub use self::{
+    dynamic::{DynIncrementalEncoder, DynLinearEncoder, DynRotaryEncoder},
     incremental::{IncrementalEncoder, LinearEncoder, RotaryEncoder},
     indexed::{IndexedIncrementalEncoder, IndexedLinearEncoder, IndexedRotaryEncoder},
+    step_dir::StepDirEncoder,
+    up_down::UpDownEncoder,
 };
@@ -0,0 +1,84 @@
+//! Counting of detents (e.g. of EC11 encoders, emitting multiple pulses per click).
+
+use num_traits::{SaturatingAdd, Zero};
+use quadrature_decoder::Change;
+
+/// A counter of detents, accumulating pulses until a full detent has been moved.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct DetentCounter<T> {
+    pulses_per_detent: u8,
+    pulses: i8,
+    position: T,
+}
+
+impl<T> DetentCounter<T>
+where
+    T: Copy + Zero + SaturatingAdd + From<i8>,
+{
+    pub(crate) fn new(pulses_per_detent: u8) -> Self {
+        Self {
+            pulses_per_detent: pulses_per_detent.clamp(1, i8::MAX as u8),
+            pulses: 0,
+            position: Zero::zero(),
+        }
+    }
+
+    pub(crate) fn pulses_per_detent(&self) -> u8 {
+        self.pulses_per_detent
+    }
+
+    /// Updates the counter with the given pulse `change`,
+    /// returning the change of detents, if a full detent has been moved.
+    pub(crate) fn update(&mut self, change: Change) -> Option<Change> {
+        self.pulses += change as i8;
+
+        if self.pulses.unsigned_abs() < self.pulses_per_detent {
+            return None;
+        }
+
+        self.pulses = 0;
+        self.position = self.position.saturating_add(&(change as i8).into());
+
+        Some(change)
+    }
+
+    pub(crate) fn position(&self) -> T {
+        self.position
+    }
+
+    pub(crate) fn set_position(&mut self, position: T) {
+        self.pulses = 0;
+        self.position = position;
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.set_position(Zero::zero());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detents() {
+        let mut detents: DetentCounter<i32> = DetentCounter::new(4);
+
+        for _ in 0..3 {
+            assert_eq!(detents.update(Change::Positive), None);
+        }
+        assert_eq!(detents.update(Change::Positive), Some(Change::Positive));
+        assert_eq!(detents.position(), 1);
+
+        // Jittering within a detent does not count:
+        assert_eq!(detents.update(Change::Negative), None);
+        assert_eq!(detents.update(Change::Positive), None);
+        assert_eq!(detents.position(), 1);
+
+        for _ in 0..3 {
+            assert_eq!(detents.update(Change::Negative), None);
+        }
+        assert_eq!(detents.update(Change::Negative), Some(Change::Negative));
+        assert_eq!(detents.position(), 0);
+    }
+}
@@ -0,0 +1,380 @@
+//! An incremental encoder driver with a runtime-selectable step-mode.
+
+use core::marker::PhantomData;
+
+use num_traits::{One, SaturatingAdd, WrappingNeg, WrappingSub, Zero};
+use quadrature_decoder::{Change, DynIncrementalDecoder, GlitchFilter, StepModeKind};
+
+#[allow(unused_imports)]
+use crate::{
+    encoder::pins::*,
+    mode::{Async, Blocking, Movement, OperationMode, PollMode},
+    traits::*,
+    Error, ErrorPolicy, Linear, Overflow, PinSource, PollResult, Rotary,
+};
+
+/// Rotary encoder, with a runtime-selectable step-mode.
+pub type DynRotaryEncoder<Clk, Dt, T = i32, PM = Blocking> =
+    DynIncrementalEncoder<Rotary, Clk, Dt, T, PM>;
+/// Linear encoder, with a runtime-selectable step-mode.
+pub type DynLinearEncoder<Clk, Dt, T = i32, PM = Blocking> =
+    DynIncrementalEncoder<Linear, Clk, Dt, T, PM>;
+
+/// A robust incremental encoder like `IncrementalEncoder<…>`,
+/// but with its step-mode selected at runtime (e.g. from a configuration file),
+/// rather than via a type parameter.
+///
+/// Prefer `IncrementalEncoder<…>` whenever the step-mode is known at compile-time,
+/// as dispatching on the step-mode adds a (small) overhead to every poll.
+#[derive(Debug)]
+pub struct DynIncrementalEncoder<Mode, Clk, Dt, T = i32, PM = Blocking> {
+    decoder: DynIncrementalDecoder<T>,
+    pins: (Clk, Dt),
+    sample: (bool, bool),
+    active_low: (bool, bool),
+    is_reversed: bool,
+    error_policy: ErrorPolicy,
+    overflow: Overflow,
+    missed_steps: u32,
+    _mode: PhantomData<Mode>,
+    _poll_mode: PhantomData<PM>,
+}
+
+impl<Mode, Clk, Dt, T, PM> DynIncrementalEncoder<Mode, Clk, Dt, T, PM>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    PM: PollMode,
+{
+    /// Creates an incremental encoder driver for the given pins and step-mode.
+    pub fn new(mut pin_clk: Clk, mut pin_dt: Dt, step_mode: StepModeKind) -> Self {
+        // read the initial pin states to determine starting values
+        let sample = (
+            pin_clk.is_high().unwrap_or(false),
+            pin_dt.is_high().unwrap_or(false),
+        );
+
+        Self {
+            decoder: DynIncrementalDecoder::new(step_mode),
+            pins: (pin_clk, pin_dt),
+            sample,
+            active_low: (false, false),
+            is_reversed: false,
+            error_policy: ErrorPolicy::default(),
+            overflow: Overflow::default(),
+            missed_steps: 0,
+            _mode: PhantomData,
+            _poll_mode: PhantomData,
+        }
+    }
+
+    /// Sets the encoder's reversed mode, making it report flipped movements and positions.
+    pub fn reversed(mut self) -> Self {
+        self.is_reversed = true;
+        self
+    }
+
+    /// Returns `true` if the encoder is reversed, otherwise `false`.
+    pub fn is_reversed(&self) -> bool {
+        self.is_reversed
+    }
+
+    /// Sets the **clock** pin as active-low, making the encoder invert its readings.
+    pub fn with_clk_active_low(mut self) -> Self {
+        self.active_low.0 = true;
+        self
+    }
+
+    /// Sets the **data** pin as active-low, making the encoder invert its readings.
+    pub fn with_dt_active_low(mut self) -> Self {
+        self.active_low.1 = true;
+        self
+    }
+
+    /// Returns whether the **clock** and **data** pins are active-low.
+    pub fn active_low(&self) -> (bool, bool) {
+        self.active_low
+    }
+
+    /// Returns the encoder's step-mode.
+    pub fn step_mode(&self) -> StepModeKind {
+        self.decoder.step_mode()
+    }
+
+    /// Sets the encoder's step-mode.
+    ///
+    /// The position is kept as is (i.e. in pulses of the previous step-mode),
+    /// while the decoding state gets re-derived from the most recent reading.
+    pub fn set_step_mode(&mut self, step_mode: StepModeKind) {
+        self.decoder.set_step_mode(step_mode);
+    }
+
+    /// Returns mutable borrows for the signal channel pins.
+    pub fn pins_mut(&mut self) -> (&mut Clk, &mut Dt) {
+        (&mut self.pins.0, &mut self.pins.1)
+    }
+
+    /// Consumes self, returning the signal channel pins.
+    pub fn release(self) -> (Clk, Dt) {
+        self.pins
+    }
+
+    fn update(
+        &mut self,
+        sample: (bool, bool),
+        now: Option<u64>,
+    ) -> Result<Option<Mode::Movement>, Error> {
+        self.sample = sample;
+        let previous = self.decoder.counter();
+
+        let (clk, dt) = (sample.0 ^ self.active_low.0, sample.1 ^ self.active_low.1);
+        let change = match now {
+            Some(now) => self.decoder.update_at(clk, dt, now),
+            None => self.decoder.update(clk, dt),
+        };
+        let change: Option<Change> = match change {
+            Ok(change) => change,
+            Err(error) => self
+                .error_policy
+                .handle(error, &mut self.missed_steps, || self.decoder.reset_state())?,
+        };
+        if let Some(change) = change {
+            if let Some(counter) = self.overflow.handle(previous, change)? {
+                self.decoder.set_counter(counter);
+                self.decoder.clear_saturated();
+            }
+        }
+        let movement: Option<Mode::Movement> = change.map(From::from);
+
+        Ok(movement.map(|movement| {
+            if self.is_reversed() {
+                movement.flipped()
+            } else {
+                movement
+            }
+        }))
+    }
+
+    /// Resets the encoder to its initial state, keeping its step-mode.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.missed_steps = 0;
+    }
+
+    /// Sets the encoder's policy for handling invalid transitions, returning the modified encoder.
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// Returns the encoder's policy for handling invalid transitions.
+    pub fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    /// Sets the encoder's policy for handling invalid transitions.
+    pub fn set_error_policy(&mut self, error_policy: ErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Sets the encoder's behavior on its counter overflowing its bounds, returning the modified encoder.
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Returns the encoder's behavior on its counter overflowing its bounds.
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    /// Sets the encoder's behavior on its counter overflowing its bounds.
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    /// Returns the number of glitches rejected by the encoder's glitch filter
+    /// since the encoder's creation (or its most recent reset, or clearing of its diagnostics).
+    pub fn glitch_count(&self) -> u32 {
+        self.decoder.glitch_count()
+    }
+
+    /// Returns the number of missed steps (i.e. invalid transitions, regardless of the encoder's error policy)
+    /// since the encoder's creation (or its most recent reset, or clearing of its diagnostics).
+    pub fn missed_step_count(&self) -> u32 {
+        self.missed_steps
+    }
+
+    /// Clears the encoder's diagnostics counters.
+    pub fn clear_diagnostics(&mut self) {
+        self.decoder.clear_glitch_count();
+        self.missed_steps = 0;
+    }
+
+    /// Returns `true` if the encoder's position has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the encoder's creation (or its most recent reset), otherwise `false`.
+    pub fn is_saturated(&self) -> bool {
+        self.decoder.is_saturated()
+    }
+
+    /// Clears the encoder's sticky saturation flag.
+    pub fn clear_saturated(&mut self) {
+        self.decoder.clear_saturated();
+    }
+
+    /// Returns the encoder's position counter relative to its initial position
+    /// in pulses of the encoder's step-mode.
+    pub fn position(&self) -> T {
+        match self.is_reversed {
+            true => self.decoder.counter().wrapping_neg(),
+            false => self.decoder.counter(),
+        }
+    }
+
+    /// Sets the encoder's position.
+    pub fn set_position(&mut self, position: T) {
+        match self.is_reversed {
+            true => self.decoder.set_counter(position.wrapping_neg()),
+            false => self.decoder.set_counter(position),
+        }
+    }
+
+    /// Returns the encoder's travel since the given `previous` position,
+    /// accounting for the position having wrapped around (see `Overflow::Wrapping`) in between.
+    ///
+    /// The travel is only unambiguous if less than half the range of `T` (e.g. `i16`).
+    pub fn delta_since(&self, previous: T) -> T
+    where
+        T: WrappingSub,
+    {
+        self.position().wrapping_sub(&previous)
+    }
+
+    fn into_poll_mode<PM2>(self) -> DynIncrementalEncoder<Mode, Clk, Dt, T, PM2> {
+        DynIncrementalEncoder {
+            decoder: self.decoder,
+            pins: self.pins,
+            sample: self.sample,
+            active_low: self.active_low,
+            is_reversed: self.is_reversed,
+            error_policy: self.error_policy,
+            overflow: self.overflow,
+            missed_steps: self.missed_steps,
+            _mode: PhantomData,
+            _poll_mode: PhantomData,
+        }
+    }
+}
+
+impl<Mode, Clk, Dt, T> DynIncrementalEncoder<Mode, Clk, Dt, T, Blocking>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Updates the encoder's state based on the given **clock** and **data** pins,
+    /// returning the direction if a movement was detected, `None` if no movement was detected,
+    /// or `Err(_)` if an invalid input (i.e. a positional "jump") was detected.
+    pub fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        self.poll_at(None)
+    }
+
+    fn poll_at(&mut self, now: Option<u64>) -> Result<Option<Mode::Movement>, Error> {
+        let sample = self.pins.read()?;
+        self.update(sample, now)
+    }
+
+    /// Sets the encoder's glitch filter (e.g. for bouncy mechanical encoders), returning the modified encoder.
+    ///
+    /// Glitch filtering is only available in blocking mode (and disabled by `into_async()`).
+    pub fn with_glitch_filter(mut self, filter: GlitchFilter) -> Self {
+        self.decoder.set_glitch_filter(Some(filter));
+        self
+    }
+
+    /// Returns the encoder's glitch filter, if any.
+    pub fn glitch_filter(&self) -> Option<GlitchFilter> {
+        self.decoder.glitch_filter()
+    }
+
+    /// Sets the encoder's glitch filter, or disables glitch filtering if `None`.
+    pub fn set_glitch_filter(&mut self, filter: Option<GlitchFilter>) {
+        self.decoder.set_glitch_filter(filter);
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), returning the detected movement
+    /// together with the resulting position and a timestamp obtained from `now`.
+    pub fn poll_full(
+        &mut self,
+        now: impl FnOnce() -> u64,
+    ) -> Result<PollResult<Mode::Movement, T>, Error> {
+        let timestamp = now();
+        let movement = self.poll_at(Some(timestamp))?;
+
+        Ok(PollResult {
+            movement,
+            position: self.position(),
+            timestamp,
+        })
+    }
+}
+
+/// If the pins provided satisfy the `PinSource` trait, the into_async() method is exposed.
+impl<Mode, Clk, Dt, T> DynIncrementalEncoder<Mode, Clk, Dt, T, Blocking>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Reconfigure the driver so that poll() is an async fn
+    ///
+    /// This disables the encoder's glitch filter (if any).
+    pub fn into_async(mut self) -> DynIncrementalEncoder<Mode, Clk, Dt, T, Async> {
+        self.decoder.set_glitch_filter(None);
+        self.into_poll_mode()
+    }
+}
+
+impl<Mode, Clk, Dt, T> DynIncrementalEncoder<Mode, Clk, Dt, T, Async>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Updates the encoder's state based on the given **clock** and **data** pins,
+    /// returning the direction if a movement was detected, `None` if no movement was detected,
+    /// or `Err(_)` if an invalid input (i.e. a positional "jump") was detected.
+    ///
+    /// Waits asynchronously for any of the pins to change state, before returning.
+    pub async fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let sample = self.pins.wait_for_edge(self.sample).await?;
+        self.update(sample, None)
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), returning the detected movement
+    /// together with the resulting position and a timestamp obtained from `now`.
+    ///
+    /// The timestamp is obtained once a change of the pins has been awaited.
+    pub async fn poll_full(
+        &mut self,
+        now: impl FnOnce() -> u64,
+    ) -> Result<PollResult<Mode::Movement, T>, Error> {
+        let movement = self.poll().await?;
+
+        Ok(PollResult {
+            movement,
+            position: self.position(),
+            timestamp: now(),
+        })
+    }
+
+    /// Reconfigure the driver so that poll() is a blocking function
+    pub fn into_blocking(self) -> DynIncrementalEncoder<Mode, Clk, Dt, T, Blocking> {
+        self.into_poll_mode()
+    }
+}
@@ -1,17 +1,33 @@
 //! A robust incremental encoder driver with support for multiple step-modes.
 
-use core::marker::PhantomData;
+use core::{
+    borrow::Borrow,
+    marker::PhantomData,
+    ops::{Add, Div, Mul},
+};
 
-use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
-use quadrature_decoder::{Change, FullStep, IncrementalDecoder, StepMode};
+use num_traits::{FromPrimitive, One, SaturatingAdd, ToPrimitive, WrappingNeg, WrappingSub, Zero};
+use quadrature_decoder::{
+    AccelProfile, Accelerator, BatchReport, Change, DecoderState, FullStep, GlitchFilter,
+    IncrementalDecoder, Snapshot, SnapshotError, StepMode, VelocityEstimator, VelocityUnit,
+    WiringCheck,
+};
 
 #[allow(unused_imports)]
 use crate::{
+    encoder::{detent::DetentCounter, pins::*, turn::TurnCounter},
     mode::{Async, Blocking, Movement, OperationMode, PollMode},
     traits::*,
-    Error, InputPinError, Linear, Rotary,
+    Error, ErrorPolicy, Linear, Overflow, PinSource, PollResult, Rotary, RotaryScale, WiringReport,
 };
 
+#[cfg(feature = "nb")]
+use crate::EdgeFlag;
+#[cfg(feature = "stats")]
+use crate::PollStats;
+#[cfg(feature = "embassy-time")]
+use embassy_time::Instant;
+
 /// Rotary encoder.
 pub type RotaryEncoder<Clk, Dt, Steps = FullStep, T = i32, PM = Blocking> =
     IncrementalEncoder<Rotary, Clk, Dt, Steps, T, PM>;
@@ -23,11 +39,22 @@ pub type LinearEncoder<Clk, Dt, Steps = FullStep, T = i32, PM = Blocking> =
 #[derive(Debug)]
 pub struct IncrementalEncoder<Mode, Clk, Dt, Steps = FullStep, T = i32, PM = Blocking> {
     decoder: IncrementalDecoder<Steps, T>,
-    pin_clk: Clk,
-    pin_dt: Dt,
-    pin_clk_state: bool,
-    pin_dt_state: bool,
+    pins: (Clk, Dt),
+    sample: (bool, bool),
+    active_low: (bool, bool),
     is_reversed: bool,
+    error_policy: ErrorPolicy,
+    overflow: Overflow,
+    missed_steps: u32,
+    detents: Option<DetentCounter<T>>,
+    turns: Option<TurnCounter<T>>,
+    pulse: Option<Change>,
+    velocity: Option<VelocityEstimator>,
+    acceleration: Option<Accelerator>,
+    #[cfg(feature = "stats")]
+    stats: PollStats,
+    #[cfg(feature = "embassy-time")]
+    last_movement: Option<Instant>,
     _mode: PhantomData<Mode>,
     _poll_mode: PhantomData<PM>,
 }
@@ -49,20 +76,62 @@ where
         Dt: InputPin,
     {
         // read the initial pin states to determine starting values
-        let pin_clk_state = pin_clk.is_high().unwrap_or(false);
-        let pin_dt_state = pin_dt.is_high().unwrap_or(false);
+        let sample = (
+            pin_clk.is_high().unwrap_or(false),
+            pin_dt.is_high().unwrap_or(false),
+        );
+
+        Self::from_sample((pin_clk, pin_dt), sample)
+    }
+
+    /// Creates an incremental encoder driver for the given pins,
+    /// like [`new()`](Self::new), but propagating errors of the initial pin reads,
+    /// rather than falling back to low levels.
+    pub fn try_new(pin_clk: Clk, pin_dt: Dt) -> Result<Self, Error>
+    where
+        IncrementalDecoder<Steps, T>: Default,
+    {
+        let mut pins = (pin_clk, pin_dt);
+        let sample = pins.read()?;
+
+        Ok(Self::from_sample(pins, sample))
+    }
 
+    fn from_sample(pins: (Clk, Dt), sample: (bool, bool)) -> Self
+    where
+        IncrementalDecoder<Steps, T>: Default,
+    {
         Self {
             decoder: Default::default(),
-            pin_clk,
-            pin_dt,
-            pin_clk_state,
-            pin_dt_state,
+            pins,
+            sample,
+            active_low: (false, false),
             is_reversed: false,
+            error_policy: ErrorPolicy::default(),
+            overflow: Overflow::default(),
+            missed_steps: 0,
+            detents: None,
+            turns: None,
+            pulse: None,
+            velocity: None,
+            acceleration: None,
+            #[cfg(feature = "stats")]
+            stats: PollStats::default(),
+            #[cfg(feature = "embassy-time")]
+            last_movement: None,
             _mode: PhantomData,
             _poll_mode: PhantomData,
         }
     }
+
+    /// Performs a basic sanity read of the pins, reading every pin as both high and low,
+    /// returning `Err(_)` if a read fails or the readings are inconsistent.
+    ///
+    /// Intended to be called right after construction, catching broken pin configurations
+    /// early on, rather than as silent miscounts.
+    pub fn check_pins(&mut self) -> Result<(), Error> {
+        self.pins.check()
+    }
 }
 
 impl<Mode, Clk, Dt, Steps, T, PM> IncrementalEncoder<Mode, Clk, Dt, Steps, T, PM>
@@ -71,7 +140,7 @@ where
     Clk: InputPin,
     Dt: InputPin,
     Steps: StepMode,
-    T: Copy + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
     PM: PollMode,
 {
     /// Sets the encoder's reversed mode, making it report flipped movements and positions.
@@ -85,25 +154,106 @@ where
         self.is_reversed
     }
 
+    /// Sets the **clock** pin as active-low, making the encoder invert its readings
+    /// (e.g. for encoders with open-collector outputs, or wired via inverting level shifters).
+    pub fn with_clk_active_low(mut self) -> Self {
+        self.active_low.0 = true;
+        self
+    }
+
+    /// Sets the **data** pin as active-low, making the encoder invert its readings.
+    pub fn with_dt_active_low(mut self) -> Self {
+        self.active_low.1 = true;
+        self
+    }
+
+    /// Returns whether the **clock** and **data** pins are active-low.
+    pub fn active_low(&self) -> (bool, bool) {
+        self.active_low
+    }
+
+    /// Returns the given sample of pin levels as logical levels, subject to the pins' polarity.
+    fn logical(&self, (clk, dt): (bool, bool)) -> (bool, bool) {
+        (clk ^ self.active_low.0, dt ^ self.active_low.1)
+    }
+
     /// Returns mutable borrows for the signal channel pins.
     pub fn pins_mut(&mut self) -> (&mut Clk, &mut Dt) {
-        (&mut self.pin_clk, &mut self.pin_dt)
+        (&mut self.pins.0, &mut self.pins.1)
     }
 
     /// Consumes self, returning the signal channel pins.
     pub fn release(self) -> (Clk, Dt) {
-        (self.pin_clk, self.pin_dt)
+        self.pins
     }
 
-    /// Updates the internal decoder state, from the latest IO readings.
-    /// This is called within poll() / poll_async()
-    fn update(&mut self) -> Result<Option<Mode::Movement>, Error> {
-        let change: Option<Change> = self
-            .decoder
-            .update(self.pin_clk_state, self.pin_dt_state)
-            .map_err(Error::Quadrature)?;
+    /// Returns the most recently read (physical) levels of the **clock** and **data** pins,
+    /// regardless of the pins' polarity.
+    pub fn raw_state(&self) -> (bool, bool) {
+        self.sample
+    }
+
+    /// Returns the number of pulses per quadrature cycle of the encoder's step-mode.
+    pub fn pulses_per_cycle(&self) -> usize {
+        Steps::PULSES_PER_CYCLE
+    }
+
+    /// Updates the internal decoder state from the given sample of pin levels.
+    ///
+    /// This is the core shared by all poll modes, called within `poll()`.
+    fn update(
+        &mut self,
+        sample: (bool, bool),
+        now: Option<u64>,
+    ) -> Result<Option<Mode::Movement>, Error> {
+        self.sample = sample;
+        let previous = self.decoder.counter();
+
+        let (clk, dt) = self.logical(sample);
+        let change = match now {
+            Some(now) => self.decoder.update_at(clk, dt, now),
+            None => self.decoder.update(clk, dt),
+        };
+        let change: Option<Change> = match change {
+            Ok(change) => change,
+            Err(error) => self
+                .error_policy
+                .handle(error, &mut self.missed_steps, || self.decoder.reset_state())?,
+        };
+        if let Some(change) = change {
+            if let Some(counter) = self.overflow.handle(previous, change)? {
+                self.decoder.set_counter(counter);
+                self.decoder.clear_saturated();
+            }
+            if let Some(turns) = &mut self.turns {
+                turns.update(change);
+            }
+        }
+        self.pulse = change;
+        let change = match (&mut self.detents, change) {
+            (Some(detents), Some(change)) => {
+                let previous = detents.position();
+                let change = detents.update(change);
+                if let Some(change) = change {
+                    if let Some(position) = self.overflow.handle(previous, change)? {
+                        detents.set_position(position);
+                    }
+                }
+                change
+            }
+            (Some(_), None) => None,
+            (None, change) => change,
+        };
         let movement: Option<Mode::Movement> = change.map(From::from);
 
+        #[cfg(feature = "stats")]
+        self.stats.record_poll(movement.is_some());
+
+        #[cfg(feature = "embassy-time")]
+        if movement.is_some() {
+            self.last_movement = Some(Instant::now());
+        }
+
         Ok(movement.map(|movement| {
             if self.is_reversed() {
                 movement.flipped()
@@ -113,24 +263,460 @@ where
         }))
     }
 
+    /// Returns the encoder's poll and wakeup counters.
+    #[cfg(feature = "stats")]
+    pub fn stats(&self) -> &PollStats {
+        &self.stats
+    }
+
+    /// Resets the encoder's poll and wakeup counters.
+    #[cfg(feature = "stats")]
+    pub fn reset_stats(&mut self) {
+        self.stats.reset();
+    }
+
+    /// Returns the time elapsed since the encoder's most recent movement,
+    /// or `None` if it has not moved yet (e.g. for idle detection).
+    #[cfg(feature = "embassy-time")]
+    pub fn last_movement_age(&self) -> Option<embassy_time::Duration> {
+        self.last_movement.map(|instant| instant.elapsed())
+    }
+
+    /// Updates the encoder's state with the given batch of **clock** and **data** pin readings
+    /// (e.g. captured via DMA), rather than by reading the pins, returning a summary
+    /// of the net movement (subject to the encoder's reversal) and any invalid transitions.
+    pub fn update_batch<I>(&mut self, samples: I) -> BatchReport<T>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(bool, bool)>,
+    {
+        let active_low = self.active_low;
+        let mut report = self.decoder.update_batch(samples.into_iter().map(|sample| {
+            self.sample = *sample.borrow();
+            (self.sample.0 ^ active_low.0, self.sample.1 ^ active_low.1)
+        }));
+
+        self.missed_steps = self
+            .missed_steps
+            .saturating_add(report.errors.try_into().unwrap_or(u32::MAX));
+
+        if self.is_reversed {
+            report.delta = report.delta.wrapping_neg();
+        }
+
+        report
+    }
+
+    /// Enables the tracking of raw quarter-step movements (see [`sub_step()`](Self::sub_step)),
+    /// returning the modified encoder.
+    pub fn with_sub_steps(mut self) -> Self {
+        self.decoder.set_sub_steps(true);
+        self
+    }
+
+    /// Returns `true` if the encoder tracks raw quarter-step movements, otherwise `false`.
+    pub fn tracks_sub_steps(&self) -> bool {
+        self.decoder.tracks_sub_steps()
+    }
+
+    /// Enables (or disables) the tracking of raw quarter-step movements (see [`sub_step()`](Self::sub_step)).
+    pub fn set_sub_steps(&mut self, enabled: bool) {
+        self.decoder.set_sub_steps(enabled);
+    }
+
+    /// Returns the raw quarter-step movement detected by the most recent poll,
+    /// or `None` if no quarter-step movement was detected.
+    ///
+    /// This reports every quarter-step movement, regardless of the encoder's step-mode
+    /// (e.g. four per cycle, even with `FullStep` only reporting one movement per cycle).
+    /// Sub-steps are only tracked once enabled via [`with_sub_steps()`](Self::with_sub_steps).
+    pub fn sub_step(&self) -> Option<Mode::Movement> {
+        let movement: Option<Mode::Movement> = self.decoder.sub_step().map(From::from);
+
+        movement.map(|movement| {
+            if self.is_reversed() {
+                movement.flipped()
+            } else {
+                movement
+            }
+        })
+    }
+
     /// Resets the encoder to its initial state.
     pub fn reset(&mut self) {
         self.decoder.reset();
+        self.missed_steps = 0;
+
+        if let Some(detents) = &mut self.detents {
+            detents.reset();
+        }
+
+        if let Some(turns) = &mut self.turns {
+            turns.reset();
+        }
+
+        if let Some(velocity) = &mut self.velocity {
+            velocity.reset();
+        }
+
+        if let Some(acceleration) = &mut self.acceleration {
+            acceleration.reset();
+        }
+    }
+
+    /// Sets the encoder's policy for handling invalid transitions, returning the modified encoder.
+    pub fn with_error_policy(mut self, error_policy: ErrorPolicy) -> Self {
+        self.error_policy = error_policy;
+        self
+    }
+
+    /// Returns the encoder's policy for handling invalid transitions.
+    pub fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    /// Sets the encoder's policy for handling invalid transitions.
+    pub fn set_error_policy(&mut self, error_policy: ErrorPolicy) {
+        self.error_policy = error_policy;
+    }
+
+    /// Sets the encoder's behavior on its counter overflowing its bounds, returning the modified encoder.
+    pub fn with_overflow(mut self, overflow: Overflow) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Returns the encoder's behavior on its counter overflowing its bounds.
+    pub fn overflow(&self) -> Overflow {
+        self.overflow
+    }
+
+    /// Sets the encoder's behavior on its counter overflowing its bounds.
+    pub fn set_overflow(&mut self, overflow: Overflow) {
+        self.overflow = overflow;
+    }
+
+    /// Returns the number of glitches rejected by the encoder's glitch filter
+    /// since the encoder's creation (or its most recent reset, or clearing of its diagnostics).
+    pub fn glitch_count(&self) -> u32 {
+        self.decoder.glitch_count()
     }
 
-    /// Returns the encoder's position counter relative to its initial position in number of cycles.
+    /// Returns the number of missed steps (i.e. invalid transitions, regardless of the encoder's error policy)
+    /// since the encoder's creation (or its most recent reset, or clearing of its diagnostics).
+    pub fn missed_step_count(&self) -> u32 {
+        self.missed_steps
+    }
+
+    /// Clears the encoder's diagnostics counters.
+    pub fn clear_diagnostics(&mut self) {
+        self.decoder.clear_glitch_count();
+        self.missed_steps = 0;
+    }
+
+    /// Returns `true` if the encoder's position has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the encoder's creation (or its most recent reset), otherwise `false`,
+    /// in which case the reported position is no longer trustworthy.
+    ///
+    /// The flag is sticky, staying set until cleared via [`clear_saturated()`](Self::clear_saturated),
+    /// or [`reset()`](Self::reset).
+    pub fn is_saturated(&self) -> bool {
+        self.decoder.is_saturated()
+    }
+
+    /// Clears the encoder's sticky saturation flag.
+    pub fn clear_saturated(&mut self) {
+        self.decoder.clear_saturated();
+    }
+
+    /// Returns a plain-data snapshot of the encoder's decoder state (i.e. without pins).
+    ///
+    /// The snapshot's counter is the decoder's raw counter, unaffected by the encoder's reversal.
+    pub fn decoder_state(&self) -> DecoderState<Steps, T> {
+        self.decoder.state()
+    }
+
+    /// Sets the encoder's decoder state to the given snapshot.
+    pub fn set_decoder_state(&mut self, state: DecoderState<Steps, T>) {
+        self.decoder.set_state(state);
+    }
+
+    /// Returns a compact snapshot of the encoder's position (e.g. for persisting it across resets
+    /// in backup RAM, or EEPROM), which the encoder can later be rehydrated from via [`restore()`](Self::restore).
+    ///
+    /// Unlike the decoder's snapshot, the snapshot's counter is the encoder's position (see `position()`).
+    pub fn snapshot(&self) -> Snapshot<T> {
+        Snapshot {
+            counter: self.position(),
+            ..self.decoder.snapshot()
+        }
+    }
+
+    /// Restores the encoder's position and phase from the given `snapshot`,
+    /// returning `Err(_)` if the snapshot was taken of an encoder of another step-mode,
+    /// or has an invalid phase (leaving the encoder unchanged in that case).
+    pub fn restore(&mut self, snapshot: Snapshot<T>) -> Result<(), SnapshotError> {
+        self.decoder.restore(snapshot)?;
+        self.set_position(snapshot.counter);
+
+        Ok(())
+    }
+
+    /// Returns the encoder's position counter relative to its initial position in number of cycles,
+    /// or in number of detents, if the encoder has a number of pulses per detent.
     pub fn position(&self) -> T {
+        let counter = match &self.detents {
+            Some(detents) => detents.position(),
+            None => self.decoder.counter(),
+        };
+
         match self.is_reversed {
-            true => self.decoder.counter().wrapping_neg(),
-            false => self.decoder.counter(),
+            true => counter.wrapping_neg(),
+            false => counter,
         }
     }
 
-    /// Sets the encoder's position.
+    /// Sets the encoder's position (in number of detents, if the encoder has a number of pulses per detent).
     pub fn set_position(&mut self, position: T) {
+        let counter = match self.is_reversed {
+            true => position.wrapping_neg(),
+            false => position,
+        };
+
+        match &mut self.detents {
+            Some(detents) => detents.set_position(counter),
+            None => self.decoder.set_counter(counter),
+        }
+    }
+
+    /// Returns the encoder's travel since the given `previous` position,
+    /// accounting for the position having wrapped around (see `Overflow::Wrapping`) in between.
+    ///
+    /// The travel is only unambiguous if less than half the range of `T` (e.g. `i16`).
+    pub fn delta_since(&self, previous: T) -> T
+    where
+        T: WrappingSub,
+    {
+        self.position().wrapping_sub(&previous)
+    }
+
+    /// Returns the encoder's raw position counter in number of pulses (of the encoder's step-mode),
+    /// regardless of the encoder's number of pulses per detent.
+    pub fn raw_position(&self) -> T {
         match self.is_reversed {
-            true => self.decoder.set_counter(position.wrapping_neg()),
-            false => self.decoder.set_counter(position),
+            true => self.decoder.counter().wrapping_neg(),
+            false => self.decoder.counter(),
+        }
+    }
+
+    /// Sets the encoder's number of pulses per detent (relative to the encoder's step-mode,
+    /// e.g. `4` for an EC11 encoder in `QuadStep` mode), returning the modified encoder.
+    ///
+    /// The encoder then reports a single movement per detent, with its position counting detents
+    /// (starting at `0`), while the pulses remain available via [`raw_position()`](Self::raw_position).
+    /// Jittering back and forth within a detent does not report any movements.
+    ///
+    /// Batch updates (via `update_batch()`) only advance the raw position.
+    /// This should be called before [`with_initial_position()`](Self::with_initial_position), if at all.
+    pub fn with_pulses_per_detent(mut self, pulses_per_detent: u8) -> Self {
+        self.detents = Some(DetentCounter::new(pulses_per_detent));
+        self
+    }
+
+    /// Returns the encoder's number of pulses per detent (i.e. `1`, if not provided).
+    pub fn pulses_per_detent(&self) -> u8 {
+        self.detents
+            .as_ref()
+            .map_or(1, DetentCounter::pulses_per_detent)
+    }
+
+    /// Sets the encoder's initial position (e.g. restored from non-volatile storage at boot),
+    /// returning the modified encoder.
+    ///
+    /// The position is interpreted relative to the encoder's reversal,
+    /// hence this should be called after [`reversed()`](Self::reversed), if at all.
+    pub fn with_initial_position(mut self, position: T) -> Self {
+        self.set_position(position);
+        self
+    }
+
+    /// Sets the encoder's velocity estimator, returning the modified encoder.
+    ///
+    /// The estimator gets updated by every call of `poll_full()`,
+    /// using the timestamps obtained from the provided clock,
+    /// which thus must be in ticks of the estimator's `ticks_per_second`.
+    pub fn with_velocity_estimator(mut self, estimator: VelocityEstimator) -> Self {
+        self.velocity = Some(estimator);
+        self
+    }
+
+    /// Returns the encoder's velocity estimator, if any.
+    pub fn velocity_estimator(&self) -> Option<&VelocityEstimator> {
+        self.velocity.as_ref()
+    }
+
+    /// Returns the encoder's estimated velocity at the given timestamp in pulses per second,
+    /// with positive values corresponding to positive movements (subject to the encoder's reversal),
+    /// or `None` if the encoder has no velocity estimator.
+    pub fn velocity(&self, now: u64) -> Option<f32> {
+        Some(self.velocity.as_ref()?.velocity(now))
+    }
+
+    /// Returns the encoder's estimated velocity at the given timestamp in the given unit (e.g. RPM),
+    /// or `None` if the encoder has no velocity estimator,
+    /// or the resolution required by the unit has not been provided.
+    pub fn velocity_in(&self, now: u64, unit: VelocityUnit) -> Option<f32> {
+        self.velocity.as_ref()?.velocity_in(now, unit)
+    }
+
+    /// Sets the encoder's acceleration profile (e.g. for UI knobs), returning the modified encoder.
+    ///
+    /// The profile gets applied to every movement detected by `poll_full()`,
+    /// using the timestamps obtained from the provided clock,
+    /// with the scaled delta being available via [`accelerated_delta()`](Self::accelerated_delta).
+    pub fn with_acceleration(mut self, profile: AccelProfile) -> Self {
+        self.acceleration = Some(Accelerator::new(profile));
+        self
+    }
+
+    /// Returns the encoder's acceleration profile, if any.
+    pub fn acceleration(&self) -> Option<AccelProfile> {
+        Some(self.acceleration.as_ref()?.profile())
+    }
+
+    /// Returns the scaled delta of the most recent `poll_full()`
+    /// (i.e. `0` if it detected no movement, subject to the encoder's reversal),
+    /// or `None` if the encoder has no acceleration profile.
+    pub fn accelerated_delta(&self) -> Option<i32> {
+        Some(self.acceleration.as_ref()?.delta())
+    }
+
+    /// Updates the encoder's velocity estimator and accelerator (if any)
+    /// with the movement detected at `timestamp`.
+    ///
+    /// The velocity estimator gets fed the pulse detected, rather than the movement,
+    /// which counts detents if the encoder has a number of pulses per detent.
+    fn record_movement(&mut self, timestamp: u64, movement: Option<&Mode::Movement>) {
+        let change = movement.map(|movement| match *movement == Change::Positive.into() {
+            true => Change::Positive,
+            false => Change::Negative,
+        });
+
+        if let Some(velocity) = &mut self.velocity {
+            let pulse = self.pulse.map(|pulse| match (pulse, self.is_reversed) {
+                (Change::Positive, false) | (Change::Negative, true) => Change::Positive,
+                (Change::Negative, false) | (Change::Positive, true) => Change::Negative,
+            });
+            velocity.update(timestamp, pulse);
+        }
+
+        if let Some(acceleration) = &mut self.acceleration {
+            acceleration.update(timestamp, change);
+        }
+    }
+
+    fn into_poll_mode<PM2>(self) -> IncrementalEncoder<Mode, Clk, Dt, Steps, T, PM2> {
+        IncrementalEncoder {
+            decoder: self.decoder,
+            pins: self.pins,
+            sample: self.sample,
+            active_low: self.active_low,
+            is_reversed: self.is_reversed,
+            error_policy: self.error_policy,
+            overflow: self.overflow,
+            missed_steps: self.missed_steps,
+            detents: self.detents,
+            turns: self.turns,
+            pulse: self.pulse,
+            velocity: self.velocity,
+            acceleration: self.acceleration,
+            #[cfg(feature = "stats")]
+            stats: self.stats,
+            #[cfg(feature = "embassy-time")]
+            last_movement: self.last_movement,
+            _mode: PhantomData,
+            _poll_mode: PhantomData,
+        }
+    }
+}
+
+impl<Clk, Dt, Steps, T, PM> IncrementalEncoder<Rotary, Clk, Dt, Steps, T, PM>
+where
+    Clk: InputPin,
+    Dt: InputPin,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    PM: PollMode,
+{
+    /// Sets the encoder's number of pulses per revolution (PPR), returning the modified encoder,
+    /// which then keeps track of its turns (see [`revolutions()`](Self::revolutions)
+    /// and [`angle_in_turn()`](Self::angle_in_turn)), starting at its current position.
+    ///
+    /// The number of pulses is expected to be positive (falling back to `1` otherwise) and relative
+    /// to the encoder's step-mode (i.e. `cycles_per_revolution * Steps::PULSES_PER_CYCLE`),
+    /// regardless of its number of pulses per detent.
+    ///
+    /// Turns get tracked by movements, rather than by positions, hence they are unaffected by
+    /// the position wrapping around (see `Overflow::Wrapping`), or being set via `set_position()`.
+    /// Batch updates (via `update_batch()`) only advance the raw position.
+    pub fn with_pulses_per_revolution(mut self, pulses_per_revolution: T) -> Self
+    where
+        T: PartialOrd,
+    {
+        let pulses_per_revolution = match pulses_per_revolution > T::zero() {
+            true => pulses_per_revolution,
+            false => T::one(),
+        };
+
+        self.turns = Some(TurnCounter::new(pulses_per_revolution));
+        self
+    }
+
+    /// Returns the encoder's number of pulses per revolution (PPR), if provided.
+    pub fn pulses_per_revolution(&self) -> Option<T> {
+        self.turns.as_ref().map(TurnCounter::pulses_per_revolution)
+    }
+
+    fn oriented_turns(&self) -> Option<TurnCounter<T>> {
+        self.turns.map(|turns| match self.is_reversed {
+            true => turns.flipped(),
+            false => turns,
+        })
+    }
+
+    /// Returns the number of full turns since the turns' origin (i.e. the encoder's position
+    /// when provided its PPR, or its most recent reset), rounding towards negative infinity,
+    /// with positive values corresponding to positive movements (subject to the encoder's reversal),
+    /// or `0` if no PPR has been provided.
+    pub fn revolutions(&self) -> i32 {
+        self.oriented_turns().map_or(0, |turns| turns.revolutions())
+    }
+
+    /// Returns the encoder's number of pulses into the current turn, within `[0, PPR)`,
+    /// or `None` if no PPR has been provided.
+    pub fn position_in_rev(&self) -> Option<T> {
+        self.oriented_turns().map(|turns| turns.pulses())
+    }
+
+    /// Returns the encoder's angle within the current turn in degrees, within `[0, 360)`,
+    /// or `None` if no PPR has been provided, or the angle is not representable by `U`.
+    ///
+    /// Integer outputs (i.e. a coarse fixed-point type) get rounded down to whole degrees.
+    pub fn angle_in_turn<U>(&self) -> Option<U>
+    where
+        T: ToPrimitive,
+        U: Copy + FromPrimitive + Add<Output = U> + Mul<Output = U> + Div<Output = U>,
+    {
+        let turns = self.oriented_turns()?;
+        let scale: RotaryScale<U> = RotaryScale::new(turns.pulses_per_revolution().to_u32()?)?;
+
+        scale.degrees(turns.pulses())
+    }
+
+    /// Moves the origin of the encoder's turns to its current position.
+    pub fn reset_turns(&mut self) {
+        if let Some(turns) = &mut self.turns {
+            turns.reset();
         }
     }
 }
@@ -141,7 +727,7 @@ where
     Clk: InputPin,
     Dt: InputPin,
     Steps: StepMode,
-    T: Copy + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
 {
     /// Updates the encoder's state based on the given **clock** and **data** pins,
     /// returning the direction if a movement was detected, `None` if no movement was detected,
@@ -152,54 +738,135 @@ where
     /// you would either call `encoder.poll()` directly, or via `encoder.poll().unwrap_or_default()`
     /// to fall back to `None` in case of `Err(_)`.
     pub fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
-        self.pin_clk_state = self
-            .pin_clk
-            .is_high()
-            .map_err(|_| Error::InputPin(InputPinError::PinClk))?;
-        self.pin_dt_state = self
-            .pin_dt
-            .is_high()
-            .map_err(|_| Error::InputPin(InputPinError::PinDt))?;
-        self.update()
+        self.poll_at(None)
+    }
+
+    fn poll_at(&mut self, now: Option<u64>) -> Result<Option<Mode::Movement>, Error> {
+        #[cfg(feature = "stats")]
+        self.stats.record_wakeup();
+
+        let sample = self.pins.read()?;
+        self.update(sample, now)
+    }
+
+    /// Sets the encoder's glitch filter (e.g. for bouncy mechanical encoders), returning the modified encoder.
+    ///
+    /// Samples rejected by the filter are reported as no movement, rather than as `Err(_)`.
+    /// Duration-based filters use the timestamps obtained by `poll_full()`,
+    /// while samples of `poll()` pass them right away.
+    ///
+    /// Glitch filtering relies on the pins being sampled repeatedly,
+    /// hence is only available in blocking mode (and disabled by `into_async()`).
+    pub fn with_glitch_filter(mut self, filter: GlitchFilter) -> Self {
+        self.decoder.set_glitch_filter(Some(filter));
+        self
+    }
+
+    /// Returns the encoder's glitch filter, if any.
+    pub fn glitch_filter(&self) -> Option<GlitchFilter> {
+        self.decoder.glitch_filter()
+    }
+
+    /// Sets the encoder's glitch filter, or disables glitch filtering if `None`.
+    pub fn set_glitch_filter(&mut self, filter: Option<GlitchFilter>) {
+        self.decoder.set_glitch_filter(filter);
+    }
+
+    /// Performs a wiring self-test, reading the pins `samples` times and calling `between()`
+    /// before every read (e.g. for delaying, or for commanding motion), returning a report
+    /// on open (i.e. never toggling), shorted and swapped channels.
+    ///
+    /// Swapped channels can only be detected if the direction of the movement commanded
+    /// during the check is provided as `expected`. The encoder's position is not affected.
+    pub fn check_wiring(
+        &mut self,
+        samples: usize,
+        expected: Option<Mode::Movement>,
+        mut between: impl FnMut(),
+    ) -> Result<WiringReport, Error> {
+        let mut check = WiringCheck::new();
+
+        if let Some(movement) = expected {
+            let movement = match self.is_reversed {
+                true => movement.flipped(),
+                false => movement,
+            };
+            let change = match movement == Change::Positive.into() {
+                true => Change::Positive,
+                false => Change::Negative,
+            };
+            check = check.with_expected(change);
+        }
+
+        for _ in 0..samples {
+            between();
+            let sample = self.pins.read()?;
+            let (clk, dt) = self.logical(sample);
+            check.update(clk, dt);
+        }
+
+        Ok(check.report())
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), but only if `edge_flag`
+    /// indicates a change of the pins, returning `Err(nb::Error::WouldBlock)` otherwise.
+    ///
+    /// Since there is nothing to report until a movement has been completed,
+    /// an edge without resulting movement (e.g. mid-cycle in `FullStep` mode)
+    /// returns `Err(nb::Error::WouldBlock)` as well, allowing for `nb::block!(…)`.
+    #[cfg(feature = "nb")]
+    pub fn poll_nb(&mut self, edge_flag: &mut impl EdgeFlag) -> nb::Result<Mode::Movement, Error> {
+        if !edge_flag.take() {
+            return Err(nb::Error::WouldBlock);
+        }
+
+        self.poll()?.ok_or(nb::Error::WouldBlock)
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), returning the detected movement
+    /// together with the resulting position and a timestamp obtained from `now`.
+    pub fn poll_full(
+        &mut self,
+        now: impl FnOnce() -> u64,
+    ) -> Result<PollResult<Mode::Movement, T>, Error> {
+        let timestamp = now();
+        let movement = self.poll_at(Some(timestamp))?;
+
+        self.record_movement(timestamp, movement.as_ref());
+
+        Ok(PollResult {
+            movement,
+            position: self.position(),
+            timestamp,
+        })
     }
 }
 
-/// If async is enabled, and the pins provided satisfy the AsyncInputPin trait, the into_async() method is exposed.
-#[cfg(feature = "async")]
+/// If the pins provided satisfy the `PinSource` trait, the into_async() method is exposed.
 impl<Mode, Clk, Dt, Steps, T> IncrementalEncoder<Mode, Clk, Dt, Steps, T, Blocking>
 where
     Mode: OperationMode,
-    Clk: InputPin + Wait,
-    Dt: InputPin + Wait,
+    Clk: PinSource,
+    Dt: PinSource,
     Steps: StepMode,
-    T: Copy + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
 {
     /// Reconfigure the driver so that poll() is an async fn
-    pub fn into_async(self) -> IncrementalEncoder<Mode, Clk, Dt, Steps, T, Async>
-    where
-        IncrementalDecoder<Steps, T>: Default,
-    {
-        IncrementalEncoder::<Mode, Clk, Dt, Steps, T, Async> {
-            decoder: self.decoder,
-            pin_clk: self.pin_clk,
-            pin_dt: self.pin_dt,
-            pin_clk_state: self.pin_clk_state,
-            pin_dt_state: self.pin_dt_state,
-            is_reversed: self.is_reversed,
-            _mode: PhantomData,
-            _poll_mode: PhantomData,
-        }
+    ///
+    /// This disables the encoder's glitch filter (if any).
+    pub fn into_async(mut self) -> IncrementalEncoder<Mode, Clk, Dt, Steps, T, Async> {
+        self.decoder.set_glitch_filter(None);
+        self.into_poll_mode()
     }
 }
 
-#[cfg(feature = "async")]
 impl<Mode, Clk, Dt, Steps, T> IncrementalEncoder<Mode, Clk, Dt, Steps, T, Async>
 where
     Mode: OperationMode,
-    Clk: InputPin + Wait,
-    Dt: InputPin + Wait,
+    Clk: PinSource,
+    Dt: PinSource,
     Steps: StepMode,
-    T: Copy + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
 {
     /// Updates the encoder's state based on the given **clock** and **data** pins,
     /// returning the direction if a movement was detected, `None` if no movement was detected,
@@ -212,45 +879,306 @@ where
     ///
     /// Waits asynchronously for any of the pins to change state, before returning.
     pub async fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
-        let clk_fut = match self.pin_clk_state {
-            true => self.pin_clk.wait_for_low().left_future(),
-            false => self.pin_clk.wait_for_high().right_future(),
+        let edge = self.pins.wait_for_edge(self.sample);
+
+        #[cfg(feature = "stats")]
+        let sample = self.stats.counted(edge).await?;
+        #[cfg(not(feature = "stats"))]
+        let sample = edge.await?;
+
+        self.update(sample, None)
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), returning the detected movement
+    /// together with the resulting position and a timestamp obtained from `now`.
+    ///
+    /// The timestamp is obtained once a change of the pins has been awaited.
+    pub async fn poll_full(
+        &mut self,
+        now: impl FnOnce() -> u64,
+    ) -> Result<PollResult<Mode::Movement, T>, Error> {
+        let movement = self.poll().await?;
+        let timestamp = now();
+
+        self.record_movement(timestamp, movement.as_ref());
+
+        Ok(PollResult {
+            movement,
+            position: self.position(),
+            timestamp,
+        })
+    }
+
+    /// Reconfigure the driver so that poll() is a blocking function
+    pub fn into_blocking(self) -> IncrementalEncoder<Mode, Clk, Dt, Steps, T, Blocking> {
+        self.into_poll_mode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use embedded_hal_mock::eh1::{
+        digital::{Mock as PinMock, State as PinState, Transaction as PinTransaction},
+        MockError,
+    };
+
+    use crate::{InputPinError, QuadStep, Resolution, RotaryMovement};
+
+    use super::*;
+
+    #[test]
+    fn try_new() {
+        let pin_clk = PinMock::new(&[PinTransaction::get(PinState::High)]);
+        let pin_dt = PinMock::new(&[
+            PinTransaction::get(PinState::Low).with_error(MockError::Io(ErrorKind::Other))
+        ]);
+
+        // The pins get dropped along with the failed encoder, hence checked via clones:
+        let (mut clk, mut dt) = (pin_clk.clone(), pin_dt.clone());
+
+        let encoder: Result<RotaryEncoder<_, _>, _> = RotaryEncoder::try_new(pin_clk, pin_dt);
+
+        let Err(error) = encoder else {
+            panic!("Expected `Err(_)` from erroring initial read.");
         };
+        assert_eq!(error, Error::InputPin(InputPinError::PinDt));
+
+        clk.done();
+        dt.done();
+
+        let pin_clk = PinMock::new(&[
+            PinTransaction::get(PinState::Low),
+            PinTransaction::get(PinState::Low),
+        ]);
+        let pin_dt = PinMock::new(&[
+            PinTransaction::get(PinState::Low),
+            PinTransaction::get(PinState::High),
+        ]);
 
-        let dt_fut = match self.pin_dt_state {
-            true => self.pin_dt.wait_for_low().left_future(),
-            false => self.pin_dt.wait_for_high().right_future(),
+        let mut encoder: RotaryEncoder<_, _, QuadStep> =
+            RotaryEncoder::try_new(pin_clk, pin_dt).unwrap();
+
+        assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::Clockwise)));
+
+        let (pin_clk, pin_dt) = encoder.pins_mut();
+        pin_clk.done();
+        pin_dt.done();
+    }
+
+    #[test]
+    fn active_low() {
+        use PinState::*;
+
+        // One clockwise cycle, followed by two counter-clockwise steps:
+        let levels = [
+            (High, High),
+            (Low, High),
+            (Low, Low),
+            (High, Low),
+            (High, High),
+            (High, Low),
+            (Low, Low),
+        ];
+        let inverted = |level: PinState| match level {
+            High => Low,
+            Low => High,
+        };
+        let transactions = |level: fn(&(PinState, PinState)) -> PinState, invert: bool| {
+            levels
+                .iter()
+                .map(|levels| match invert {
+                    true => PinTransaction::get(inverted(level(levels))),
+                    false => PinTransaction::get(level(levels)),
+                })
+                .collect::<Vec<_>>()
         };
 
-        // toggle the internal state, rather than reading the pin state directly,
-        // as the pin state has likely changed since the wait_for_low() future was resolved
-        // by the hardware interrupt behind-the-scenes.
-        match select(clk_fut, dt_fut).await {
-            Either::First(_) => {
-                self.pin_clk_state = !self.pin_clk_state;
-            }
-            Either::Second(_) => {
-                self.pin_dt_state = !self.pin_dt_state;
-            }
+        let mut encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(
+            PinMock::new(&transactions(|levels| levels.0, false)),
+            PinMock::new(&transactions(|levels| levels.1, false)),
+        );
+        let mut active_low_encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(
+            PinMock::new(&transactions(|levels| levels.0, true)),
+            PinMock::new(&transactions(|levels| levels.1, true)),
+        )
+        .with_clk_active_low()
+        .with_dt_active_low();
+
+        for _ in 1..levels.len() {
+            assert_eq!(active_low_encoder.poll(), encoder.poll());
+        }
+        assert_eq!(encoder.position(), 2);
+        assert_eq!(active_low_encoder.position(), encoder.position());
+
+        // The physical levels are reported as read:
+        assert_eq!(active_low_encoder.raw_state(), (true, true));
+        assert_eq!(encoder.raw_state(), (false, false));
+
+        let (pin_clk, pin_dt) = encoder.pins_mut();
+        pin_clk.done();
+        pin_dt.done();
+        let (pin_clk, pin_dt) = active_low_encoder.pins_mut();
+        pin_clk.done();
+        pin_dt.done();
+
+        // Batches get inverted just the same:
+        let samples: Vec<_> = levels
+            .iter()
+            .map(|&(clk, dt)| (clk == High, dt == High))
+            .collect();
+        let inverted_samples: Vec<_> = samples.iter().map(|&(clk, dt)| (!clk, !dt)).collect();
+
+        encoder.reset();
+        active_low_encoder.reset();
+
+        let report = encoder.update_batch(&samples);
+        assert_eq!(report.delta, 2);
+        assert_eq!(report.errors, 0);
+        assert_eq!(active_low_encoder.update_batch(&inverted_samples), report);
+        assert_eq!(active_low_encoder.position(), encoder.position());
+    }
+
+    #[test]
+    fn turns() {
+        use PinState::*;
+
+        // Five clockwise steps, followed by six counter-clockwise steps:
+        let levels = [
+            (High, High),
+            (Low, High),
+            (Low, Low),
+            (High, Low),
+            (High, High),
+            (Low, High),
+            (High, High),
+            (High, Low),
+            (Low, Low),
+            (Low, High),
+            (High, High),
+            (High, Low),
+        ];
+        let transactions = |level: fn(&(PinState, PinState)) -> PinState| {
+            levels
+                .iter()
+                .map(|levels| PinTransaction::get(level(levels)))
+                .collect::<Vec<_>>()
         };
 
-        self.update()
+        let mut encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(
+            PinMock::new(&transactions(|levels| levels.0)),
+            PinMock::new(&transactions(|levels| levels.1)),
+        )
+        .with_pulses_per_revolution(4);
+        let mut reversed_encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(
+            PinMock::new(&transactions(|levels| levels.0)),
+            PinMock::new(&transactions(|levels| levels.1)),
+        )
+        .reversed()
+        .with_pulses_per_revolution(4);
+
+        for _ in 0..5 {
+            assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::Clockwise)));
+            assert!(reversed_encoder.poll().unwrap().is_some());
+        }
+        assert_eq!(encoder.revolutions(), 1);
+        assert_eq!(encoder.position_in_rev(), Some(1));
+        assert_eq!(encoder.angle_in_turn::<f32>(), Some(90.0));
+        assert_eq!(encoder.angle_in_turn::<i32>(), Some(90));
+        assert_eq!(reversed_encoder.revolutions(), -2);
+        assert_eq!(reversed_encoder.position_in_rev(), Some(3));
+        assert_eq!(reversed_encoder.angle_in_turn::<i16>(), Some(270));
+
+        // Changing direction, crossing back over the origin:
+        for _ in 0..6 {
+            assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::CounterClockwise)));
+            assert!(reversed_encoder.poll().unwrap().is_some());
+        }
+        assert_eq!(encoder.revolutions(), -1);
+        assert_eq!(encoder.position_in_rev(), Some(3));
+        assert_eq!(encoder.angle_in_turn::<i32>(), Some(270));
+        assert_eq!(reversed_encoder.revolutions(), 0);
+        assert_eq!(reversed_encoder.angle_in_turn::<f32>(), Some(90.0));
+
+        let (pin_clk, pin_dt) = encoder.pins_mut();
+        pin_clk.done();
+        pin_dt.done();
+        let (pin_clk, pin_dt) = reversed_encoder.pins_mut();
+        pin_clk.done();
+        pin_dt.done();
+
+        // Without a PPR there are no turns to track:
+        let encoder: RotaryEncoder<_, _> = RotaryEncoder::new(
+            PinMock::new(&[PinTransaction::get(High)]),
+            PinMock::new(&[PinTransaction::get(High)]),
+        );
+        assert_eq!(encoder.revolutions(), 0);
+        assert_eq!(encoder.position_in_rev(), None);
+        assert_eq!(encoder.angle_in_turn::<f32>(), None);
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
     }
 
-    /// Reconfigure the driver so that poll() is a blocking function
-    pub fn into_blocking(self) -> IncrementalEncoder<Mode, Clk, Dt, Steps, T, Blocking>
-    where
-        IncrementalDecoder<Steps, T>: Default,
-    {
-        IncrementalEncoder::<Mode, Clk, Dt, Steps, T, Blocking> {
-            decoder: self.decoder,
-            pin_clk: self.pin_clk,
-            pin_dt: self.pin_dt,
-            pin_clk_state: self.pin_clk_state,
-            pin_dt_state: self.pin_dt_state,
-            is_reversed: self.is_reversed,
-            _mode: PhantomData,
-            _poll_mode: PhantomData,
+    #[test]
+    fn velocity_detents() {
+        use PinState::*;
+
+        // Two clockwise cycles:
+        let levels = [
+            (High, High),
+            (Low, High),
+            (Low, Low),
+            (High, Low),
+            (High, High),
+            (Low, High),
+            (Low, Low),
+            (High, Low),
+            (High, High),
+        ];
+        let transactions = |level: fn(&(PinState, PinState)) -> PinState| {
+            levels
+                .iter()
+                .map(|levels| PinTransaction::get(level(levels)))
+                .collect::<Vec<_>>()
+        };
+
+        let resolution = Resolution::default().with_pulses_per_detent(4);
+        let estimator = VelocityEstimator::new(1000).with_resolution(resolution);
+        let mut encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(
+            PinMock::new(&transactions(|levels| levels.0)),
+            PinMock::new(&transactions(|levels| levels.1)),
+        )
+        .with_pulses_per_detent(4)
+        .with_velocity_estimator(estimator.clone());
+        let mut reversed_encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(
+            PinMock::new(&transactions(|levels| levels.0)),
+            PinMock::new(&transactions(|levels| levels.1)),
+        )
+        .reversed()
+        .with_pulses_per_detent(4)
+        .with_velocity_estimator(estimator);
+
+        // One pulse every 10 ticks, with only every fourth pulse completing a detent:
+        for timestamp in (10..=80).step_by(10) {
+            encoder.poll_full(|| timestamp).unwrap();
+            reversed_encoder.poll_full(|| timestamp).unwrap();
         }
+        assert_eq!(encoder.position(), 2);
+        assert_eq!(encoder.velocity(80), Some(100.0));
+        assert_eq!(
+            encoder.velocity_in(80, VelocityUnit::DetentsPerSecond),
+            Some(25.0)
+        );
+        assert_eq!(reversed_encoder.velocity(80), Some(-100.0));
+
+        let (pin_clk, pin_dt) = encoder.pins_mut();
+        pin_clk.done();
+        pin_dt.done();
+        let (pin_clk, pin_dt) = reversed_encoder.pins_mut();
+        pin_clk.done();
+        pin_dt.done();
     }
 }
@@ -0,0 +1,144 @@
+//! The sampling core shared by all encoder drivers and poll modes.
+//!
+//! Drivers keep their pins in a `PinSet` and only differ in how they obtain
+//! the next sample: blocking drivers read it right away, while async drivers
+//! wait for the next edge. Everything past sampling is shared.
+
+use crate::{
+    select::{select, select3, Either, Either3},
+    traits::*,
+    Error, InputPinError, PinSource,
+};
+
+/// A set of input pins, sampled together.
+pub(crate) trait PinSet {
+    /// The pin levels making up a single sample.
+    type Sample: Copy;
+
+    /// Reads the current levels of all pins.
+    fn read(&mut self) -> Result<Self::Sample, Error>;
+
+    /// Reads every pin as both high and low, checking the readings for consistency.
+    fn check(&mut self) -> Result<(), Error>;
+}
+
+/// A set of input pins, capable of waiting for edges.
+pub(crate) trait AsyncPinSet: PinSet {
+    /// Waits for any pin to leave its level in `sample`,
+    /// returning the resulting sample.
+    async fn wait_for_edge(&mut self, sample: Self::Sample) -> Result<Self::Sample, Error>;
+}
+
+fn read<P>(pin: &mut P, error: InputPinError) -> Result<bool, Error>
+where
+    P: InputPin,
+{
+    pin.is_high().map_err(|_| Error::InputPin(error))
+}
+
+fn check<P>(pin: &mut P, error: InputPinError) -> Result<(), Error>
+where
+    P: InputPin,
+{
+    let is_high = read(pin, error)?;
+    let is_low = pin.is_low().map_err(|_| Error::InputPin(error))?;
+
+    match is_high != is_low {
+        true => Ok(()),
+        false => Err(Error::InputPin(error)),
+    }
+}
+
+/// Waits for `pin` to leave the given `level`, returning the new level.
+async fn toggled<P>(pin: &mut P, level: bool, error: InputPinError) -> Result<bool, Error>
+where
+    P: PinSource,
+{
+    pin.wait_edge(level)
+        .await
+        .map_err(|_| Error::InputPin(error))
+}
+
+impl<Clk, Dt> PinSet for (Clk, Dt)
+where
+    Clk: InputPin,
+    Dt: InputPin,
+{
+    type Sample = (bool, bool);
+
+    fn read(&mut self) -> Result<Self::Sample, Error> {
+        let clk = read(&mut self.0, InputPinError::PinClk)?;
+        let dt = read(&mut self.1, InputPinError::PinDt)?;
+        Ok((clk, dt))
+    }
+
+    fn check(&mut self) -> Result<(), Error> {
+        check(&mut self.0, InputPinError::PinClk)?;
+        check(&mut self.1, InputPinError::PinDt)
+    }
+}
+
+impl<Clk, Dt> AsyncPinSet for (Clk, Dt)
+where
+    Clk: PinSource,
+    Dt: PinSource,
+{
+    async fn wait_for_edge(&mut self, (clk, dt): Self::Sample) -> Result<Self::Sample, Error> {
+        let (pin_clk, pin_dt) = self;
+
+        match select(
+            toggled(pin_clk, clk, InputPinError::PinClk),
+            toggled(pin_dt, dt, InputPinError::PinDt),
+        )
+        .await
+        {
+            Either::First(clk) => Ok((clk?, dt)),
+            Either::Second(dt) => Ok((clk, dt?)),
+        }
+    }
+}
+
+impl<Clk, Dt, Idx> PinSet for (Clk, Dt, Idx)
+where
+    Clk: InputPin,
+    Dt: InputPin,
+    Idx: InputPin,
+{
+    type Sample = (bool, bool, bool);
+
+    fn read(&mut self) -> Result<Self::Sample, Error> {
+        let clk = read(&mut self.0, InputPinError::PinClk)?;
+        let dt = read(&mut self.1, InputPinError::PinDt)?;
+        let idx = read(&mut self.2, InputPinError::PinIdx)?;
+        Ok((clk, dt, idx))
+    }
+
+    fn check(&mut self) -> Result<(), Error> {
+        check(&mut self.0, InputPinError::PinClk)?;
+        check(&mut self.1, InputPinError::PinDt)?;
+        check(&mut self.2, InputPinError::PinIdx)
+    }
+}
+
+impl<Clk, Dt, Idx> AsyncPinSet for (Clk, Dt, Idx)
+where
+    Clk: PinSource,
+    Dt: PinSource,
+    Idx: PinSource,
+{
+    async fn wait_for_edge(&mut self, (clk, dt, idx): Self::Sample) -> Result<Self::Sample, Error> {
+        let (pin_clk, pin_dt, pin_idx) = self;
+
+        match select3(
+            toggled(pin_clk, clk, InputPinError::PinClk),
+            toggled(pin_dt, dt, InputPinError::PinDt),
+            toggled(pin_idx, idx, InputPinError::PinIdx),
+        )
+        .await
+        {
+            Either3::First(clk) => Ok((clk?, dt, idx)),
+            Either3::Second(dt) => Ok((clk, dt?, idx)),
+            Either3::Third(idx) => Ok((clk, dt, idx?)),
+        }
+    }
+}
@@ -0,0 +1,263 @@
+//! A step/direction encoder driver (e.g. for motion controllers, or stepper drivers).
+
+use core::marker::PhantomData;
+
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+use quadrature_decoder::{Change, StepDirDecoder};
+
+#[allow(unused_imports)]
+use crate::{
+    encoder::pins::*,
+    mode::{Async, Blocking, Movement, OperationMode, PollMode},
+    traits::*,
+    Error, PinSource, PollResult,
+};
+
+/// An encoder driver for step/direction signals (e.g. as output by motion controllers),
+/// reporting a movement for every rising edge of the **step** pin,
+/// in the direction indicated by the level of the **dir** pin (high: positive, low: negative).
+#[derive(Debug)]
+pub struct StepDirEncoder<Mode, Step, Dir, T = i32, PM = Blocking> {
+    decoder: StepDirDecoder<T>,
+    pins: (Step, Dir),
+    sample: (bool, bool),
+    is_reversed: bool,
+    _mode: PhantomData<Mode>,
+    _poll_mode: PhantomData<PM>,
+}
+
+impl<Mode, Step, Dir, T, PM> StepDirEncoder<Mode, Step, Dir, T, PM>
+where
+    Mode: OperationMode,
+    Step: InputPin,
+    Dir: InputPin,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    PM: PollMode,
+{
+    /// Creates a step/direction encoder driver for the given **step** and **dir** pins.
+    pub fn new(mut pin_step: Step, mut pin_dir: Dir) -> Self {
+        // read the initial pin states to determine starting values
+        let sample = (
+            pin_step.is_high().unwrap_or(false),
+            pin_dir.is_high().unwrap_or(false),
+        );
+
+        let mut decoder: StepDirDecoder<T> = Default::default();
+        decoder.update(sample.0, sample.1);
+
+        Self {
+            decoder,
+            pins: (pin_step, pin_dir),
+            sample,
+            is_reversed: false,
+            _mode: PhantomData,
+            _poll_mode: PhantomData,
+        }
+    }
+
+    /// Sets the encoder's reversed mode, making it report flipped movements and positions.
+    pub fn reversed(mut self) -> Self {
+        self.is_reversed = true;
+        self
+    }
+
+    /// Returns `true` if the encoder is reversed, otherwise `false`.
+    pub fn is_reversed(&self) -> bool {
+        self.is_reversed
+    }
+
+    /// Returns mutable borrows for the **step** and **dir** pins.
+    pub fn pins_mut(&mut self) -> (&mut Step, &mut Dir) {
+        (&mut self.pins.0, &mut self.pins.1)
+    }
+
+    /// Consumes self, returning the **step** and **dir** pins.
+    pub fn release(self) -> (Step, Dir) {
+        self.pins
+    }
+
+    fn update(&mut self, sample: (bool, bool)) -> Option<Mode::Movement> {
+        self.sample = sample;
+
+        let (step, dir) = sample;
+        let change: Option<Change> = self.decoder.update(step, dir);
+        let movement: Option<Mode::Movement> = change.map(From::from);
+
+        movement.map(|movement| {
+            if self.is_reversed() {
+                movement.flipped()
+            } else {
+                movement
+            }
+        })
+    }
+
+    /// Resets the encoder to its initial state.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.decoder.update(self.sample.0, self.sample.1);
+    }
+
+    /// Returns `true` if the encoder's position has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the encoder's creation (or its most recent reset), otherwise `false`.
+    pub fn is_saturated(&self) -> bool {
+        self.decoder.is_saturated()
+    }
+
+    /// Clears the encoder's sticky saturation flag.
+    pub fn clear_saturated(&mut self) {
+        self.decoder.clear_saturated();
+    }
+
+    /// Returns the encoder's position counter relative to its initial position in number of pulses.
+    pub fn position(&self) -> T {
+        match self.is_reversed {
+            true => self.decoder.counter().wrapping_neg(),
+            false => self.decoder.counter(),
+        }
+    }
+
+    /// Sets the encoder's position.
+    pub fn set_position(&mut self, position: T) {
+        match self.is_reversed {
+            true => self.decoder.set_counter(position.wrapping_neg()),
+            false => self.decoder.set_counter(position),
+        }
+    }
+
+    fn into_poll_mode<PM2>(self) -> StepDirEncoder<Mode, Step, Dir, T, PM2> {
+        StepDirEncoder {
+            decoder: self.decoder,
+            pins: self.pins,
+            sample: self.sample,
+            is_reversed: self.is_reversed,
+            _mode: PhantomData,
+            _poll_mode: PhantomData,
+        }
+    }
+}
+
+impl<Mode, Step, Dir, T> StepDirEncoder<Mode, Step, Dir, T, Blocking>
+where
+    Mode: OperationMode,
+    Step: InputPin,
+    Dir: InputPin,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Updates the encoder's state based on the given **step** and **dir** pins,
+    /// returning the direction if a movement was detected, `None` if no movement was detected,
+    /// or `Err(_)` if reading a pin failed.
+    pub fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let sample = self.pins.read()?;
+        Ok(self.update(sample))
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), returning the detected movement
+    /// together with the resulting position and a timestamp obtained from `now`.
+    pub fn poll_full(
+        &mut self,
+        now: impl FnOnce() -> u64,
+    ) -> Result<PollResult<Mode::Movement, T>, Error> {
+        let movement = self.poll()?;
+
+        Ok(PollResult {
+            movement,
+            position: self.position(),
+            timestamp: now(),
+        })
+    }
+}
+
+/// If the pins provided satisfy the `PinSource` trait, the into_async() method is exposed.
+impl<Mode, Step, Dir, T> StepDirEncoder<Mode, Step, Dir, T, Blocking>
+where
+    Mode: OperationMode,
+    Step: PinSource,
+    Dir: PinSource,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Reconfigure the driver so that poll() is an async fn
+    pub fn into_async(self) -> StepDirEncoder<Mode, Step, Dir, T, Async> {
+        self.into_poll_mode()
+    }
+}
+
+impl<Mode, Step, Dir, T> StepDirEncoder<Mode, Step, Dir, T, Async>
+where
+    Mode: OperationMode,
+    Step: PinSource,
+    Dir: PinSource,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Updates the encoder's state based on the given **step** and **dir** pins,
+    /// returning the direction if a movement was detected, `None` if no movement was detected,
+    /// or `Err(_)` if reading a pin failed.
+    ///
+    /// Waits asynchronously for any of the pins to change state, before returning.
+    pub async fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let sample = self.pins.wait_for_edge(self.sample).await?;
+        Ok(self.update(sample))
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), returning the detected movement
+    /// together with the resulting position and a timestamp obtained from `now`.
+    ///
+    /// The timestamp is obtained once a change of the pins has been awaited.
+    pub async fn poll_full(
+        &mut self,
+        now: impl FnOnce() -> u64,
+    ) -> Result<PollResult<Mode::Movement, T>, Error> {
+        let movement = self.poll().await?;
+
+        Ok(PollResult {
+            movement,
+            position: self.position(),
+            timestamp: now(),
+        })
+    }
+
+    /// Reconfigure the driver so that poll() is a blocking function
+    pub fn into_blocking(self) -> StepDirEncoder<Mode, Step, Dir, T, Blocking> {
+        self.into_poll_mode()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{Linear, LinearMovement};
+
+    use super::*;
+
+    #[test]
+    fn poll() {
+        use PinState::*;
+
+        let pin_step = PinMock::new(&[
+            PinTransaction::get(Low),
+            PinTransaction::get(High),
+            PinTransaction::get(Low),
+            PinTransaction::get(High),
+        ]);
+        let pin_dir = PinMock::new(&[
+            PinTransaction::get(High),
+            PinTransaction::get(High),
+            PinTransaction::get(Low),
+            PinTransaction::get(Low),
+        ]);
+
+        let mut encoder: StepDirEncoder<Linear, _, _> = StepDirEncoder::new(pin_step, pin_dir);
+
+        assert_eq!(encoder.poll(), Ok(Some(LinearMovement::Forward)));
+        assert_eq!(encoder.poll(), Ok(None));
+        assert_eq!(encoder.poll(), Ok(Some(LinearMovement::Backward)));
+        assert_eq!(encoder.position(), 0);
+
+        let (mut pin_step, mut pin_dir) = encoder.release();
+        pin_step.done();
+        pin_dir.done();
+    }
+}
@@ -0,0 +1,117 @@
+//! Counting of turns (i.e. revolutions) of rotary encoders.
+
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+use quadrature_decoder::Change;
+
+/// A counter of turns, tracking the number of revolutions and the pulses within the current turn.
+///
+/// Being updated by pulse changes, rather than by positions,
+/// the counter is unaffected by the encoder's position wrapping around (see `Overflow::Wrapping`).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) struct TurnCounter<T> {
+    pulses_per_revolution: T,
+    pulses: T,
+    revolutions: i32,
+}
+
+impl<T> TurnCounter<T>
+where
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg,
+{
+    /// Creates a counter for the given (positive) number of pulses per revolution.
+    pub(crate) fn new(pulses_per_revolution: T) -> Self {
+        Self {
+            pulses_per_revolution,
+            pulses: Zero::zero(),
+            revolutions: 0,
+        }
+    }
+
+    pub(crate) fn pulses_per_revolution(&self) -> T {
+        self.pulses_per_revolution
+    }
+
+    /// Returns the pulses within the current turn, within `[0, PPR)`.
+    pub(crate) fn pulses(&self) -> T {
+        self.pulses
+    }
+
+    pub(crate) fn revolutions(&self) -> i32 {
+        self.revolutions
+    }
+
+    /// Updates the counter with the given pulse `change`.
+    pub(crate) fn update(&mut self, change: Change) {
+        match change {
+            Change::Positive => {
+                self.pulses = self.pulses.saturating_add(&T::one());
+                if self.pulses == self.pulses_per_revolution {
+                    self.pulses = Zero::zero();
+                    self.revolutions = self.revolutions.wrapping_add(1);
+                }
+            }
+            Change::Negative => {
+                if self.pulses.is_zero() {
+                    self.pulses = self.pulses_per_revolution;
+                    self.revolutions = self.revolutions.wrapping_sub(1);
+                }
+                self.pulses = self.pulses.saturating_add(&T::one().wrapping_neg());
+            }
+        }
+    }
+
+    /// Returns the counter as seen in the opposite direction (e.g. for reversed encoders).
+    pub(crate) fn flipped(&self) -> Self {
+        match self.pulses.is_zero() {
+            true => Self {
+                revolutions: self.revolutions.wrapping_neg(),
+                ..*self
+            },
+            false => Self {
+                pulses: self
+                    .pulses_per_revolution
+                    .saturating_add(&self.pulses.wrapping_neg()),
+                revolutions: self.revolutions.wrapping_neg().wrapping_sub(1),
+                ..*self
+            },
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.pulses = Zero::zero();
+        self.revolutions = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        let mut turns: TurnCounter<i32> = TurnCounter::new(4);
+
+        for _ in 0..5 {
+            turns.update(Change::Positive);
+        }
+        assert_eq!((turns.revolutions(), turns.pulses()), (1, 1));
+        assert_eq!(turns.flipped().revolutions(), -2);
+        assert_eq!(turns.flipped().pulses(), 3);
+
+        // Changing direction, crossing back over the origin:
+        for _ in 0..7 {
+            turns.update(Change::Negative);
+        }
+        assert_eq!((turns.revolutions(), turns.pulses()), (-1, 2));
+
+        for _ in 0..2 {
+            turns.update(Change::Negative);
+        }
+        assert_eq!((turns.revolutions(), turns.pulses()), (-1, 0));
+        assert_eq!(turns.flipped().revolutions(), 1);
+        assert_eq!(turns.flipped().pulses(), 0);
+
+        turns.reset();
+        assert_eq!((turns.revolutions(), turns.pulses()), (0, 0));
+    }
+}
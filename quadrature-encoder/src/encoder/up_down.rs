@@ -0,0 +1,223 @@
+//! An up/down pulse encoder driver (aka CW/CCW, or count-up/count-down).
+
+use core::marker::PhantomData;
+
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+use quadrature_decoder::{Change, UpDownPulseDecoder};
+
+#[allow(unused_imports)]
+use crate::{
+    encoder::pins::*,
+    mode::{Async, Blocking, Movement, OperationMode, PollMode},
+    traits::*,
+    Error, PinSource, PollResult,
+};
+
+/// An encoder driver for separate up/down pulse signals (aka CW/CCW, as output by some drives and counters),
+/// reporting a positive movement for every rising edge of the **up** pin,
+/// and a negative movement for every rising edge of the **down** pin.
+#[derive(Debug)]
+pub struct UpDownEncoder<Mode, Up, Down, T = i32, PM = Blocking> {
+    decoder: UpDownPulseDecoder<T>,
+    pins: (Up, Down),
+    sample: (bool, bool),
+    is_reversed: bool,
+    _mode: PhantomData<Mode>,
+    _poll_mode: PhantomData<PM>,
+}
+
+impl<Mode, Up, Down, T, PM> UpDownEncoder<Mode, Up, Down, T, PM>
+where
+    Mode: OperationMode,
+    Up: InputPin,
+    Down: InputPin,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    PM: PollMode,
+{
+    /// Creates an up/down pulse encoder driver for the given **up** and **down** pins.
+    pub fn new(mut pin_up: Up, mut pin_down: Down) -> Self {
+        // read the initial pin states to determine starting values
+        let sample = (
+            pin_up.is_high().unwrap_or(false),
+            pin_down.is_high().unwrap_or(false),
+        );
+
+        let mut decoder: UpDownPulseDecoder<T> = Default::default();
+        decoder.update(sample.0, sample.1);
+
+        Self {
+            decoder,
+            pins: (pin_up, pin_down),
+            sample,
+            is_reversed: false,
+            _mode: PhantomData,
+            _poll_mode: PhantomData,
+        }
+    }
+
+    /// Sets the encoder's reversed mode, making it report flipped movements and positions.
+    pub fn reversed(mut self) -> Self {
+        self.is_reversed = true;
+        self
+    }
+
+    /// Returns `true` if the encoder is reversed, otherwise `false`.
+    pub fn is_reversed(&self) -> bool {
+        self.is_reversed
+    }
+
+    /// Returns mutable borrows for the **up** and **down** pins.
+    pub fn pins_mut(&mut self) -> (&mut Up, &mut Down) {
+        (&mut self.pins.0, &mut self.pins.1)
+    }
+
+    /// Consumes self, returning the **up** and **down** pins.
+    pub fn release(self) -> (Up, Down) {
+        self.pins
+    }
+
+    fn update(&mut self, sample: (bool, bool)) -> Option<Mode::Movement> {
+        self.sample = sample;
+
+        let (up, down) = sample;
+        let change: Option<Change> = self.decoder.update(up, down);
+        let movement: Option<Mode::Movement> = change.map(From::from);
+
+        movement.map(|movement| {
+            if self.is_reversed() {
+                movement.flipped()
+            } else {
+                movement
+            }
+        })
+    }
+
+    /// Resets the encoder to its initial state.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.decoder.update(self.sample.0, self.sample.1);
+    }
+
+    /// Returns `true` if the encoder's position has hit one of its bounds (e.g. `i32::MAX`)
+    /// since the encoder's creation (or its most recent reset), otherwise `false`.
+    pub fn is_saturated(&self) -> bool {
+        self.decoder.is_saturated()
+    }
+
+    /// Clears the encoder's sticky saturation flag.
+    pub fn clear_saturated(&mut self) {
+        self.decoder.clear_saturated();
+    }
+
+    /// Returns the encoder's position counter relative to its initial position in number of pulses.
+    pub fn position(&self) -> T {
+        match self.is_reversed {
+            true => self.decoder.counter().wrapping_neg(),
+            false => self.decoder.counter(),
+        }
+    }
+
+    /// Sets the encoder's position.
+    pub fn set_position(&mut self, position: T) {
+        match self.is_reversed {
+            true => self.decoder.set_counter(position.wrapping_neg()),
+            false => self.decoder.set_counter(position),
+        }
+    }
+
+    fn into_poll_mode<PM2>(self) -> UpDownEncoder<Mode, Up, Down, T, PM2> {
+        UpDownEncoder {
+            decoder: self.decoder,
+            pins: self.pins,
+            sample: self.sample,
+            is_reversed: self.is_reversed,
+            _mode: PhantomData,
+            _poll_mode: PhantomData,
+        }
+    }
+}
+
+impl<Mode, Up, Down, T> UpDownEncoder<Mode, Up, Down, T, Blocking>
+where
+    Mode: OperationMode,
+    Up: InputPin,
+    Down: InputPin,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Updates the encoder's state based on the given **up** and **down** pins,
+    /// returning the direction if a movement was detected, `None` if no movement was detected,
+    /// or `Err(_)` if reading a pin failed.
+    pub fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let sample = self.pins.read()?;
+        Ok(self.update(sample))
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), returning the detected movement
+    /// together with the resulting position and a timestamp obtained from `now`.
+    pub fn poll_full(
+        &mut self,
+        now: impl FnOnce() -> u64,
+    ) -> Result<PollResult<Mode::Movement, T>, Error> {
+        let movement = self.poll()?;
+
+        Ok(PollResult {
+            movement,
+            position: self.position(),
+            timestamp: now(),
+        })
+    }
+}
+
+/// If the pins provided satisfy the `PinSource` trait, the into_async() method is exposed.
+impl<Mode, Up, Down, T> UpDownEncoder<Mode, Up, Down, T, Blocking>
+where
+    Mode: OperationMode,
+    Up: PinSource,
+    Down: PinSource,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Reconfigure the driver so that poll() is an async fn
+    pub fn into_async(self) -> UpDownEncoder<Mode, Up, Down, T, Async> {
+        self.into_poll_mode()
+    }
+}
+
+impl<Mode, Up, Down, T> UpDownEncoder<Mode, Up, Down, T, Async>
+where
+    Mode: OperationMode,
+    Up: PinSource,
+    Down: PinSource,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Updates the encoder's state based on the given **up** and **down** pins,
+    /// returning the direction if a movement was detected, `None` if no movement was detected,
+    /// or `Err(_)` if reading a pin failed.
+    ///
+    /// Waits asynchronously for any of the pins to change state, before returning.
+    pub async fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let sample = self.pins.wait_for_edge(self.sample).await?;
+        Ok(self.update(sample))
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), returning the detected movement
+    /// together with the resulting position and a timestamp obtained from `now`.
+    ///
+    /// The timestamp is obtained once a change of the pins has been awaited.
+    pub async fn poll_full(
+        &mut self,
+        now: impl FnOnce() -> u64,
+    ) -> Result<PollResult<Mode::Movement, T>, Error> {
+        let movement = self.poll().await?;
+
+        Ok(PollResult {
+            movement,
+            position: self.position(),
+            timestamp: now(),
+        })
+    }
+
+    /// Reconfigure the driver so that poll() is a blocking function
+    pub fn into_blocking(self) -> UpDownEncoder<Mode, Up, Down, T, Blocking> {
+        self.into_poll_mode()
+    }
+}
@@ -0,0 +1,151 @@
+//! Handling of invalid quadrature transitions.
+
+use crate::{Error, QuadratureError};
+
+/// The policy of an encoder for handling invalid transitions (i.e. positional "jumps"),
+/// allowing long-running devices to tolerate transient signal issues, rather than aborting on them.
+///
+/// Regardless of the policy every invalid transition gets counted as a missed step
+/// (see `missed_step_count()`).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ErrorPolicy {
+    /// Reports no movement, continuing to decode as is.
+    Ignore,
+    /// Reports the first invalid transition as `Err(_)`, and no movement for any further ones,
+    /// until the encoder's diagnostics get cleared (see `clear_diagnostics()`).
+    Saturate,
+    /// Reports no movement, resetting the decoding state (discarding any partially decoded cycle),
+    /// while keeping the encoder's position.
+    ResetState,
+    /// Reports every invalid transition as `Err(_)`.
+    #[default]
+    Propagate,
+}
+
+impl ErrorPolicy {
+    /// Handles the given `error` according to the policy, counting it as a missed step
+    /// and calling `reset_state` if required by the policy.
+    pub(crate) fn handle<C>(
+        self,
+        error: QuadratureError,
+        missed_steps: &mut u32,
+        reset_state: impl FnOnce(),
+    ) -> Result<Option<C>, Error> {
+        let is_first = *missed_steps == 0;
+        *missed_steps = missed_steps.saturating_add(1);
+
+        match self {
+            Self::Ignore => Ok(None),
+            Self::Saturate if is_first => Err(Error::Quadrature(error)),
+            Self::Saturate => Ok(None),
+            Self::ResetState => {
+                reset_state();
+                Ok(None)
+            }
+            Self::Propagate => Err(Error::Quadrature(error)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{RotaryEncoder, RotaryMovement};
+
+    use super::*;
+
+    fn pins(levels: &[(PinState, PinState)]) -> (PinMock, PinMock) {
+        let (clk, dt): (Vec<_>, Vec<_>) = levels
+            .iter()
+            .map(|&(clk, dt)| (PinTransaction::get(clk), PinTransaction::get(dt)))
+            .unzip();
+
+        (PinMock::new(&clk), PinMock::new(&dt))
+    }
+
+    #[test]
+    fn propagate() {
+        use PinState::*;
+
+        let (pin_clk, pin_dt) = pins(&[(High, High), (Low, Low), (High, High)]);
+        let mut encoder: RotaryEncoder<_, _> = RotaryEncoder::new(pin_clk, pin_dt);
+        assert_eq!(encoder.error_policy(), ErrorPolicy::Propagate);
+
+        assert_eq!(
+            encoder.poll(),
+            Err(Error::Quadrature(QuadratureError::E11_00))
+        );
+        assert_eq!(
+            encoder.poll(),
+            Err(Error::Quadrature(QuadratureError::E00_11))
+        );
+        assert_eq!(encoder.missed_step_count(), 2);
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+
+    #[test]
+    fn saturate() {
+        use PinState::*;
+
+        let (pin_clk, pin_dt) = pins(&[(High, High), (Low, Low), (High, High), (Low, Low)]);
+        let mut encoder: RotaryEncoder<_, _> =
+            RotaryEncoder::new(pin_clk, pin_dt).with_error_policy(ErrorPolicy::Saturate);
+
+        assert_eq!(
+            encoder.poll(),
+            Err(Error::Quadrature(QuadratureError::E11_00))
+        );
+        assert_eq!(encoder.poll(), Ok(None));
+        assert_eq!(encoder.missed_step_count(), 2);
+
+        encoder.clear_diagnostics();
+        assert_eq!(encoder.missed_step_count(), 0);
+        assert_eq!(
+            encoder.poll(),
+            Err(Error::Quadrature(QuadratureError::E11_00))
+        );
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+
+    #[test]
+    fn reset_state() {
+        use PinState::*;
+
+        let (pin_clk, pin_dt) = pins(&[
+            (High, High),
+            (Low, High),
+            (High, Low),
+            (Low, Low),
+            (High, Low),
+            (High, High),
+        ]);
+        let mut encoder: RotaryEncoder<_, _> = RotaryEncoder::new(pin_clk, pin_dt)
+            .with_error_policy(ErrorPolicy::ResetState)
+            .with_initial_position(5);
+
+        assert_eq!(encoder.poll(), Ok(None));
+        assert_eq!(encoder.poll(), Ok(None));
+        assert_eq!(encoder.missed_step_count(), 1);
+        assert_eq!(encoder.position(), 5);
+
+        // Decoding resumes from the most recent reading (i.e. three quarters into a clockwise cycle):
+        assert_eq!(encoder.poll(), Ok(None));
+        assert_eq!(encoder.poll(), Ok(None));
+        assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::Clockwise)));
+        assert_eq!(encoder.missed_step_count(), 1);
+        assert_eq!(encoder.position(), 6);
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+}
@@ -0,0 +1,202 @@
+//! Positions sampled at a fixed rate, driven by `embassy-time`.
+
+use embassy_time::{Duration, Instant, Ticker};
+#[cfg(feature = "stream")]
+use futures_util::stream::{unfold, Stream};
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+
+use crate::{
+    select::{select, Either},
+    Async, Error, IncrementalEncoder, IndexedIncrementalEncoder, OperationMode, PinSource,
+    StepMode,
+};
+
+/// A stream of an async encoder's `(position, timestamp)` pairs, yielded at a fixed rate
+/// regardless of whether any movement occurred (e.g. for fixed-period control loops or plotters).
+///
+/// The encoder keeps being polled while waiting for the next tick of the stream,
+/// so no movement goes unnoticed in between ticks.
+///
+/// Timestamps are in ticks of `embassy-time` (i.e. `Instant::as_ticks()`),
+/// taken once the tick has been awaited.
+///
+/// Ticks missed (e.g. due to the stream not being awaited in time) are yielded
+/// right away by subsequent calls to [`next()`](Self::next), in accordance with `Ticker`.
+///
+/// The stream does not implement `futures::Stream` itself, as doing so would require storing
+/// the encoder's in-flight poll future in between calls of `poll_next()`, which cannot be named
+/// (see [`PinSource`]). Recreating the future on every call instead would drop the pins' wakers.
+/// With the `stream` feature enabled, `into_stream()` turns it into a `futures::Stream` instead.
+pub struct FixedRateStream<'a, E> {
+    encoder: &'a mut E,
+    ticker: Ticker,
+}
+
+impl<'a, E> FixedRateStream<'a, E> {
+    /// Creates a stream of the given async `encoder`'s positions,
+    /// yielding one every `period` (e.g. `Duration::from_hz(1000)`).
+    pub fn new(encoder: &'a mut E, period: Duration) -> Self {
+        Self {
+            encoder,
+            ticker: Ticker::every(period),
+        }
+    }
+
+    /// Returns a reference to the stream's encoder.
+    pub fn encoder(&self) -> &E {
+        self.encoder
+    }
+
+    /// Resets the stream's ticker, making the next tick due one period from now.
+    pub fn reset(&mut self) {
+        self.ticker.reset();
+    }
+}
+
+impl<'a, Mode, Clk, Dt, Steps, T>
+    FixedRateStream<'a, IncrementalEncoder<Mode, Clk, Dt, Steps, T, Async>>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Waits for the stream's next tick, polling the encoder in the meantime,
+    /// returning the encoder's position and the tick's timestamp.
+    ///
+    /// Errors are returned as they occur, without waiting for the next tick,
+    /// with the following call continuing to wait for the still pending tick.
+    pub async fn next(&mut self) -> Result<(T, u64), Error> {
+        loop {
+            match select(self.ticker.next(), self.encoder.poll()).await {
+                Either::First(()) => {
+                    return Ok((self.encoder.position(), Instant::now().as_ticks()));
+                }
+                Either::Second(result) => {
+                    result?;
+                }
+            }
+        }
+    }
+
+    /// Consumes self, returning a never-ending `futures::Stream` of the encoder's positions,
+    /// yielding the results of [`next()`](Self::next).
+    #[cfg(feature = "stream")]
+    pub fn into_stream(self) -> impl Stream<Item = Result<(T, u64), Error>> + 'a {
+        unfold(self, |mut stream| async move {
+            let item = stream.next().await;
+            Some((item, stream))
+        })
+    }
+}
+
+impl<'a, Mode, Clk, Dt, Idx, Steps, T>
+    FixedRateStream<'a, IndexedIncrementalEncoder<Mode, Clk, Dt, Idx, Steps, T, Async>>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    Idx: PinSource,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Waits for the stream's next tick, polling the encoder in the meantime,
+    /// returning the encoder's position and the tick's timestamp.
+    ///
+    /// Errors are returned as they occur, without waiting for the next tick,
+    /// with the following call continuing to wait for the still pending tick.
+    pub async fn next(&mut self) -> Result<(T, u64), Error> {
+        loop {
+            match select(self.ticker.next(), self.encoder.poll()).await {
+                Either::First(()) => {
+                    return Ok((self.encoder.position(), Instant::now().as_ticks()));
+                }
+                Either::Second(result) => {
+                    result?;
+                }
+            }
+        }
+    }
+
+    /// Consumes self, returning a never-ending `futures::Stream` of the encoder's positions,
+    /// yielding the results of [`next()`](Self::next).
+    #[cfg(feature = "stream")]
+    pub fn into_stream(self) -> impl Stream<Item = Result<(T, u64), Error>> + 'a {
+        unfold(self, |mut stream| async move {
+            let item = stream.next().await;
+            Some((item, stream))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::{pin::pin, task::Poll};
+
+    use embassy_futures::poll_once;
+    use embassy_time::MockDriver;
+
+    use crate::{
+        mock_time::{self, IdlePin},
+        QuadStep, RotaryEncoder,
+    };
+
+    use super::*;
+
+    #[test]
+    fn next() {
+        let _clock = mock_time::lock();
+
+        let period = Duration::from_millis(10);
+
+        let encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(IdlePin, IdlePin);
+        let mut encoder = encoder.into_async();
+        encoder.set_position(42);
+
+        let mut stream = FixedRateStream::new(&mut encoder, period);
+        let start = Instant::now();
+
+        for tick in 1..=2 {
+            let mut next = pin!(stream.next());
+            assert_eq!(poll_once(&mut next), Poll::Pending);
+
+            MockDriver::get().advance(period);
+            let timestamp = (start + period * tick).as_ticks();
+            assert_eq!(poll_once(&mut next), Poll::Ready(Ok((42, timestamp))));
+        }
+
+        // Missed ticks get yielded right away:
+        MockDriver::get().advance(period * 2);
+        for _ in 0..2 {
+            assert!(poll_once(pin!(stream.next())).is_ready());
+        }
+        assert_eq!(poll_once(pin!(stream.next())), Poll::Pending);
+    }
+
+    #[cfg(feature = "stream")]
+    #[test]
+    fn into_stream() {
+        use futures_util::StreamExt;
+
+        let _clock = mock_time::lock();
+
+        let period = Duration::from_millis(10);
+
+        let encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(IdlePin, IdlePin);
+        let mut encoder = encoder.into_async();
+
+        let stream = FixedRateStream::new(&mut encoder, period).into_stream();
+        let mut stream = pin!(stream);
+        let start = Instant::now();
+
+        assert_eq!(poll_once(stream.next()), Poll::Pending);
+
+        MockDriver::get().advance(period);
+        let timestamp = (start + period).as_ticks();
+        assert_eq!(
+            poll_once(stream.next()),
+            Poll::Ready(Some(Ok((0, timestamp))))
+        );
+    }
+}
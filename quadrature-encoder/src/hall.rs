@@ -0,0 +1,159 @@
+//! Emulated hall-sensor (i.e. UVW) outputs, derived from an encoder's position.
+
+use core::ops::{Div, Mul};
+
+use num_traits::Euclid;
+use quadrature_decoder::HallState;
+
+use crate::{traits::*, OutputPinError};
+
+/// A driver of three output pins with emulated hall-sensor states,
+/// derived from an encoder's position, for feeding drives expecting hall-sensor
+/// commutation signals from a quadrature source.
+///
+/// The position is expected to be relative to the index
+/// (i.e. mechanical angle zero coinciding with electrical angle zero),
+/// as provided by [`IndexedIncrementalEncoder::synced_position()`](crate::IndexedIncrementalEncoder::synced_position).
+#[derive(Debug)]
+pub struct HallOutput<U, V, W> {
+    pins: (U, V, W),
+    pole_pairs: u8,
+    state: Option<HallState>,
+}
+
+impl<U, V, W> HallOutput<U, V, W>
+where
+    U: OutputPin,
+    V: OutputPin,
+    W: OutputPin,
+{
+    /// Creates a hall output driving the given `u`, `v` and `w` pins
+    /// for a motor with the given number of pole pairs.
+    pub fn new(pin_u: U, pin_v: V, pin_w: W, pole_pairs: u8) -> Self {
+        Self {
+            pins: (pin_u, pin_v, pin_w),
+            pole_pairs: pole_pairs.max(1),
+            state: None,
+        }
+    }
+
+    /// Returns the output's number of pole pairs.
+    pub fn pole_pairs(&self) -> u8 {
+        self.pole_pairs
+    }
+
+    /// Returns the most recently output hall state, if any.
+    pub fn state(&self) -> Option<HallState> {
+        self.state
+    }
+
+    /// Updates the output pins for the given `position`,
+    /// returning the resulting hall state.
+    ///
+    /// The position and number of pulses per revolution (PPR) are expected to be
+    /// relative to the encoder's step-mode (i.e. `cycles_per_revolution * Steps::PULSES_PER_CYCLE`).
+    ///
+    /// Pins only get written to if their state changed.
+    pub fn update<T>(
+        &mut self,
+        position: T,
+        pulses_per_revolution: T,
+    ) -> Result<HallState, OutputPinError>
+    where
+        T: Copy + PartialOrd + Euclid + Mul<Output = T> + Div<Output = T> + From<i8>,
+    {
+        let state = HallState::from_position(position, pulses_per_revolution, self.pole_pairs);
+
+        self.set_state(state)?;
+
+        Ok(state)
+    }
+
+    /// Sets the output pins to the given hall state.
+    ///
+    /// Pins only get written to if their state changed.
+    pub fn set_state(&mut self, state: HallState) -> Result<(), OutputPinError> {
+        let previous = self.state;
+
+        // Invalidate the state until all pins have been written successfully:
+        self.state = None;
+
+        if previous.map_or(true, |previous| previous.u != state.u) {
+            write(&mut self.pins.0, state.u).map_err(|_| OutputPinError::PinU)?;
+        }
+
+        if previous.map_or(true, |previous| previous.v != state.v) {
+            write(&mut self.pins.1, state.v).map_err(|_| OutputPinError::PinV)?;
+        }
+
+        if previous.map_or(true, |previous| previous.w != state.w) {
+            write(&mut self.pins.2, state.w).map_err(|_| OutputPinError::PinW)?;
+        }
+
+        self.state = Some(state);
+
+        Ok(())
+    }
+
+    /// Returns mutable references to the output's `u`, `v` and `w` pins.
+    pub fn pins_mut(&mut self) -> (&mut U, &mut V, &mut W) {
+        (&mut self.pins.0, &mut self.pins.1, &mut self.pins.2)
+    }
+
+    /// Consumes self, returning the output's `u`, `v` and `w` pins.
+    pub fn release(self) -> (U, V, W) {
+        self.pins
+    }
+}
+
+fn write<P>(pin: &mut P, level: bool) -> Result<(), P::Error>
+where
+    P: OutputPin,
+{
+    match level {
+        true => pin.set_high(),
+        false => pin.set_low(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use super::*;
+
+    #[test]
+    fn update() {
+        let pin_u = PinMock::new(&[
+            PinTransaction::set(PinState::High),
+            PinTransaction::set(PinState::Low),
+        ]);
+        let pin_v = PinMock::new(&[PinTransaction::set(PinState::Low)]);
+        let pin_w = PinMock::new(&[
+            PinTransaction::set(PinState::High),
+            PinTransaction::set(PinState::Low),
+            PinTransaction::set(PinState::High),
+        ]);
+
+        // One pole pair, 60 pulses per revolution: 10 pulses per sector.
+        let mut output = HallOutput::new(pin_u, pin_v, pin_w, 1);
+
+        assert_eq!(output.update(0, 60), Ok(HallState::new(true, false, true)));
+        assert_eq!(output.update(5, 60), Ok(HallState::new(true, false, true)));
+        assert_eq!(
+            output.update(10, 60),
+            Ok(HallState::new(true, false, false))
+        );
+        assert_eq!(
+            output.update(-1, 60),
+            Ok(HallState::new(false, false, true))
+        );
+
+        let (mut pin_u, mut pin_v, mut pin_w) = output.release();
+        pin_u.done();
+        pin_v.done();
+        pin_w.done();
+    }
+}
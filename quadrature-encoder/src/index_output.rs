@@ -0,0 +1,121 @@
+//! Synthesized index (i.e. Z) outputs, derived from an encoder's position.
+
+use num_traits::Euclid;
+
+use crate::{traits::*, OutputPinError};
+
+/// A driver of an output pin with a synthesized index (i.e. Z) signal,
+/// pulsing it every time an encoder's position (wrapped to a revolution) crosses zero,
+/// for feeding downstream equipment expecting an index signal from a 2-channel encoder.
+///
+/// A pulse is started by the update detecting the crossing and ended by the subsequent one,
+/// making the pulse width equal to the update interval.
+#[derive(Debug)]
+pub struct IndexOutput<P, T = i32> {
+    pin: P,
+    revolution: Option<T>,
+    is_pulsing: bool,
+}
+
+impl<P, T> IndexOutput<P, T>
+where
+    P: OutputPin,
+    T: Copy + PartialEq + Euclid,
+{
+    /// Creates an index output driving the given pin.
+    ///
+    /// The pin's initial level is assumed to be low.
+    pub fn new(pin: P) -> Self {
+        Self {
+            pin,
+            revolution: None,
+            is_pulsing: false,
+        }
+    }
+
+    /// Returns `true` if the output is currently pulsing, otherwise `false`.
+    pub fn is_pulsing(&self) -> bool {
+        self.is_pulsing
+    }
+
+    /// Updates the output pin for the given `position`,
+    /// returning `true` if a pulse was started, otherwise `false`.
+    ///
+    /// The position and number of pulses per revolution (PPR) are expected to be
+    /// relative to the encoder's step-mode (i.e. `cycles_per_revolution * Steps::PULSES_PER_CYCLE`).
+    ///
+    /// Any ongoing pulse gets ended first. No pulse is started by the very first update.
+    pub fn update(
+        &mut self,
+        position: T,
+        pulses_per_revolution: T,
+    ) -> Result<bool, OutputPinError> {
+        if self.is_pulsing {
+            self.pin.set_low().map_err(|_| OutputPinError::PinIdx)?;
+            self.is_pulsing = false;
+        }
+
+        let revolution = position.div_euclid(&pulses_per_revolution);
+        let previous = self.revolution.replace(revolution);
+
+        if previous.map_or(true, |previous| previous == revolution) {
+            return Ok(false);
+        }
+
+        self.pin.set_high().map_err(|_| OutputPinError::PinIdx)?;
+        self.is_pulsing = true;
+
+        Ok(true)
+    }
+
+    /// Resets the output, making the next observed position the new reference.
+    ///
+    /// Any ongoing pulse is left as is, getting ended by the next update.
+    pub fn reset(&mut self) {
+        self.revolution = None;
+    }
+
+    /// Returns a mutable reference to the output's pin.
+    pub fn pin_mut(&mut self) -> &mut P {
+        &mut self.pin
+    }
+
+    /// Consumes self, returning the output's pin.
+    pub fn release(self) -> P {
+        self.pin
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use super::*;
+
+    #[test]
+    fn update() {
+        let pin = PinMock::new(&[
+            PinTransaction::set(PinState::High),
+            PinTransaction::set(PinState::Low),
+            PinTransaction::set(PinState::High),
+            PinTransaction::set(PinState::Low),
+        ]);
+
+        let mut output = IndexOutput::new(pin);
+
+        // Crossing zero forwards (i.e. 3 -> 4), then backwards (i.e. 4 -> 3):
+        assert_eq!(output.update(2, 4), Ok(false));
+        assert_eq!(output.update(3, 4), Ok(false));
+        assert_eq!(output.update(4, 4), Ok(true));
+        assert!(output.is_pulsing());
+        assert_eq!(output.update(5, 4), Ok(false));
+        assert!(!output.is_pulsing());
+        assert_eq!(output.update(4, 4), Ok(false));
+        assert_eq!(output.update(3, 4), Ok(true));
+        assert_eq!(output.update(3, 4), Ok(false));
+
+        output.release().done();
+    }
+}
@@ -3,21 +3,95 @@
 #![warn(missing_docs)]
 #![cfg_attr(not(test), no_std)]
 
+mod absolute;
+mod button;
+pub mod compat;
+mod debounce;
+mod divider;
+#[cfg(feature = "dwt")]
+mod dwt;
+#[cfg(feature = "nb")]
+mod edge_flag;
+mod emitter;
 mod encoder;
+mod error_policy;
+#[cfg(feature = "embassy-time")]
+mod fixed_rate;
+mod hall;
+mod index_output;
+#[cfg(feature = "linux")]
+pub mod linux;
+#[cfg(all(test, feature = "embassy-time"))]
+mod mock_time;
 mod mode;
+mod notify;
+mod odometry;
+mod overflow;
+mod pin_source;
+mod poll_result;
+mod position_source;
+mod qei;
+#[cfg(feature = "remote")]
+pub mod remote;
+mod scale;
+mod select;
+#[cfg(feature = "shared")]
+mod shared;
+pub mod simulator;
+#[cfg(feature = "stats")]
+mod stats;
+#[cfg(feature = "stream")]
+mod stream;
+#[cfg(feature = "async")]
+mod timeout;
+pub mod trace;
 mod traits;
-pub use quadrature_decoder::{Error as QuadratureError, FullStep, HalfStep, QuadStep};
+pub use quadrature_decoder::{
+    max_poll_interval, AbsoluteDecoder, AccelProfile, Accelerator, BatchReport, Coding,
+    DecoderState, Divider, Error as QuadratureError, FullStep, GlitchFilter, HalfStep, HallState,
+    IndexAction, IndexBehavior, IndexEvent, IndexMode, IndexedDecoderState, LineState, LinearScale,
+    MultiEncoderDecoder, QuadStep, Resolution, RotaryScale, Snapshot, SnapshotError,
+    StepDirDecoder, StepMode, StepModeKind, Tick, UpDownPulseDecoder, VelocityEstimator,
+    VelocityTimeout, VelocityUnit, WiringReport,
+};
 
 pub use self::{
+    absolute::{AbsoluteError, GrayCodeEncoder, SsiEncoder},
+    button::{Button, ButtonEvent},
+    debounce::Debouncer,
+    divider::DividerOutput,
+    emitter::{NoPin, QuadratureEmitter},
     encoder::{
-        IncrementalEncoder, IndexedIncrementalEncoder, IndexedLinearEncoder, IndexedRotaryEncoder,
-        LinearEncoder, RotaryEncoder,
+        DynIncrementalEncoder, DynLinearEncoder, DynRotaryEncoder, IncrementalEncoder,
+        IndexedIncrementalEncoder, IndexedLinearEncoder, IndexedRotaryEncoder, LinearEncoder,
+        RotaryEncoder, StepDirEncoder, UpDownEncoder,
     },
+    error_policy::ErrorPolicy,
+    hall::HallOutput,
+    index_output::IndexOutput,
     mode::{
         Async, Blocking, Linear, LinearMovement, OperationMode, PollMode, Rotary, RotaryMovement,
     },
+    notify::Notifier,
+    odometry::{DifferentialOdometry, Pose},
+    overflow::Overflow,
+    pin_source::{PinSource, Polled},
+    poll_result::PollResult,
+    position_source::PositionSource,
+    qei::{LinearQeiEncoder, QeiBackend, QeiEncoder, RotaryQeiEncoder},
 };
 
+#[cfg(feature = "dwt")]
+pub use self::dwt::DwtClock;
+#[cfg(feature = "nb")]
+pub use self::edge_flag::EdgeFlag;
+#[cfg(feature = "embassy-time")]
+pub use self::fixed_rate::FixedRateStream;
+#[cfg(feature = "shared")]
+pub use self::shared::{PositionReader, PositionUpdater, SharedPosition};
+#[cfg(feature = "stats")]
+pub use self::stats::PollStats;
+
 /// An error indicating an input pin issue.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -30,6 +104,26 @@ pub enum InputPinError {
     PinIdx,
 }
 
+/// An error indicating an output pin issue.
+#[repr(u8)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutputPinError {
+    /// Failed writing `u` pin.
+    PinU,
+    /// Failed writing `v` pin.
+    PinV,
+    /// Failed writing `w` pin.
+    PinW,
+    /// Failed writing tick pin.
+    PinTick,
+    /// Failed writing index pin.
+    PinIdx,
+    /// Failed writing clock pin.
+    PinClk,
+    /// Failed writing data pin.
+    PinDt,
+}
+
 /// An error indicating quadrature or input pin issues.
 #[repr(u8)]
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -38,4 +132,6 @@ pub enum Error {
     Quadrature(QuadratureError),
     /// Input pin error.
     InputPin(InputPinError),
+    /// Counter overflow error (see `Overflow::Checked`).
+    Overflow,
 }
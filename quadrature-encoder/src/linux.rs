@@ -0,0 +1,260 @@
+//! Encoders on GPIO lines of Linux single-board computers (e.g. a Raspberry Pi),
+//! accessed via the GPIO character device (i.e. `/dev/gpiochipN`).
+//!
+//! Lines are requested as inputs via [`gpiocdev`], one request per line:
+//!
+//! ```no_run
+//! use quadrature_encoder::{linux::GpioLine, RotaryEncoder};
+//!
+//! let mut encoder: RotaryEncoder<GpioLine, GpioLine> =
+//!     RotaryEncoder::from_gpio_lines("/dev/gpiochip0", 17, 27)?;
+//!
+//! loop {
+//!     if let Ok(Some(movement)) = encoder.poll() {
+//!         println!("Movement: {movement:?}");
+//!     }
+//! }
+//! # Ok::<(), quadrature_encoder::linux::GpioError>(())
+//! ```
+//!
+//! With the `linux-tokio` feature enabled, lines further detect edges in the kernel,
+//! implementing `Wait`, so that async encoders (see `into_async()`) sleep until an edge event arrives,
+//! rather than busy-polling the lines.
+
+extern crate std;
+
+use std::path::Path;
+
+use embedded_hal_compat::eh1_0::digital::{Error, ErrorKind, ErrorType};
+use gpiocdev::{
+    line::{Offset, Value},
+    Request,
+};
+use num_traits::Zero;
+use quadrature_decoder::{IncrementalDecoder, IndexedIncrementalDecoder, StepMode};
+
+use crate::{traits::*, IncrementalEncoder, IndexedIncrementalEncoder, OperationMode, PollMode};
+
+/// The consumer name the lines are requested with (e.g. as listed by `gpioinfo`).
+const CONSUMER: &str = "quadrature-encoder";
+
+/// An error of a GPIO line (e.g. a failed request, or read),
+/// or of creating an encoder from GPIO lines.
+#[derive(Debug)]
+pub enum GpioError {
+    /// A failed request, or read, of a GPIO line.
+    Line(gpiocdev::Error),
+    /// A failed initial read of the encoder's lines (see `from_gpio_lines()`).
+    Encoder(crate::Error),
+}
+
+impl Error for GpioError {
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Other
+    }
+}
+
+impl From<gpiocdev::Error> for GpioError {
+    fn from(error: gpiocdev::Error) -> Self {
+        Self::Line(error)
+    }
+}
+
+impl From<crate::Error> for GpioError {
+    fn from(error: crate::Error) -> Self {
+        Self::Encoder(error)
+    }
+}
+
+/// An input pin on a GPIO line of a Linux GPIO character device.
+pub struct GpioLine {
+    #[cfg(not(feature = "linux-tokio"))]
+    request: Request,
+    #[cfg(feature = "linux-tokio")]
+    request: gpiocdev::tokio::AsyncRequest,
+    offset: Offset,
+}
+
+impl GpioLine {
+    /// Requests the line at the given `offset` of the given `chip` (e.g. `/dev/gpiochip0`) as an input.
+    pub fn new(chip: impl AsRef<Path>, offset: Offset) -> Result<Self, GpioError> {
+        let mut builder = Request::builder();
+        builder
+            .on_chip(chip.as_ref())
+            .with_consumer(CONSUMER)
+            .with_line(offset)
+            .as_input();
+
+        #[cfg(feature = "linux-tokio")]
+        builder.with_edge_detection(gpiocdev::line::EdgeDetection::BothEdges);
+
+        let request = builder.request()?;
+
+        Ok(Self {
+            #[cfg(not(feature = "linux-tokio"))]
+            request,
+            #[cfg(feature = "linux-tokio")]
+            request: gpiocdev::tokio::AsyncRequest::new(request),
+            offset,
+        })
+    }
+
+    /// Returns the line's offset on its chip.
+    pub fn offset(&self) -> Offset {
+        self.offset
+    }
+
+    fn request(&self) -> &Request {
+        #[cfg(not(feature = "linux-tokio"))]
+        return &self.request;
+        #[cfg(feature = "linux-tokio")]
+        return self.request.as_ref();
+    }
+}
+
+impl core::fmt::Debug for GpioLine {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("GpioLine")
+            .field("offset", &self.offset)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ErrorType for GpioLine {
+    type Error = GpioError;
+}
+
+impl InputPin for GpioLine {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        let value = self.request().value(self.offset)?;
+        Ok(value == Value::Active)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.is_high().map(|is_high| !is_high)
+    }
+}
+
+#[cfg(feature = "linux-tokio")]
+impl Wait for GpioLine {
+    async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+        // Edges occurring in between reading the line and awaiting an event
+        // get buffered by the kernel, so none of them can be missed:
+        while !self.is_high()? {
+            self.request.read_edge_event().await?;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+        while self.is_high()? {
+            self.request.read_edge_event().await?;
+        }
+        Ok(())
+    }
+
+    async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_low().await?;
+        self.wait_for_high().await
+    }
+
+    async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+        self.wait_for_high().await?;
+        self.wait_for_low().await
+    }
+
+    async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+        self.request.read_edge_event().await?;
+        Ok(())
+    }
+}
+
+impl<Mode, Steps, T, PM> IncrementalEncoder<Mode, GpioLine, GpioLine, Steps, T, PM>
+where
+    Mode: OperationMode,
+    Steps: StepMode,
+    T: Zero,
+    PM: PollMode,
+    IncrementalDecoder<Steps, T>: Default,
+{
+    /// Creates an incremental encoder driver for the given **clock** and **data** line offsets
+    /// of the given `chip` (e.g. `/dev/gpiochip0`).
+    ///
+    /// Returns `Err(GpioError::Line(_))` if requesting a line fails,
+    /// or `Err(GpioError::Encoder(_))` if reading a line's initial level fails (see `try_new()`):
+    ///
+    /// ```no_run
+    /// use quadrature_encoder::{linux::{GpioError, GpioLine}, Error, RotaryEncoder};
+    ///
+    /// let encoder: Result<RotaryEncoder<GpioLine, GpioLine>, _> =
+    ///     RotaryEncoder::from_gpio_lines("/dev/gpiochip0", 17, 27);
+    ///
+    /// match encoder {
+    ///     Ok(_) => println!("Encoder ready."),
+    ///     Err(GpioError::Line(error)) => println!("Failed requesting line: {error}"),
+    ///     Err(GpioError::Encoder(Error::InputPin(pin))) => println!("Failed reading {pin:?}."),
+    ///     Err(GpioError::Encoder(error)) => println!("Failed creating encoder: {error:?}"),
+    /// }
+    /// ```
+    pub fn from_gpio_lines(
+        chip: impl AsRef<Path>,
+        clk: Offset,
+        dt: Offset,
+    ) -> Result<Self, GpioError> {
+        let chip = chip.as_ref();
+
+        Ok(Self::try_new(
+            GpioLine::new(chip, clk)?,
+            GpioLine::new(chip, dt)?,
+        )?)
+    }
+}
+
+impl<Mode, Steps, T, PM> IndexedIncrementalEncoder<Mode, GpioLine, GpioLine, GpioLine, Steps, T, PM>
+where
+    Mode: OperationMode,
+    Steps: StepMode,
+    T: Zero,
+    PM: PollMode,
+    IndexedIncrementalDecoder<Steps, T>: Default,
+{
+    /// Creates an indexed incremental encoder driver for the given **clock**, **data** and **index** line offsets
+    /// of the given `chip` (e.g. `/dev/gpiochip0`).
+    ///
+    /// Returns `Err(GpioError::Line(_))` if requesting a line fails,
+    /// or `Err(GpioError::Encoder(_))` if reading a line's initial level fails (see `try_new()`).
+    pub fn from_gpio_lines(
+        chip: impl AsRef<Path>,
+        clk: Offset,
+        dt: Offset,
+        idx: Offset,
+    ) -> Result<Self, GpioError> {
+        let chip = chip.as_ref();
+
+        Ok(Self::try_new(
+            GpioLine::new(chip, clk)?,
+            GpioLine::new(chip, dt)?,
+            GpioLine::new(chip, idx)?,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{IndexedRotaryEncoder, RotaryEncoder};
+
+    use super::*;
+
+    #[test]
+    fn from_gpio_lines() {
+        let chip = "/dev/gpiochip-missing";
+
+        let encoder: Result<RotaryEncoder<GpioLine, GpioLine>, _> =
+            RotaryEncoder::from_gpio_lines(chip, 17, 27);
+        assert!(matches!(encoder, Err(GpioError::Line(_))));
+
+        let encoder: Result<IndexedRotaryEncoder<GpioLine, GpioLine, GpioLine>, _> =
+            IndexedRotaryEncoder::from_gpio_lines(chip, 17, 27, 22);
+        assert!(matches!(encoder, Err(GpioError::Line(_))));
+    }
+}
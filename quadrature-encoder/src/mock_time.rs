@@ -0,0 +1,37 @@
+//! Test support for code driven by `embassy-time`, via its (global) mock driver.
+
+use std::sync::{Mutex, MutexGuard};
+
+use embedded_hal_compat::eh1_0::digital::ErrorType;
+
+use crate::{traits::InputPin, PinSource};
+
+static CLOCK: Mutex<()> = Mutex::new(());
+
+/// Locks the mock driver's clock, serializing tests advancing it.
+pub(crate) fn lock() -> MutexGuard<'static, ()> {
+    CLOCK.lock().unwrap_or_else(|error| error.into_inner())
+}
+
+/// A pin that is stuck high, never seeing any edges.
+pub(crate) struct IdlePin;
+
+impl ErrorType for IdlePin {
+    type Error = core::convert::Infallible;
+}
+
+impl InputPin for IdlePin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+}
+
+impl PinSource for IdlePin {
+    async fn wait_edge(&mut self, _level: bool) -> Result<bool, Self::Error> {
+        core::future::pending().await
+    }
+}
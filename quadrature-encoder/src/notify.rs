@@ -0,0 +1,187 @@
+//! Change notifications of encoders, pushing positions rather than having them pulled.
+
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+use quadrature_decoder::StepMode;
+
+use crate::{
+    Async, Error, IncrementalEncoder, IndexedIncrementalEncoder, OperationMode, PinSource,
+    PositionSource,
+};
+
+/// A wrapper of an encoder, calling its `on_change` callback with the encoder's position
+/// and movement after every poll detecting a movement.
+///
+/// This allows for a single task to own the encoder's pins, while other tasks observe its position
+/// (e.g. by signaling an `embassy_sync::signal::Signal`, or sending into an `embassy_sync::watch::Watch`
+/// from within the callback), without sharing the encoder.
+#[derive(Debug)]
+pub struct Notifier<E, F> {
+    encoder: E,
+    on_change: F,
+}
+
+impl<E, F> Notifier<E, F> {
+    /// Wraps the given `encoder`, calling `on_change` with the encoder's position and movement
+    /// after every poll detecting a movement.
+    pub fn new(encoder: E, on_change: F) -> Self {
+        Self { encoder, on_change }
+    }
+
+    /// Returns a reference to the notifier's encoder.
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Returns a mutable reference to the notifier's encoder.
+    ///
+    /// Changes of the encoder's position made through the reference do not get notified.
+    pub fn encoder_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+
+    /// Consumes self, returning the notifier's encoder.
+    pub fn release(self) -> E {
+        self.encoder
+    }
+}
+
+impl<E, F> Notifier<E, F>
+where
+    E: PositionSource,
+    E::Movement: Copy,
+    F: FnMut(E::Position, E::Movement),
+{
+    /// Polls the encoder like [`PositionSource::poll()`],
+    /// calling the notifier's callback if a movement was detected.
+    pub fn poll(&mut self) -> Result<Option<E::Movement>, E::Error> {
+        let movement = self.encoder.poll()?;
+
+        if let Some(movement) = movement {
+            (self.on_change)(self.encoder.position(), movement);
+        }
+
+        Ok(movement)
+    }
+}
+
+impl<E, F> PositionSource for Notifier<E, F>
+where
+    E: PositionSource,
+    E::Movement: Copy,
+    F: FnMut(E::Position, E::Movement),
+{
+    type Movement = E::Movement;
+    type Position = E::Position;
+    type Error = E::Error;
+
+    fn poll(&mut self) -> Result<Option<Self::Movement>, Self::Error> {
+        self.poll()
+    }
+
+    fn position(&self) -> Self::Position {
+        self.encoder.position()
+    }
+
+    fn set_position(&mut self, position: Self::Position) {
+        self.encoder.set_position(position);
+    }
+
+    fn is_reversed(&self) -> bool {
+        self.encoder.is_reversed()
+    }
+
+    fn reset(&mut self) {
+        self.encoder.reset();
+    }
+}
+
+impl<Mode, Clk, Dt, Steps, T, F> Notifier<IncrementalEncoder<Mode, Clk, Dt, Steps, T, Async>, F>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    Mode::Movement: Copy,
+    F: FnMut(T, Mode::Movement),
+{
+    /// Polls the encoder like [`IncrementalEncoder::poll()`],
+    /// calling the notifier's callback if a movement was detected.
+    pub async fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let movement = self.encoder.poll().await?;
+
+        if let Some(movement) = movement {
+            (self.on_change)(self.encoder.position(), movement);
+        }
+
+        Ok(movement)
+    }
+}
+
+impl<Mode, Clk, Dt, Idx, Steps, T, F>
+    Notifier<IndexedIncrementalEncoder<Mode, Clk, Dt, Idx, Steps, T, Async>, F>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    Idx: PinSource,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+    Mode::Movement: Copy,
+    F: FnMut(T, Mode::Movement),
+{
+    /// Polls the encoder like [`IndexedIncrementalEncoder::poll()`],
+    /// calling the notifier's callback if a movement was detected.
+    pub async fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let movement = self.encoder.poll().await?;
+
+        if let Some(movement) = movement {
+            (self.on_change)(self.encoder.position(), movement);
+        }
+
+        Ok(movement)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{QuadStep, RotaryEncoder, RotaryMovement};
+
+    use super::*;
+
+    #[test]
+    fn poll() {
+        use PinState::*;
+
+        let pin_clk = PinMock::new(&[
+            PinTransaction::get(High),
+            PinTransaction::get(Low),
+            PinTransaction::get(Low),
+        ]);
+        let pin_dt = PinMock::new(&[
+            PinTransaction::get(High),
+            PinTransaction::get(High),
+            PinTransaction::get(High),
+        ]);
+
+        let encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(pin_clk, pin_dt);
+
+        let mut changes = Vec::new();
+        let mut notifier = Notifier::new(encoder, |position, movement| {
+            changes.push((position, movement));
+        });
+
+        assert_eq!(notifier.poll(), Ok(Some(RotaryMovement::Clockwise)));
+        assert_eq!(notifier.poll(), Ok(None));
+
+        let (mut pin_clk, mut pin_dt) = notifier.release().release();
+        pin_clk.done();
+        pin_dt.done();
+
+        assert_eq!(changes, [(1, RotaryMovement::Clockwise)]);
+    }
+}
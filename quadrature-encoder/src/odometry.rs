@@ -0,0 +1,233 @@
+//! Wheel odometry of differential-drive robots, based on a pair of encoders.
+
+use core::ops::{Add, Div, Mul, Sub};
+
+use num_traits::{FromPrimitive, ToPrimitive, WrappingSub};
+
+use crate::{PositionSource, RotaryScale};
+
+/// A change of a robot's pose, relative to its pose at the time of the previous update
+/// (i.e. with `x` pointing forward, `y` pointing to the left and `heading` turning counter-clockwise).
+///
+/// The distances are in the units of the odometry's wheel radius and track width,
+/// while the heading is in radians.
+#[derive(Clone, Copy, PartialEq, Default, Debug)]
+pub struct Pose<U> {
+    /// The distance travelled forward.
+    pub x: U,
+    /// The distance travelled to the left.
+    pub y: U,
+    /// The change of heading (counter-clockwise, in radians).
+    pub heading: U,
+}
+
+/// Wheel odometry of a differential-drive robot, turning the positions of its `left` and `right`
+/// wheels' encoders into pose deltas.
+///
+/// The odometry either owns the encoders, or borrows them mutably (as `&mut E`),
+/// with the encoders getting polled independently of the odometry's updates
+/// (e.g. in a fast loop, or in async tasks).
+///
+/// Both encoders are expected to report positive positions when driving forward
+/// (e.g. by reversing the encoder of the mirrored wheel via `.reversed()`).
+///
+/// The odometry is generic over its output type `U`, which may be a float (e.g. `f32`),
+/// or a fixed-point type (e.g. from the `fixed` crate, with its `num-traits` feature enabled),
+/// as it approximates the trigonometry of arcs by polynomials, accurate for small changes of heading
+/// (i.e. below `0.5` radians per update).
+#[derive(Debug)]
+pub struct DifferentialOdometry<L, R, U = f32>
+where
+    L: PositionSource,
+    R: PositionSource,
+{
+    left: L,
+    right: R,
+    scale: RotaryScale<U>,
+    wheel_radius: U,
+    track_width: U,
+    previous: (L::Position, R::Position),
+}
+
+impl<L, R, U> DifferentialOdometry<L, R, U>
+where
+    L: PositionSource,
+    R: PositionSource,
+    L::Position: Copy + WrappingSub + ToPrimitive,
+    R::Position: Copy + WrappingSub + ToPrimitive,
+    U: Copy + FromPrimitive + Add<Output = U> + Sub<Output = U> + Mul<Output = U> + Div<Output = U>,
+{
+    /// Creates an odometry for the given `left` and `right` wheels' encoders,
+    /// sharing the wheels' `scale`, with the given `wheel_radius` and `track_width`
+    /// (i.e. the distance between the wheels' contact points, in the same unit as `wheel_radius`).
+    ///
+    /// The encoders' current positions are taken as the reference of the first update.
+    pub fn new(left: L, right: R, scale: RotaryScale<U>, wheel_radius: U, track_width: U) -> Self {
+        let previous = (left.position(), right.position());
+
+        Self {
+            left,
+            right,
+            scale,
+            wheel_radius,
+            track_width,
+            previous,
+        }
+    }
+
+    /// Returns references to the odometry's `left` and `right` encoders.
+    pub fn encoders(&self) -> (&L, &R) {
+        (&self.left, &self.right)
+    }
+
+    /// Returns mutable references to the odometry's `left` and `right` encoders (e.g. for polling them).
+    ///
+    /// Changes of the encoders' positions made through the references (other than by polling)
+    /// get reported as movements by the next update, unless followed by a call of [`rebase()`](Self::rebase).
+    pub fn encoders_mut(&mut self) -> (&mut L, &mut R) {
+        (&mut self.left, &mut self.right)
+    }
+
+    /// Consumes self, returning the odometry's `left` and `right` encoders.
+    pub fn release(self) -> (L, R) {
+        (self.left, self.right)
+    }
+
+    /// Takes the encoders' current positions as the reference of the next update,
+    /// discarding any movement since the previous update.
+    pub fn rebase(&mut self) {
+        self.previous = (self.left.position(), self.right.position());
+    }
+
+    /// Returns the change of the robot's pose since the previous update (or creation),
+    /// or `None` if it is not representable by `U` (in which case the movement carries over).
+    pub fn update(&mut self) -> Option<Pose<U>> {
+        let positions = (self.left.position(), self.right.position());
+
+        let left = self.distance(positions.0.wrapping_sub(&self.previous.0))?;
+        let right = self.distance(positions.1.wrapping_sub(&self.previous.1))?;
+
+        let two = U::from_u8(2)?;
+        let distance = (left + right) / two;
+        let heading = (right - left) / self.track_width;
+
+        // Travelling along an arc of the given `distance` and change of `heading`
+        // moves the robot by `distance * sin(heading) / heading` forward
+        // and by `distance * (1 - cos(heading)) / heading` to the left,
+        // with both factors approximated by their Taylor series:
+        let one = U::from_u8(1)?;
+        let squared = heading * heading;
+        let sinc = one - squared / U::from_u8(6)? * (one - squared / U::from_u8(20)?);
+        let cosc =
+            heading / two * (one - squared / U::from_u8(12)? * (one - squared / U::from_u8(30)?));
+
+        self.previous = positions;
+
+        Some(Pose {
+            x: distance * sinc,
+            y: distance * cosc,
+            heading,
+        })
+    }
+
+    fn distance<T>(&self, delta: T) -> Option<U>
+    where
+        T: ToPrimitive,
+    {
+        Some(self.scale.radians(delta)? * self.wheel_radius)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::f32::consts::{PI, TAU};
+
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{LinearEncoder, QuadStep};
+
+    use super::*;
+
+    fn assert_pose_eq(pose: Option<Pose<f32>>, expected: Pose<f32>) {
+        let pose = pose.unwrap();
+        assert!(
+            (pose.x - expected.x).abs() < 1e-4,
+            "{pose:?} != {expected:?}"
+        );
+        assert!(
+            (pose.y - expected.y).abs() < 1e-4,
+            "{pose:?} != {expected:?}"
+        );
+        assert!(
+            (pose.heading - expected.heading).abs() < 1e-4,
+            "{pose:?} != {expected:?}"
+        );
+    }
+
+    #[test]
+    fn update() {
+        fn encoder() -> LinearEncoder<PinMock, PinMock, QuadStep> {
+            let pin_clk = PinMock::new(&[PinTransaction::get(PinState::Low)]);
+            let pin_dt = PinMock::new(&[PinTransaction::get(PinState::Low)]);
+            LinearEncoder::new(pin_clk, pin_dt)
+        }
+
+        let mut left = encoder();
+        let mut right = encoder();
+
+        let scale = RotaryScale::new(400).unwrap();
+        let mut odometry = DifferentialOdometry::new(&mut left, &mut right, scale, 10.0, 100.0);
+
+        assert_eq!(odometry.update(), Some(Pose::default()));
+
+        // Driving straight ahead, one wheel revolution:
+        let (left, right) = odometry.encoders_mut();
+        left.set_position(400);
+        right.set_position(400);
+        assert_pose_eq(
+            odometry.update(),
+            Pose {
+                x: TAU * 10.0,
+                y: 0.0,
+                heading: 0.0,
+            },
+        );
+
+        // Turning on the spot, counter-clockwise:
+        let (left, right) = odometry.encoders_mut();
+        left.set_position(300);
+        right.set_position(500);
+        assert_pose_eq(
+            odometry.update(),
+            Pose {
+                x: 0.0,
+                y: 0.0,
+                heading: PI / 10.0,
+            },
+        );
+
+        // Driving along an arc, turning clockwise:
+        let (left, right) = odometry.encoders_mut();
+        left.set_position(400);
+        right.set_position(580);
+        let heading: f32 = -PI / 100.0;
+        let distance = PI * 9.0 / 2.0;
+        assert_pose_eq(
+            odometry.update(),
+            Pose {
+                x: distance * heading.sin() / heading,
+                y: distance * (1.0 - heading.cos()) / heading,
+                heading,
+            },
+        );
+
+        let (left, right) = odometry.release();
+        for encoder in [left, right] {
+            let (pin_clk, pin_dt) = encoder.pins_mut();
+            pin_clk.done();
+            pin_dt.done();
+        }
+    }
+}
@@ -0,0 +1,102 @@
+//! Handling of counter overflows.
+
+use num_traits::{SaturatingAdd, WrappingNeg};
+use quadrature_decoder::Change;
+
+use crate::Error;
+
+/// The behavior of an encoder's counter on overflowing its bounds (e.g. `i16::MAX`).
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum Overflow {
+    /// Wraps around to the opposite bound (e.g. for modular angle tracking).
+    Wrapping,
+    /// Stays pegged at the bound, setting the sticky saturation flag (see `is_saturated()`).
+    #[default]
+    Saturating,
+    /// Stays pegged at the bound, like `Saturating`, but reports every overflow as `Err(Error::Overflow)`.
+    Checked,
+}
+
+impl Overflow {
+    /// Handles the adjustment of a counter by the given `change` from `previous`,
+    /// returning the wrapped counter, if it is to be replaced.
+    pub(crate) fn handle<T>(self, previous: T, change: Change) -> Result<Option<T>, Error>
+    where
+        T: Copy + PartialEq + SaturatingAdd + WrappingNeg + From<i8>,
+    {
+        // A change unable to move the counter means it is pegged at one of its bounds
+        // (regardless of any subsequent corrections, e.g. by an index):
+        let delta = T::from(change as i8);
+        if previous.saturating_add(&delta) != previous {
+            return Ok(None);
+        }
+
+        match self {
+            Self::Wrapping => Ok(Some(wrapped(previous, change))),
+            Self::Saturating => Ok(None),
+            Self::Checked => Err(Error::Overflow),
+        }
+    }
+}
+
+/// Returns the opposite bound of the given `bound` overflown by `change`
+/// (i.e. `MIN` for `MAX` and vice versa, given a two's complement `T`).
+fn wrapped<T>(bound: T, change: Change) -> T
+where
+    T: SaturatingAdd + WrappingNeg + From<i8>,
+{
+    match change {
+        // MIN == -MAX - 1
+        Change::Positive => bound.wrapping_neg().saturating_add(&T::from(-1)),
+        // MAX == -(MIN + 1)
+        Change::Negative => bound.saturating_add(&T::from(1)).wrapping_neg(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{QuadStep, RotaryEncoder, RotaryMovement};
+
+    use super::*;
+
+    #[test]
+    fn wrapped() {
+        assert_eq!(super::wrapped(i8::MAX, Change::Positive), i8::MIN);
+        assert_eq!(super::wrapped(i8::MIN, Change::Negative), i8::MAX);
+    }
+
+    #[test]
+    fn overflow() {
+        use PinState::*;
+
+        let levels = [(High, High), (Low, High), (Low, Low)];
+        let (clk, dt): (Vec<_>, Vec<_>) = levels
+            .iter()
+            .map(|&(clk, dt)| (PinTransaction::get(clk), PinTransaction::get(dt)))
+            .unzip();
+
+        let mut encoder: RotaryEncoder<_, _, QuadStep, i8> =
+            RotaryEncoder::new(PinMock::new(&clk), PinMock::new(&dt))
+                .with_overflow(Overflow::Wrapping);
+        encoder.set_position(i8::MAX);
+
+        assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::Clockwise)));
+        assert_eq!(encoder.position(), i8::MIN);
+        assert!(!encoder.is_saturated());
+        assert_eq!(encoder.delta_since(i8::MAX), 1);
+
+        encoder.set_overflow(Overflow::Checked);
+        encoder.set_position(i8::MAX);
+
+        assert_eq!(encoder.poll(), Err(Error::Overflow));
+        assert_eq!(encoder.position(), i8::MAX);
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+}
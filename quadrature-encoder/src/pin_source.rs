@@ -0,0 +1,138 @@
+//! Input pins usable by both blocking and async encoder drivers.
+
+use core::{
+    future::{poll_fn, Future},
+    task::Poll,
+};
+
+use embedded_hal_compat::eh1_0::digital::ErrorType;
+
+use crate::traits::*;
+
+/// An input pin that can be read, as well as waited on for edges.
+///
+/// Blocking drivers only require their pins to implement `InputPin`,
+/// while async drivers require `PinSource`, waiting for edges via [`wait_edge()`](PinSource::wait_edge).
+///
+/// The trait is implemented for:
+///
+/// - any pin implementing both `InputPin` and `Wait`, waiting for edges via interrupts.
+/// - any `InputPin`, wrapped in [`Polled<…>`], waiting for edges via busy-polling.
+///
+/// Drivers select their async support by these implementations, rather than by cfg flags.
+/// The only exception is the implementation for `Wait` pins requiring the `async` feature,
+/// as `Wait` is provided by the (optional) `embedded-hal-async` dependency.
+///
+/// The future returned by `wait_edge()` is not an associated type (i.e. a GAT),
+/// but a return-position `impl Future`, as the futures of `Wait` pins cannot be named
+/// (being returned by `async fn`s). Either way, no allocations or boxing are involved.
+pub trait PinSource: InputPin {
+    /// Reads the pin's current level, returning `true` if the pin is high.
+    fn read(&mut self) -> Result<bool, Self::Error> {
+        self.is_high()
+    }
+
+    /// Waits for the pin to leave the given `level`, returning its new level.
+    fn wait_edge(&mut self, level: bool) -> impl Future<Output = Result<bool, Self::Error>>;
+}
+
+#[cfg(feature = "async")]
+impl<P> PinSource for P
+where
+    P: InputPin + Wait,
+{
+    async fn wait_edge(&mut self, level: bool) -> Result<bool, Self::Error> {
+        // Return the toggled level, rather than reading the pin state directly,
+        // as the pin state has likely changed since the wait future was resolved
+        // by the hardware interrupt behind-the-scenes.
+        match level {
+            true => self.wait_for_low().await?,
+            false => self.wait_for_high().await?,
+        }
+
+        Ok(!level)
+    }
+}
+
+/// A wrapper for input pins without `Wait` support,
+/// making them usable by async drivers by busy-polling for edges.
+///
+/// Busy-polling keeps the executor awake, so prefer pins
+/// implementing `Wait` in power-sensitive applications.
+#[derive(Debug)]
+pub struct Polled<P> {
+    pin: P,
+}
+
+impl<P> Polled<P> {
+    /// Wraps the given `pin`.
+    pub fn new(pin: P) -> Self {
+        Self { pin }
+    }
+
+    /// Consumes self, returning the wrapped pin.
+    pub fn into_inner(self) -> P {
+        self.pin
+    }
+}
+
+impl<P> ErrorType for Polled<P>
+where
+    P: InputPin,
+{
+    type Error = P::Error;
+}
+
+impl<P> InputPin for Polled<P>
+where
+    P: InputPin,
+{
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        self.pin.is_high()
+    }
+
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        self.pin.is_low()
+    }
+}
+
+impl<P> PinSource for Polled<P>
+where
+    P: InputPin,
+{
+    fn wait_edge(&mut self, level: bool) -> impl Future<Output = Result<bool, Self::Error>> {
+        poll_fn(move |cx| match self.pin.is_high() {
+            Ok(is_high) if is_high != level => Poll::Ready(Ok(is_high)),
+            Ok(_) => {
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+            Err(error) => Poll::Ready(Err(error)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embassy_futures::block_on;
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use super::*;
+
+    #[test]
+    fn polled_wait_edge() {
+        let pin = PinMock::new(&[
+            PinTransaction::get(PinState::High),
+            PinTransaction::get(PinState::High),
+            PinTransaction::get(PinState::Low),
+        ]);
+
+        let mut pin = Polled::new(pin);
+
+        assert_eq!(block_on(pin.wait_edge(true)), Ok(false));
+
+        pin.into_inner().done();
+    }
+}
@@ -0,0 +1,15 @@
+//! The combined result of a single poll.
+
+/// The movement, position and timestamp of a single poll, as returned by `poll_full()`.
+///
+/// Obtaining all three from a single call keeps them consistent,
+/// as opposed to separately querying the position after polling.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PollResult<Movement, T> {
+    /// The detected movement, if any.
+    pub movement: Option<Movement>,
+    /// The encoder's position after the poll.
+    pub position: T,
+    /// The poll's timestamp in ticks, as provided by the caller's clock.
+    pub timestamp: u64,
+}
@@ -0,0 +1,197 @@
+//! A common facade of encoders, regardless of whether they are decoded in software or in hardware.
+
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+use quadrature_decoder::StepMode;
+
+use crate::{
+    traits::*, Blocking, DynIncrementalEncoder, Error, IncrementalEncoder,
+    IndexedIncrementalEncoder, OperationMode, QeiBackend, QeiEncoder,
+};
+
+/// A source of an encoder's position, implemented by both software-decoded encoders
+/// (i.e. `IncrementalEncoder<…>`, `IndexedIncrementalEncoder<…>` and `DynIncrementalEncoder<…>` in blocking poll mode)
+/// and encoders backed by hardware quadrature counters (i.e. [`QeiEncoder<…>`]),
+/// allowing application code to be written against either.
+pub trait PositionSource {
+    /// The type of movement reported by the source.
+    type Movement;
+    /// The type of the source's position.
+    type Position;
+    /// The error type of polls.
+    type Error;
+
+    /// Updates the source's position, returning the detected movement, if any.
+    fn poll(&mut self) -> Result<Option<Self::Movement>, Self::Error>;
+
+    /// Returns the source's position.
+    fn position(&self) -> Self::Position;
+
+    /// Sets the source's position.
+    fn set_position(&mut self, position: Self::Position);
+
+    /// Returns `true` if the source is reversed, otherwise `false`.
+    fn is_reversed(&self) -> bool;
+
+    /// Resets the source's position back to `0`.
+    fn reset(&mut self);
+}
+
+impl<S> PositionSource for &mut S
+where
+    S: PositionSource + ?Sized,
+{
+    type Movement = S::Movement;
+    type Position = S::Position;
+    type Error = S::Error;
+
+    fn poll(&mut self) -> Result<Option<Self::Movement>, Self::Error> {
+        (**self).poll()
+    }
+
+    fn position(&self) -> Self::Position {
+        (**self).position()
+    }
+
+    fn set_position(&mut self, position: Self::Position) {
+        (**self).set_position(position);
+    }
+
+    fn is_reversed(&self) -> bool {
+        (**self).is_reversed()
+    }
+
+    fn reset(&mut self) {
+        (**self).reset();
+    }
+}
+
+impl<Mode, Clk, Dt, Steps, T> PositionSource
+    for IncrementalEncoder<Mode, Clk, Dt, Steps, T, Blocking>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    type Movement = Mode::Movement;
+    type Position = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Result<Option<Self::Movement>, Self::Error> {
+        self.poll()
+    }
+
+    fn position(&self) -> Self::Position {
+        self.position()
+    }
+
+    fn set_position(&mut self, position: Self::Position) {
+        self.set_position(position);
+    }
+
+    fn is_reversed(&self) -> bool {
+        self.is_reversed()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+impl<Mode, Clk, Dt, Idx, Steps, T> PositionSource
+    for IndexedIncrementalEncoder<Mode, Clk, Dt, Idx, Steps, T, Blocking>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    Idx: InputPin,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    type Movement = Mode::Movement;
+    type Position = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Result<Option<Self::Movement>, Self::Error> {
+        self.poll()
+    }
+
+    fn position(&self) -> Self::Position {
+        self.position()
+    }
+
+    fn set_position(&mut self, position: Self::Position) {
+        self.set_position(position);
+    }
+
+    fn is_reversed(&self) -> bool {
+        self.is_reversed()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+impl<Mode, Clk, Dt, T> PositionSource for DynIncrementalEncoder<Mode, Clk, Dt, T, Blocking>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    type Movement = Mode::Movement;
+    type Position = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Result<Option<Self::Movement>, Self::Error> {
+        self.poll()
+    }
+
+    fn position(&self) -> Self::Position {
+        self.position()
+    }
+
+    fn set_position(&mut self, position: Self::Position) {
+        self.set_position(position);
+    }
+
+    fn is_reversed(&self) -> bool {
+        self.is_reversed()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
+
+impl<Mode, Q> PositionSource for QeiEncoder<Mode, Q>
+where
+    Mode: OperationMode,
+    Q: QeiBackend,
+{
+    type Movement = Mode::Movement;
+    type Position = i32;
+    type Error = Q::Error;
+
+    fn poll(&mut self) -> Result<Option<Self::Movement>, Self::Error> {
+        self.poll()
+    }
+
+    fn position(&self) -> Self::Position {
+        self.position()
+    }
+
+    fn set_position(&mut self, position: Self::Position) {
+        self.set_position(position);
+    }
+
+    fn is_reversed(&self) -> bool {
+        self.is_reversed()
+    }
+
+    fn reset(&mut self) {
+        self.reset();
+    }
+}
@@ -0,0 +1,196 @@
+//! Encoders backed by hardware quadrature counters (e.g. STM32 timers, or RP2040 PIO).
+
+use core::marker::PhantomData;
+
+use quadrature_decoder::Change;
+
+use crate::{
+    mode::{Movement, OperationMode},
+    Linear, Rotary,
+};
+
+/// A hardware quadrature counter (i.e. a quadrature encoder interface, QEI),
+/// such as an STM32 timer in encoder mode, or an RP2040 PIO program.
+///
+/// The counter is expected to count up for positive changes (i.e. `Change::Positive`)
+/// and to wrap around at its width of [`BITS`](Self::BITS) bits.
+pub trait QeiBackend {
+    /// The counter's width in bits (e.g. `16` for most STM32 timers), up to `32`.
+    const BITS: u32;
+
+    /// The error type of counter reads.
+    type Error;
+
+    /// Reads the counter's current value.
+    fn count(&mut self) -> Result<u32, Self::Error>;
+}
+
+/// Rotary encoder, backed by a hardware quadrature counter.
+pub type RotaryQeiEncoder<Q> = QeiEncoder<Rotary, Q>;
+/// Linear encoder, backed by a hardware quadrature counter.
+pub type LinearQeiEncoder<Q> = QeiEncoder<Linear, Q>;
+
+/// An encoder backed by a hardware quadrature counter, rather than by polled pins,
+/// providing the same facade (i.e. position, reversal and reset) as `IncrementalEncoder<…>`.
+///
+/// The counter's step-mode is determined by the hardware's configuration.
+/// Positions are tracked in software (saturating at the bounds of `i32`),
+/// extending the counter's range beyond its width, as long as the encoder
+/// is polled at least once per half of the counter's range.
+#[derive(Debug)]
+pub struct QeiEncoder<Mode, Q> {
+    backend: Q,
+    count: Option<u32>,
+    position: i32,
+    is_reversed: bool,
+    _mode: PhantomData<Mode>,
+}
+
+impl<Mode, Q> QeiEncoder<Mode, Q>
+where
+    Mode: OperationMode,
+    Q: QeiBackend,
+{
+    /// Creates an encoder backed by the given hardware quadrature counter.
+    ///
+    /// The counter's value at the first poll defines the encoder's initial position.
+    pub fn new(backend: Q) -> Self {
+        Self {
+            backend,
+            count: None,
+            position: 0,
+            is_reversed: false,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Sets the encoder's reversed mode, making it report flipped movements and positions.
+    pub fn reversed(mut self) -> Self {
+        self.is_reversed = true;
+        self
+    }
+
+    /// Returns `true` if the encoder is reversed, otherwise `false`.
+    pub fn is_reversed(&self) -> bool {
+        self.is_reversed
+    }
+
+    /// Returns a mutable borrow of the hardware quadrature counter.
+    pub fn backend_mut(&mut self) -> &mut Q {
+        &mut self.backend
+    }
+
+    /// Consumes self, returning the hardware quadrature counter.
+    pub fn release(self) -> Q {
+        self.backend
+    }
+
+    /// Updates the encoder's position from the hardware counter,
+    /// returning the direction of the net movement since the previous poll
+    /// (or `None` if there was none), or `Err(_)` if reading the counter failed.
+    pub fn poll(&mut self) -> Result<Option<Mode::Movement>, Q::Error> {
+        let count = self.backend.count()?;
+
+        let Some(previous) = self.count.replace(count) else {
+            return Ok(None);
+        };
+
+        // Sign-extend the wrapping difference of the counter's width:
+        let shift = 32 - Q::BITS.clamp(1, 32);
+        let delta = (count.wrapping_sub(previous) << shift) as i32 >> shift;
+
+        self.position = self.position.saturating_add(delta);
+
+        let change = match delta {
+            0 => return Ok(None),
+            delta if delta > 0 => Change::Positive,
+            _ => Change::Negative,
+        };
+
+        let movement = Mode::Movement::from(change);
+
+        Ok(Some(match self.is_reversed {
+            true => movement.flipped(),
+            false => movement,
+        }))
+    }
+
+    /// Returns the encoder's position relative to its initial position.
+    pub fn position(&self) -> i32 {
+        match self.is_reversed {
+            true => self.position.wrapping_neg(),
+            false => self.position,
+        }
+    }
+
+    /// Sets the encoder's position.
+    pub fn set_position(&mut self, position: i32) {
+        self.position = match self.is_reversed {
+            true => position.wrapping_neg(),
+            false => position,
+        };
+    }
+
+    /// Resets the encoder's position back to `0`.
+    ///
+    /// The hardware counter itself is left untouched.
+    pub fn reset(&mut self) {
+        self.position = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::RotaryMovement;
+
+    use super::*;
+
+    struct Counter<'a>(core::slice::Iter<'a, u32>);
+
+    impl QeiBackend for Counter<'_> {
+        const BITS: u32 = 16;
+
+        type Error = ();
+
+        fn count(&mut self) -> Result<u32, Self::Error> {
+            self.0.next().copied().ok_or(())
+        }
+    }
+
+    #[test]
+    fn poll() {
+        let counts = [65_534, 65_535, 1, 1, 65_530];
+        let mut encoder: RotaryQeiEncoder<_> = QeiEncoder::new(Counter(counts.iter()));
+
+        assert_eq!(encoder.poll(), Ok(None));
+        assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::Clockwise)));
+        assert_eq!(encoder.position(), 1);
+
+        // Wrapping around the counter's width:
+        assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::Clockwise)));
+        assert_eq!(encoder.position(), 3);
+
+        assert_eq!(encoder.poll(), Ok(None));
+
+        assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::CounterClockwise)));
+        assert_eq!(encoder.position(), -4);
+
+        assert_eq!(encoder.poll(), Err(()));
+    }
+
+    #[test]
+    fn reversed() {
+        let counts = [0, 5];
+        let mut encoder: RotaryQeiEncoder<_> = QeiEncoder::new(Counter(counts.iter())).reversed();
+
+        encoder.poll().unwrap();
+        assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::CounterClockwise)));
+        assert_eq!(encoder.position(), -5);
+
+        encoder.set_position(10);
+        assert_eq!(encoder.position(), 10);
+
+        encoder.reset();
+        assert_eq!(encoder.position(), 0);
+    }
+}
@@ -0,0 +1,239 @@
+//! Remote pin sampling over a byte stream (e.g. a UART).
+//!
+//! A remote sampler (e.g. a microcontroller attached to the encoder) streams packed samples
+//! of the encoder's pins, with up to two samples per byte, one per nibble (low nibble first):
+//!
+//! ```plain
+//!       ┌───────┬───┬───┬───┐
+//! Bits: │   3   │ 2 │ 1 │ 0 │
+//!       └───────┴───┴───┴───┘
+//!          │      │   │   └── clock (i.e. `a`) level
+//!          │      │   └── data (i.e. `b`) level
+//!          │      └── index (i.e. `z`) level
+//!          └── valid bit
+//! ```
+//!
+//! Nibbles with a cleared valid bit are padding and get skipped.
+//! Samplers without an index pin are expected to keep the index bit cleared.
+
+use core::marker::PhantomData;
+
+use embedded_io::Read;
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+use quadrature_decoder::{Change, FullStep, IndexedIncrementalDecoder, StepMode};
+
+use crate::{
+    mode::{Movement, OperationMode},
+    QuadratureError,
+};
+
+const VALID_BIT: u8 = 0b1000;
+
+/// A sample of an encoder's clock, data and index pin levels.
+pub type RemoteSample = (bool, bool, bool);
+
+/// Packs the given samples into a single byte, as expected by [`RemoteEncoder`].
+pub const fn pack(first: RemoteSample, second: Option<RemoteSample>) -> u8 {
+    let second = match second {
+        Some(sample) => pack_nibble(sample),
+        None => 0,
+    };
+
+    pack_nibble(first) | (second << 4)
+}
+
+/// Unpacks the (up to two) samples of the given byte, in order.
+pub const fn unpack(byte: u8) -> [Option<RemoteSample>; 2] {
+    [unpack_nibble(byte & 0x0F), unpack_nibble(byte >> 4)]
+}
+
+const fn pack_nibble((clk, dt, idx): RemoteSample) -> u8 {
+    VALID_BIT | (idx as u8) << 2 | (dt as u8) << 1 | clk as u8
+}
+
+const fn unpack_nibble(nibble: u8) -> Option<RemoteSample> {
+    if nibble & VALID_BIT == 0 {
+        return None;
+    }
+
+    Some((
+        nibble & 0b001 != 0,
+        nibble & 0b010 != 0,
+        nibble & 0b100 != 0,
+    ))
+}
+
+/// An error indicating quadrature or byte stream issues.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RemoteError<E> {
+    /// Quadrature error.
+    Quadrature(QuadratureError),
+    /// Byte stream read error.
+    Read(E),
+    /// The byte stream has ended.
+    EndOfStream,
+}
+
+/// An encoder decoding the samples streamed by a remote sampler,
+/// read from an `embedded-io` byte stream.
+#[derive(Debug)]
+pub struct RemoteEncoder<Mode, R, Steps = FullStep, T = i32> {
+    decoder: IndexedIncrementalDecoder<Steps, T>,
+    reader: R,
+    pending: Option<RemoteSample>,
+    is_reversed: bool,
+    _mode: PhantomData<Mode>,
+}
+
+impl<Mode, R, Steps, T> RemoteEncoder<Mode, R, Steps, T>
+where
+    Mode: OperationMode,
+    R: Read,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Creates a remote encoder reading samples from the given `reader`.
+    pub fn new(reader: R) -> Self
+    where
+        IndexedIncrementalDecoder<Steps, T>: Default,
+    {
+        Self {
+            decoder: Default::default(),
+            reader,
+            pending: None,
+            is_reversed: false,
+            _mode: PhantomData,
+        }
+    }
+
+    /// Sets the encoder's reversed mode, making it report flipped movements and positions.
+    pub fn reversed(mut self) -> Self {
+        self.is_reversed = true;
+        self
+    }
+
+    /// Returns `true` if the encoder is reversed, otherwise `false`.
+    pub fn is_reversed(&self) -> bool {
+        self.is_reversed
+    }
+
+    /// Returns a mutable borrow for the byte stream reader.
+    pub fn reader_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+
+    /// Consumes self, returning the byte stream reader.
+    pub fn release(self) -> R {
+        self.reader
+    }
+
+    /// Updates the encoder's state based on the next streamed sample,
+    /// returning the direction if a movement was detected, `None` if no movement was detected,
+    /// or `Err(_)` if an invalid input (i.e. a positional "jump") or stream issue was detected.
+    ///
+    /// Blocks until a sample is available, reading a byte from the stream only
+    /// once all previously read samples have been decoded.
+    pub fn poll(&mut self) -> Result<Option<Mode::Movement>, RemoteError<R::Error>> {
+        let (clk, dt, idx) = self.next_sample()?;
+
+        let change: Option<Change> = self
+            .decoder
+            .update(clk, dt, idx)
+            .map_err(RemoteError::Quadrature)?;
+        let movement: Option<Mode::Movement> = change.map(From::from);
+
+        Ok(movement.map(|movement| {
+            if self.is_reversed() {
+                movement.flipped()
+            } else {
+                movement
+            }
+        }))
+    }
+
+    /// Returns `true` if the most recent poll detected an index, otherwise `false`.
+    pub fn is_at_index(&self) -> bool {
+        self.decoder.is_at_index()
+    }
+
+    /// Resets the encoder to its initial state, discarding any pending sample.
+    pub fn reset(&mut self) {
+        self.decoder.reset();
+        self.pending = None;
+    }
+
+    /// Returns the encoder's position counter relative to its initial position in number of cycles.
+    pub fn position(&self) -> T {
+        match self.is_reversed {
+            true => self.decoder.counter().wrapping_neg(),
+            false => self.decoder.counter(),
+        }
+    }
+
+    /// Sets the encoder's position.
+    pub fn set_position(&mut self, position: T) {
+        match self.is_reversed {
+            true => self.decoder.set_counter(position.wrapping_neg()),
+            false => self.decoder.set_counter(position),
+        }
+    }
+
+    fn next_sample(&mut self) -> Result<RemoteSample, RemoteError<R::Error>> {
+        loop {
+            if let Some(sample) = self.pending.take() {
+                return Ok(sample);
+            }
+
+            let mut byte = [0_u8; 1];
+            if self.reader.read(&mut byte).map_err(RemoteError::Read)? == 0 {
+                return Err(RemoteError::EndOfStream);
+            }
+
+            let [first, second] = unpack(byte[0]);
+            self.pending = second;
+
+            if let Some(sample) = first {
+                return Ok(sample);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quadrature_decoder::QuadStep;
+
+    use crate::{Rotary, RotaryMovement};
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let samples = [(true, false, false), (false, true, true)];
+
+        assert_eq!(
+            unpack(pack(samples[0], Some(samples[1]))),
+            [Some(samples[0]), Some(samples[1])]
+        );
+        assert_eq!(unpack(pack(samples[1], None)), [Some(samples[1]), None]);
+        assert_eq!(unpack(0x00), [None, None]);
+    }
+
+    #[test]
+    fn poll() {
+        let bytes = [
+            pack((false, true, false), Some((false, false, false))),
+            0x00, // padding
+            pack((true, false, false), None),
+        ];
+
+        let mut encoder: RemoteEncoder<Rotary, _, QuadStep> = RemoteEncoder::new(&bytes[..]);
+
+        for _ in 0..3 {
+            assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::Clockwise)));
+        }
+
+        assert_eq!(encoder.poll(), Err(RemoteError::EndOfStream));
+        assert_eq!(encoder.position(), 3);
+    }
+}
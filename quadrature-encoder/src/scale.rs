@@ -0,0 +1,162 @@
+//! Positions in physical units (e.g. degrees, or millimeters).
+
+use core::ops::{Add, Div, Mul};
+
+use num_traits::{FromPrimitive, One, SaturatingAdd, ToPrimitive, WrappingNeg, Zero};
+use quadrature_decoder::StepMode;
+
+use crate::{
+    traits::*, IncrementalEncoder, IndexedIncrementalEncoder, Linear, LinearScale, PollMode,
+    Rotary, RotaryScale,
+};
+
+impl<Clk, Dt, Steps, T, PM> IncrementalEncoder<Rotary, Clk, Dt, Steps, T, PM>
+where
+    Clk: InputPin,
+    Dt: InputPin,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8> + ToPrimitive,
+    PM: PollMode,
+{
+    /// Returns the encoder's position as an angle in degrees (subject to the encoder's reversal),
+    /// or `None` if it is not representable by `U`.
+    ///
+    /// The scale's number of pulses per revolution is relative to the encoder's step-mode.
+    pub fn position_degrees<U>(&self, scale: &RotaryScale<U>) -> Option<U>
+    where
+        U: Copy + FromPrimitive + Add<Output = U> + Mul<Output = U> + Div<Output = U>,
+    {
+        scale.degrees(self.position())
+    }
+
+    /// Returns the encoder's position as an angle in radians (subject to the encoder's reversal),
+    /// or `None` if it is not representable by `U`.
+    ///
+    /// The scale's number of pulses per revolution is relative to the encoder's step-mode.
+    pub fn position_radians<U>(&self, scale: &RotaryScale<U>) -> Option<U>
+    where
+        U: Copy + FromPrimitive + Add<Output = U> + Mul<Output = U> + Div<Output = U>,
+    {
+        scale.radians(self.position())
+    }
+}
+
+impl<Clk, Dt, Steps, T, PM> IncrementalEncoder<Linear, Clk, Dt, Steps, T, PM>
+where
+    Clk: InputPin,
+    Dt: InputPin,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8> + ToPrimitive,
+    PM: PollMode,
+{
+    /// Returns the encoder's position as a distance in millimeters (subject to the encoder's reversal),
+    /// or `None` if it is not representable by `U`.
+    ///
+    /// The scale's number of pulses per millimeter is relative to the encoder's step-mode.
+    pub fn position_mm<U>(&self, scale: &LinearScale<U>) -> Option<U>
+    where
+        U: Copy + FromPrimitive + Div<Output = U>,
+    {
+        scale.millimeters(self.position())
+    }
+}
+
+impl<Clk, Dt, Idx, Steps, T, PM> IndexedIncrementalEncoder<Rotary, Clk, Dt, Idx, Steps, T, PM>
+where
+    Clk: InputPin,
+    Dt: InputPin,
+    Idx: InputPin,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8> + ToPrimitive,
+    PM: PollMode,
+{
+    /// Returns the encoder's position as an angle in degrees (subject to the encoder's reversal),
+    /// or `None` if it is not representable by `U`.
+    ///
+    /// The scale's number of pulses per revolution is relative to the encoder's step-mode.
+    pub fn position_degrees<U>(&self, scale: &RotaryScale<U>) -> Option<U>
+    where
+        U: Copy + FromPrimitive + Add<Output = U> + Mul<Output = U> + Div<Output = U>,
+    {
+        scale.degrees(self.position())
+    }
+
+    /// Returns the encoder's position as an angle in radians (subject to the encoder's reversal),
+    /// or `None` if it is not representable by `U`.
+    ///
+    /// The scale's number of pulses per revolution is relative to the encoder's step-mode.
+    pub fn position_radians<U>(&self, scale: &RotaryScale<U>) -> Option<U>
+    where
+        U: Copy + FromPrimitive + Add<Output = U> + Mul<Output = U> + Div<Output = U>,
+    {
+        scale.radians(self.position())
+    }
+}
+
+impl<Clk, Dt, Idx, Steps, T, PM> IndexedIncrementalEncoder<Linear, Clk, Dt, Idx, Steps, T, PM>
+where
+    Clk: InputPin,
+    Dt: InputPin,
+    Idx: InputPin,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8> + ToPrimitive,
+    PM: PollMode,
+{
+    /// Returns the encoder's position as a distance in millimeters (subject to the encoder's reversal),
+    /// or `None` if it is not representable by `U`.
+    ///
+    /// The scale's number of pulses per millimeter is relative to the encoder's step-mode.
+    pub fn position_mm<U>(&self, scale: &LinearScale<U>) -> Option<U>
+    where
+        U: Copy + FromPrimitive + Div<Output = U>,
+    {
+        scale.millimeters(self.position())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{LinearEncoder, QuadStep, RotaryEncoder};
+
+    use super::*;
+
+    #[test]
+    fn position_degrees() {
+        let pin_clk = PinMock::new(&[PinTransaction::get(PinState::Low)]);
+        let pin_dt = PinMock::new(&[PinTransaction::get(PinState::Low)]);
+
+        let encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(pin_clk, pin_dt)
+            .reversed()
+            .with_initial_position(-100);
+
+        let scale: RotaryScale<f32> = RotaryScale::new(400).unwrap();
+        assert_eq!(encoder.position_degrees(&scale), Some(-90.0));
+        assert_eq!(
+            encoder.position_radians(&scale),
+            Some(-core::f32::consts::FRAC_PI_2)
+        );
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+
+    #[test]
+    fn position_mm() {
+        let pin_clk = PinMock::new(&[PinTransaction::get(PinState::Low)]);
+        let pin_dt = PinMock::new(&[PinTransaction::get(PinState::Low)]);
+
+        let encoder: LinearEncoder<_, _> =
+            LinearEncoder::new(pin_clk, pin_dt).with_initial_position(-30);
+
+        assert_eq!(encoder.position_mm(&LinearScale::new(20.0_f32)), Some(-1.5));
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+}
@@ -0,0 +1,110 @@
+//! A minimal, executor-agnostic `select` over multiple futures.
+//!
+//! Futures are polled in order of their arguments, so earlier
+//! futures take precedence if multiple are ready at the same time.
+
+use core::{
+    future::{poll_fn, Future},
+    pin::pin,
+    task::Poll,
+};
+
+/// Result of [`select`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Either<A, B> {
+    /// First future finished first.
+    First(A),
+    /// Second future finished first.
+    Second(B),
+}
+
+/// Result of [`select3`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum Either3<A, B, C> {
+    /// First future finished first.
+    First(A),
+    /// Second future finished first.
+    Second(B),
+    /// Third future finished first.
+    Third(C),
+}
+
+/// Waits for one of two futures to complete, dropping the other one.
+pub(crate) async fn select<A, B>(a: A, b: B) -> Either<A::Output, B::Output>
+where
+    A: Future,
+    B: Future,
+{
+    let mut a = pin!(a);
+    let mut b = pin!(b);
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = a.as_mut().poll(cx) {
+            return Poll::Ready(Either::First(output));
+        }
+        if let Poll::Ready(output) = b.as_mut().poll(cx) {
+            return Poll::Ready(Either::Second(output));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+/// Waits for one of three futures to complete, dropping the other ones.
+pub(crate) async fn select3<A, B, C>(a: A, b: B, c: C) -> Either3<A::Output, B::Output, C::Output>
+where
+    A: Future,
+    B: Future,
+    C: Future,
+{
+    let mut a = pin!(a);
+    let mut b = pin!(b);
+    let mut c = pin!(c);
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = a.as_mut().poll(cx) {
+            return Poll::Ready(Either3::First(output));
+        }
+        if let Poll::Ready(output) = b.as_mut().poll(cx) {
+            return Poll::Ready(Either3::Second(output));
+        }
+        if let Poll::Ready(output) = c.as_mut().poll(cx) {
+            return Poll::Ready(Either3::Third(output));
+        }
+        Poll::Pending
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use core::future::{pending, ready};
+
+    use embassy_futures::block_on;
+
+    use super::*;
+
+    #[test]
+    fn select_first_ready() {
+        let result = block_on(select(ready(1), pending::<()>()));
+        assert_eq!(result, Either::First(1));
+
+        let result = block_on(select(pending::<()>(), ready(2)));
+        assert_eq!(result, Either::Second(2));
+    }
+
+    #[test]
+    fn select_prefers_earlier() {
+        let result = block_on(select(ready(1), ready(2)));
+        assert_eq!(result, Either::First(1));
+
+        let result = block_on(select3(ready(1), ready(2), ready(3)));
+        assert_eq!(result, Either3::First(1));
+    }
+
+    #[test]
+    fn select3_first_ready() {
+        let result = block_on(select3(pending::<()>(), pending::<()>(), ready(3)));
+        assert_eq!(result, Either3::Third(3));
+    }
+}
@@ -0,0 +1,252 @@
+//! Positions shared across cores (e.g. the two cores of an RP2040).
+
+use core::sync::atomic::{AtomicI32, Ordering};
+
+use quadrature_decoder::StepMode;
+
+use crate::{
+    traits::*, Blocking, Error, IncrementalEncoder, IndexedIncrementalEncoder, OperationMode,
+    PollMode,
+};
+
+/// A `Sync` handle of an encoder's position, shared between a single writer
+/// (e.g. core1, running the tight decode loop) and any number of readers (e.g. core0).
+///
+/// The handle is lock-free, relying on atomic loads and stores only (i.e. no compare-and-swap),
+/// which are available even on cores lacking atomic read-modify-write operations,
+/// such as the RP2040's Cortex-M0+.
+///
+/// # Memory-ordering contract
+///
+/// - There must only be a single writer calling [`publish()`](Self::publish) at any time.
+/// - A position is published with `Release` ordering and loaded with `Acquire` ordering:
+///   a reader observing a published position also observes every memory write
+///   the writer performed before publishing it.
+/// - Readers always observe a complete (i.e. untorn) position, which may be outdated
+///   by the time it is returned, but is never older than any position previously observed
+///   by the same reader.
+#[derive(Debug)]
+pub struct SharedPosition {
+    position: AtomicI32,
+}
+
+impl SharedPosition {
+    /// Creates a shared position, initialized to the given `position`.
+    ///
+    /// Being a `const fn` this allows for use in `static` items.
+    pub const fn new(position: i32) -> Self {
+        Self {
+            position: AtomicI32::new(position),
+        }
+    }
+
+    /// Publishes the given `position`, making it visible to all readers.
+    ///
+    /// Must only ever be called by a single writer at any time.
+    pub fn publish(&self, position: i32) {
+        self.position.store(position, Ordering::Release);
+    }
+
+    /// Returns the most recently published position.
+    pub fn load(&self) -> i32 {
+        self.position.load(Ordering::Acquire)
+    }
+}
+
+impl Default for SharedPosition {
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+/// The updater half of a split encoder (e.g. owned by an interrupt handler),
+/// publishing the encoder's position to its [`PositionReader`] half after every poll.
+#[derive(Debug)]
+pub struct PositionUpdater<'a, E> {
+    encoder: E,
+    shared: &'a SharedPosition,
+}
+
+impl<E> PositionUpdater<'_, E> {
+    /// Returns a reference to the updater's encoder.
+    pub fn encoder(&self) -> &E {
+        &self.encoder
+    }
+
+    /// Returns a mutable reference to the updater's encoder.
+    ///
+    /// Changes of the encoder's position made through the reference
+    /// only get published by the subsequent poll.
+    pub fn encoder_mut(&mut self) -> &mut E {
+        &mut self.encoder
+    }
+
+    /// Consumes self, returning the updater's encoder.
+    pub fn release(self) -> E {
+        self.encoder
+    }
+}
+
+/// The lock-free reader half of a split encoder (e.g. used by the main loop),
+/// returning the position most recently published by its [`PositionUpdater`] half.
+#[derive(Clone, Copy, Debug)]
+pub struct PositionReader<'a> {
+    shared: &'a SharedPosition,
+}
+
+impl PositionReader<'_> {
+    /// Returns the most recently published position.
+    pub fn position(&self) -> i32 {
+        self.shared.load()
+    }
+}
+
+impl<Mode, Clk, Dt, Steps, PM> IncrementalEncoder<Mode, Clk, Dt, Steps, i32, PM>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    Steps: StepMode,
+    PM: PollMode,
+{
+    /// Splits the encoder into an updater half (e.g. to be polled from an interrupt handler)
+    /// and a lock-free reader half (e.g. for reading the position from the main loop),
+    /// sharing the position via the given `shared` position, without a mutex around the encoder.
+    ///
+    /// The encoder's current position gets published right away.
+    pub fn split(self, shared: &SharedPosition) -> (PositionUpdater<'_, Self>, PositionReader<'_>) {
+        shared.publish(self.position());
+
+        (
+            PositionUpdater {
+                encoder: self,
+                shared,
+            },
+            PositionReader { shared },
+        )
+    }
+}
+
+impl<Mode, Clk, Dt, Idx, Steps, PM> IndexedIncrementalEncoder<Mode, Clk, Dt, Idx, Steps, i32, PM>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    Idx: InputPin,
+    Steps: StepMode,
+    PM: PollMode,
+{
+    /// Splits the encoder into an updater half (e.g. to be polled from an interrupt handler)
+    /// and a lock-free reader half (e.g. for reading the position from the main loop),
+    /// sharing the position via the given `shared` position, without a mutex around the encoder.
+    ///
+    /// The encoder's current position gets published right away.
+    pub fn split(self, shared: &SharedPosition) -> (PositionUpdater<'_, Self>, PositionReader<'_>) {
+        shared.publish(self.position());
+
+        (
+            PositionUpdater {
+                encoder: self,
+                shared,
+            },
+            PositionReader { shared },
+        )
+    }
+}
+
+impl<Mode, Clk, Dt, Steps>
+    PositionUpdater<'_, IncrementalEncoder<Mode, Clk, Dt, Steps, i32, Blocking>>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    Steps: StepMode,
+{
+    /// Polls the encoder like [`IncrementalEncoder::poll()`],
+    /// publishing the resulting position to the reader half.
+    pub fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let result = self.encoder.poll();
+        self.shared.publish(self.encoder.position());
+        result
+    }
+}
+
+impl<Mode, Clk, Dt, Idx, Steps>
+    PositionUpdater<'_, IndexedIncrementalEncoder<Mode, Clk, Dt, Idx, Steps, i32, Blocking>>
+where
+    Mode: OperationMode,
+    Clk: InputPin,
+    Dt: InputPin,
+    Idx: InputPin,
+    Steps: StepMode,
+{
+    /// Polls the encoder like [`IndexedIncrementalEncoder::poll()`],
+    /// publishing the resulting position to the reader half.
+    pub fn poll(&mut self) -> Result<Option<Mode::Movement>, Error> {
+        let result = self.encoder.poll();
+        self.shared.publish(self.encoder.position());
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{QuadStep, RotaryEncoder};
+
+    use super::*;
+
+    #[test]
+    fn cross_thread() {
+        static POSITION: SharedPosition = SharedPosition::new(0);
+
+        let writer = thread::spawn(|| {
+            for position in 1..=10_000 {
+                POSITION.publish(position);
+            }
+        });
+
+        let mut last = POSITION.load();
+
+        while !writer.is_finished() {
+            let position = POSITION.load();
+            assert!(position >= last);
+            last = position;
+        }
+
+        writer.join().unwrap();
+
+        assert_eq!(POSITION.load(), 10_000);
+    }
+
+    #[test]
+    fn split() {
+        use PinState::{High, Low};
+
+        let pin_clk = PinMock::new(&[High, Low, Low].map(PinTransaction::get));
+        let pin_dt = PinMock::new(&[High, High, Low].map(PinTransaction::get));
+
+        let encoder: RotaryEncoder<_, _, QuadStep> =
+            RotaryEncoder::new(pin_clk, pin_dt).with_initial_position(5);
+
+        let shared = SharedPosition::new(0);
+        let (mut updater, reader) = encoder.split(&shared);
+
+        assert_eq!(reader.position(), 5);
+
+        updater.poll().unwrap();
+        assert_eq!(reader.position(), 6);
+
+        updater.poll().unwrap();
+        assert_eq!(reader.position(), 7);
+
+        let (mut pin_clk, mut pin_dt) = updater.release().release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+}
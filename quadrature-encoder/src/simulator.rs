@@ -0,0 +1,407 @@
+//! A deterministic quadrature signal simulator, for exercising decoders and encoder drivers in tests
+//! (see [`quadrature_decoder::simulator`]).
+//!
+//! With the `simulator` feature enabled, a `VirtualEncoder` further provides virtual pins,
+//! driven by a scripted motion profile (including contact bounce and missed steps),
+//! for testing integration code against realistic waveforms, without any hardware.
+
+pub use quadrature_decoder::simulator::*;
+
+#[cfg(feature = "simulator")]
+pub use self::virtual_encoder::{Motion, VirtualEncoder, VirtualPin};
+
+#[cfg(feature = "simulator")]
+mod virtual_encoder {
+    extern crate alloc;
+
+    use alloc::{rc::Rc, vec, vec::Vec};
+    use core::{cell::RefCell, convert::Infallible};
+
+    #[cfg(feature = "async")]
+    use core::{future::poll_fn, task::Poll};
+
+    use embedded_hal_compat::eh1_0::digital::ErrorType;
+    use quadrature_decoder::Change;
+
+    use crate::traits::*;
+
+    use super::{Channel, Fault, Sample, Simulator};
+
+    /// A segment of a virtual encoder's motion profile.
+    ///
+    /// Movements are measured in quarter-cycles (i.e. relative to `QuadStep`).
+    #[derive(Clone, Copy, PartialEq, Eq, Debug)]
+    pub enum Motion {
+        /// Moves by the given number of quarter-cycles in clockwise direction (i.e. `Change::Positive`).
+        Clockwise(u32),
+        /// Moves by the given number of quarter-cycles in counter-clockwise direction (i.e. `Change::Negative`).
+        CounterClockwise(u32),
+        /// Bounces the most recent transition's contacts back and forth the given number of times,
+        /// without moving.
+        Bounce(u32),
+        /// Moves by the given (signed, i.e. positive for clockwise) number of quarter-cycles at once,
+        /// without any edges in between (i.e. as missed by an undersampling driver).
+        MissedSteps(i32),
+        /// Holds still for the given number of samples.
+        Hold(u32),
+    }
+
+    /// The samples of a compiled motion profile, shared by a virtual encoder's pins.
+    #[derive(Debug)]
+    struct Timeline {
+        samples: Vec<(Sample, i64)>,
+        time: usize,
+    }
+
+    impl Timeline {
+        fn compile(mut simulator: Simulator<'_>, profile: &[Motion]) -> Self {
+            let mut samples = vec![(simulator.sample(), simulator.position())];
+            let mut bounced: Option<(Sample, Sample)> = None;
+
+            for &motion in profile {
+                let current = samples[samples.len() - 1];
+
+                match motion {
+                    Motion::Clockwise(steps) | Motion::CounterClockwise(steps) => {
+                        let change = match motion {
+                            Motion::Clockwise(_) => Change::Positive,
+                            _ => Change::Negative,
+                        };
+
+                        for _ in 0..steps {
+                            let from = simulator.sample();
+                            let burst: Vec<_> = simulator.transition(change).collect();
+                            let position = simulator.position();
+
+                            samples.extend(burst.into_iter().map(|sample| (sample, position)));
+                            bounced = Some((from, simulator.sample()));
+                        }
+                    }
+                    Motion::Bounce(count) => {
+                        if let Some((from, to)) = bounced {
+                            for _ in 0..count {
+                                samples.push((from, current.1));
+                                samples.push((to, current.1));
+                            }
+                        }
+                    }
+                    Motion::MissedSteps(steps) => {
+                        let from = simulator.sample();
+                        let change = match steps < 0 {
+                            true => Change::Negative,
+                            false => Change::Positive,
+                        };
+
+                        simulator.inject(Fault::DroppedEdges);
+                        for _ in 0..steps.unsigned_abs() {
+                            simulator.step(change);
+                        }
+                        simulator.clear(Fault::DroppedEdges);
+
+                        samples.push((simulator.sample(), simulator.position()));
+                        bounced = Some((from, simulator.sample()));
+                    }
+                    Motion::Hold(count) => {
+                        samples.extend((0..count).map(|_| current));
+                    }
+                }
+            }
+
+            Self { samples, time: 0 }
+        }
+
+        fn level_at(&self, time: usize, channel: Channel) -> bool {
+            let (sample, _) = self.samples[time];
+
+            match channel {
+                Channel::A => sample.a,
+                Channel::B => sample.b,
+                Channel::Z => sample.z,
+            }
+        }
+
+        fn level(&self, channel: Channel) -> bool {
+            self.level_at(self.time, channel)
+        }
+
+        fn position(&self) -> i64 {
+            self.samples[self.time].1
+        }
+
+        fn is_finished(&self) -> bool {
+            self.time + 1 >= self.samples.len()
+        }
+
+        fn advance(&mut self) {
+            if !self.is_finished() {
+                self.time += 1;
+            }
+        }
+    }
+
+    /// A virtual (optionally indexed) quadrature encoder, providing virtual pins
+    /// driven by a scripted motion profile (e.g. "rotate 10 CW, bounce twice, 3 CCW"),
+    /// for testing integration code against realistic waveforms.
+    ///
+    /// The pins share a timeline of samples, which advances whenever a pin gets read again
+    /// (i.e. once per poll of a blocking encoder), or awaits an edge (i.e. for async encoders).
+    /// Once the profile is finished the pins hold their final levels, with waits never completing.
+    ///
+    /// ```
+    /// use quadrature_encoder::{
+    ///     simulator::{Motion, VirtualEncoder},
+    ///     QuadStep, RotaryEncoder,
+    /// };
+    ///
+    /// let simulated = VirtualEncoder::new(&[
+    ///     Motion::Clockwise(10),
+    ///     Motion::Bounce(2),
+    ///     Motion::CounterClockwise(3),
+    /// ]);
+    ///
+    /// let mut encoder: RotaryEncoder<_, _, QuadStep> =
+    ///     RotaryEncoder::new(simulated.clk(), simulated.dt());
+    ///
+    /// while !simulated.is_finished() {
+    ///     encoder.poll().unwrap();
+    /// }
+    ///
+    /// assert_eq!(encoder.position(), 7);
+    /// ```
+    #[derive(Clone, Debug)]
+    pub struct VirtualEncoder {
+        timeline: Rc<RefCell<Timeline>>,
+    }
+
+    impl VirtualEncoder {
+        /// Creates a fault-free virtual encoder without index, moving according to the given `profile`.
+        pub fn new(profile: &[Motion]) -> Self {
+            Self::with_simulator(Simulator::new(), profile)
+        }
+
+        /// Creates a virtual encoder moving according to the given `profile`,
+        /// subject to the given `simulator`'s index, contact bounce model and fault script.
+        pub fn with_simulator(simulator: Simulator<'_>, profile: &[Motion]) -> Self {
+            let timeline = Timeline::compile(simulator, profile);
+
+            Self {
+                timeline: Rc::new(RefCell::new(timeline)),
+            }
+        }
+
+        /// Returns a virtual **clock** pin (i.e. of the `a` pulse train).
+        pub fn clk(&self) -> VirtualPin {
+            self.pin(Channel::A)
+        }
+
+        /// Returns a virtual **data** pin (i.e. of the `b` pulse train).
+        pub fn dt(&self) -> VirtualPin {
+            self.pin(Channel::B)
+        }
+
+        /// Returns a virtual **index** pin (i.e. of the `z` pulse train).
+        pub fn idx(&self) -> VirtualPin {
+            self.pin(Channel::Z)
+        }
+
+        /// Returns the virtual encoder's true position in quarter-cycles at the current sample.
+        pub fn position(&self) -> i64 {
+            self.timeline.borrow().position()
+        }
+
+        /// Returns `true` if the motion profile has been played back completely, otherwise `false`.
+        pub fn is_finished(&self) -> bool {
+            self.timeline.borrow().is_finished()
+        }
+
+        fn pin(&self, channel: Channel) -> VirtualPin {
+            VirtualPin {
+                timeline: self.timeline.clone(),
+                channel,
+                read_at: None,
+            }
+        }
+    }
+
+    /// A virtual input pin of a [`VirtualEncoder`].
+    #[derive(Debug)]
+    pub struct VirtualPin {
+        timeline: Rc<RefCell<Timeline>>,
+        channel: Channel,
+        read_at: Option<usize>,
+    }
+
+    impl VirtualPin {
+        fn read(&mut self) -> bool {
+            let mut timeline = self.timeline.borrow_mut();
+
+            // Reading the same sample twice means time has passed in between:
+            if self.read_at == Some(timeline.time) {
+                timeline.advance();
+            }
+
+            self.read_at = Some(timeline.time);
+            timeline.level(self.channel)
+        }
+
+        #[cfg(feature = "async")]
+        async fn wait_for_level(&mut self, level: bool) {
+            let mut deferred_at = None;
+
+            poll_fn(|cx| {
+                let mut timeline = self.timeline.borrow_mut();
+
+                loop {
+                    if timeline.level(self.channel) == level {
+                        self.read_at = None;
+                        return Poll::Ready(());
+                    }
+
+                    if timeline.is_finished() {
+                        return Poll::Pending;
+                    }
+
+                    let next = timeline.time + 1;
+                    let is_own_edge =
+                        timeline.level_at(next, self.channel) != timeline.level(self.channel);
+                    let is_any_edge = timeline.samples[next].0 != timeline.samples[timeline.time].0;
+
+                    // Let waiters on other channels pick up their edges first,
+                    // unless nobody else has advanced the timeline since:
+                    if is_any_edge && !is_own_edge && deferred_at != Some(timeline.time) {
+                        deferred_at = Some(timeline.time);
+                        cx.waker().wake_by_ref();
+                        return Poll::Pending;
+                    }
+
+                    timeline.advance();
+                }
+            })
+            .await
+        }
+    }
+
+    impl ErrorType for VirtualPin {
+        type Error = Infallible;
+    }
+
+    impl InputPin for VirtualPin {
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.read())
+        }
+
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.read())
+        }
+    }
+
+    #[cfg(feature = "async")]
+    impl Wait for VirtualPin {
+        async fn wait_for_high(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(true).await;
+            Ok(())
+        }
+
+        async fn wait_for_low(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(false).await;
+            Ok(())
+        }
+
+        async fn wait_for_rising_edge(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(false).await;
+            self.wait_for_level(true).await;
+            Ok(())
+        }
+
+        async fn wait_for_falling_edge(&mut self) -> Result<(), Self::Error> {
+            self.wait_for_level(true).await;
+            self.wait_for_level(false).await;
+            Ok(())
+        }
+
+        async fn wait_for_any_edge(&mut self) -> Result<(), Self::Error> {
+            let level = self.timeline.borrow().level(self.channel);
+            self.wait_for_level(!level).await;
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use crate::{QuadStep, RotaryEncoder, RotaryMovement};
+
+        use super::*;
+
+        const PROFILE: [Motion; 4] = [
+            Motion::Clockwise(4),
+            Motion::Bounce(2),
+            Motion::Hold(3),
+            Motion::CounterClockwise(2),
+        ];
+
+        #[test]
+        fn blocking() {
+            let simulated = VirtualEncoder::new(&PROFILE);
+
+            let mut encoder: RotaryEncoder<_, _, QuadStep> =
+                RotaryEncoder::new(simulated.clk(), simulated.dt());
+
+            let mut movements = Vec::new();
+            while !simulated.is_finished() {
+                if let Some(movement) = encoder.poll().unwrap() {
+                    movements.push(movement);
+                }
+            }
+
+            use RotaryMovement::*;
+            assert_eq!(
+                movements,
+                [
+                    Clockwise,
+                    Clockwise,
+                    Clockwise,
+                    Clockwise,
+                    CounterClockwise,
+                    Clockwise,
+                    CounterClockwise,
+                    Clockwise,
+                    CounterClockwise,
+                    CounterClockwise,
+                ]
+            );
+            assert_eq!(encoder.position(), 2);
+            assert_eq!(simulated.position(), 2);
+        }
+
+        #[cfg(feature = "async")]
+        #[test]
+        fn wait() {
+            use embassy_futures::block_on;
+
+            let simulated = VirtualEncoder::new(&PROFILE);
+
+            let encoder: RotaryEncoder<_, _, QuadStep> =
+                RotaryEncoder::new(simulated.clk(), simulated.dt());
+            let mut encoder = encoder.into_async();
+
+            for _ in 0..10 {
+                assert!(block_on(encoder.poll()).is_ok());
+            }
+
+            assert_eq!(encoder.position(), 2);
+            assert!(simulated.is_finished());
+        }
+
+        #[test]
+        fn missed_steps() {
+            let simulated = VirtualEncoder::new(&[Motion::Clockwise(1), Motion::MissedSteps(2)]);
+
+            let mut encoder: RotaryEncoder<_, _, QuadStep> =
+                RotaryEncoder::new(simulated.clk(), simulated.dt());
+
+            assert_eq!(encoder.poll(), Ok(Some(RotaryMovement::Clockwise)));
+            assert!(encoder.poll().is_err());
+            assert_eq!(simulated.position(), 3);
+        }
+    }
+}
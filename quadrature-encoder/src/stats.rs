@@ -0,0 +1,130 @@
+//! Instrumentation of poll calls and wakeups per reported movement.
+
+use core::{
+    future::{poll_fn, Future},
+    pin::pin,
+};
+
+/// Counters of an encoder's poll calls and wakeups, relative to its reported movements.
+///
+/// A "wakeup" is a single evaluation of the encoder's pins:
+/// each blocking `poll()` counts as one, while an async `poll()`
+/// counts one for every time its future got polled by the executor.
+///
+/// Comparing the ratios before and after adding debounce filters or optimizing
+/// port reads quantifies their benefit on the actual hardware.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub struct PollStats {
+    polls: u32,
+    wakeups: u32,
+    movements: u32,
+}
+
+impl PollStats {
+    /// Returns the number of completed poll calls.
+    pub fn polls(&self) -> u32 {
+        self.polls
+    }
+
+    /// Returns the number of wakeups.
+    pub fn wakeups(&self) -> u32 {
+        self.wakeups
+    }
+
+    /// Returns the number of reported movements.
+    pub fn movements(&self) -> u32 {
+        self.movements
+    }
+
+    /// Returns the average number of poll calls per reported movement,
+    /// or `None` if no movement has been reported yet.
+    pub fn polls_per_movement(&self) -> Option<f32> {
+        ratio(self.polls, self.movements)
+    }
+
+    /// Returns the average number of wakeups per reported movement,
+    /// or `None` if no movement has been reported yet.
+    pub fn wakeups_per_movement(&self) -> Option<f32> {
+        ratio(self.wakeups, self.movements)
+    }
+
+    /// Resets all counters back to `0`.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub(crate) fn record_wakeup(&mut self) {
+        self.wakeups = self.wakeups.saturating_add(1);
+    }
+
+    pub(crate) fn record_poll(&mut self, is_movement: bool) {
+        self.polls = self.polls.saturating_add(1);
+        self.movements = self.movements.saturating_add(is_movement as u32);
+    }
+
+    /// Awaits `future`, recording a wakeup for every time it gets polled.
+    pub(crate) async fn counted<F>(&mut self, future: F) -> F::Output
+    where
+        F: Future,
+    {
+        let mut future = pin!(future);
+
+        poll_fn(|cx| {
+            self.record_wakeup();
+            future.as_mut().poll(cx)
+        })
+        .await
+    }
+}
+
+fn ratio(count: u32, movements: u32) -> Option<f32> {
+    (movements > 0).then(|| count as f32 / movements as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::task::Poll;
+
+    use embassy_futures::block_on;
+
+    use super::*;
+
+    #[test]
+    fn ratios() {
+        let mut stats = PollStats::default();
+        assert_eq!(stats.polls_per_movement(), None);
+
+        for is_movement in [false, true, false, false, true] {
+            stats.record_wakeup();
+            stats.record_wakeup();
+            stats.record_poll(is_movement);
+        }
+
+        assert_eq!(stats.polls(), 5);
+        assert_eq!(stats.wakeups(), 10);
+        assert_eq!(stats.movements(), 2);
+        assert_eq!(stats.polls_per_movement(), Some(2.5));
+        assert_eq!(stats.wakeups_per_movement(), Some(5.0));
+
+        stats.reset();
+        assert_eq!(stats, PollStats::default());
+    }
+
+    #[test]
+    fn counted() {
+        let mut stats = PollStats::default();
+        let mut pending = 3;
+
+        let future = poll_fn(|cx| match pending {
+            0 => Poll::Ready(42),
+            _ => {
+                pending -= 1;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        });
+
+        assert_eq!(block_on(stats.counted(future)), 42);
+        assert_eq!(stats.wakeups(), 4);
+    }
+}
@@ -0,0 +1,119 @@
+//! Streams of an async encoder's movements.
+
+use futures_util::stream::{unfold, Stream};
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+
+use crate::{
+    Async, Error, IncrementalEncoder, IndexedIncrementalEncoder, OperationMode, PinSource, StepMode,
+};
+
+impl<Mode, Clk, Dt, Steps, T> IncrementalEncoder<Mode, Clk, Dt, Steps, T, Async>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Consumes self, returning a never-ending stream of the encoder's movements
+    /// (e.g. for `select!`-ing over, or for stream combinators).
+    ///
+    /// Polls without any movement get skipped, while errors get yielded as `Err(_)`.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Mode::Movement, Error>> {
+        unfold(self, |mut encoder| async move {
+            let item = encoder.next_movement().await;
+            Some((item, encoder))
+        })
+    }
+
+    /// Returns a never-ending stream of the encoder's movements, like [`into_stream()`](Self::into_stream),
+    /// but borrowing the encoder (e.g. for querying its position once the stream has been dropped).
+    pub fn stream(&mut self) -> impl Stream<Item = Result<Mode::Movement, Error>> + '_ {
+        unfold(self, |encoder| async move {
+            let item = encoder.next_movement().await;
+            Some((item, encoder))
+        })
+    }
+
+    async fn next_movement(&mut self) -> Result<Mode::Movement, Error> {
+        loop {
+            if let Some(movement) = self.poll().await? {
+                return Ok(movement);
+            }
+        }
+    }
+}
+
+impl<Mode, Clk, Dt, Idx, Steps, T> IndexedIncrementalEncoder<Mode, Clk, Dt, Idx, Steps, T, Async>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    Idx: PinSource,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Consumes self, returning a never-ending stream of the encoder's movements
+    /// (e.g. for `select!`-ing over, or for stream combinators).
+    ///
+    /// Polls without any movement get skipped, while errors get yielded as `Err(_)`.
+    pub fn into_stream(self) -> impl Stream<Item = Result<Mode::Movement, Error>> {
+        unfold(self, |mut encoder| async move {
+            let item = encoder.next_movement().await;
+            Some((item, encoder))
+        })
+    }
+
+    /// Returns a never-ending stream of the encoder's movements, like [`into_stream()`](Self::into_stream),
+    /// but borrowing the encoder (e.g. for querying its position once the stream has been dropped).
+    pub fn stream(&mut self) -> impl Stream<Item = Result<Mode::Movement, Error>> + '_ {
+        unfold(self, |encoder| async move {
+            let item = encoder.next_movement().await;
+            Some((item, encoder))
+        })
+    }
+
+    async fn next_movement(&mut self) -> Result<Mode::Movement, Error> {
+        loop {
+            if let Some(movement) = self.poll().await? {
+                return Ok(movement);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::pin::pin;
+
+    use embassy_futures::block_on;
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+    use futures_util::StreamExt;
+
+    use crate::{QuadStep, RotaryEncoder, RotaryMovement};
+
+    #[test]
+    fn stream() {
+        let pin_clk = PinMock::new(&[
+            PinTransaction::get(PinState::High),
+            PinTransaction::wait_for_state(PinState::Low),
+        ]);
+        let pin_dt = PinMock::new(&[PinTransaction::get(PinState::High)]);
+
+        let encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(pin_clk, pin_dt);
+        let mut encoder = encoder.into_async();
+
+        {
+            let mut stream = pin!(encoder.stream());
+            assert_eq!(block_on(stream.next()), Some(Ok(RotaryMovement::Clockwise)));
+        }
+
+        assert_eq!(encoder.position(), 1);
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+}
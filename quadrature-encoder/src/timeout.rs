@@ -0,0 +1,173 @@
+//! Polling of async encoders with timeouts (e.g. for idle detection).
+
+use core::future::Future;
+
+use num_traits::{One, SaturatingAdd, WrappingNeg, Zero};
+
+#[cfg(feature = "embassy-time")]
+use embassy_time::{Duration, Timer};
+
+use crate::{
+    select::{select, Either},
+    Async, Error, IncrementalEncoder, IndexedIncrementalEncoder, OperationMode, PinSource,
+    StepMode,
+};
+
+impl<Mode, Clk, Dt, Steps, T> IncrementalEncoder<Mode, Clk, Dt, Steps, T, Async>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Updates the encoder's state like [`poll()`](Self::poll), unless the given `timeout` future
+    /// completes first (e.g. a timer of the executor at hand), returning `Ok(None)` in that case.
+    ///
+    /// Pin changes awaited in vain (i.e. once the timeout has completed) are picked up by the next poll.
+    pub async fn poll_until(
+        &mut self,
+        timeout: impl Future,
+    ) -> Result<Option<Mode::Movement>, Error> {
+        match select(self.poll(), timeout).await {
+            Either::First(result) => result,
+            Either::Second(_) => Ok(None),
+        }
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), unless no pin changed
+    /// within the given `timeout` (e.g. for screensavers, or menus), returning `Ok(None)` in that case.
+    #[cfg(feature = "embassy-time")]
+    pub async fn poll_with_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<Mode::Movement>, Error> {
+        self.poll_until(Timer::after(timeout)).await
+    }
+}
+
+impl<Mode, Clk, Dt, Idx, Steps, T> IndexedIncrementalEncoder<Mode, Clk, Dt, Idx, Steps, T, Async>
+where
+    Mode: OperationMode,
+    Clk: PinSource,
+    Dt: PinSource,
+    Idx: PinSource,
+    Steps: StepMode,
+    T: Copy + PartialEq + Zero + One + SaturatingAdd + WrappingNeg + From<i8>,
+{
+    /// Updates the encoder's state like [`poll()`](Self::poll), unless the given `timeout` future
+    /// completes first (e.g. a timer of the executor at hand), returning `Ok(None)` in that case.
+    ///
+    /// Pin changes awaited in vain (i.e. once the timeout has completed) are picked up by the next poll.
+    pub async fn poll_until(
+        &mut self,
+        timeout: impl Future,
+    ) -> Result<Option<Mode::Movement>, Error> {
+        match select(self.poll(), timeout).await {
+            Either::First(result) => result,
+            Either::Second(_) => Ok(None),
+        }
+    }
+
+    /// Updates the encoder's state like [`poll()`](Self::poll), unless no pin changed
+    /// within the given `timeout` (e.g. for screensavers, or menus), returning `Ok(None)` in that case.
+    #[cfg(feature = "embassy-time")]
+    pub async fn poll_with_timeout(
+        &mut self,
+        timeout: Duration,
+    ) -> Result<Option<Mode::Movement>, Error> {
+        self.poll_until(Timer::after(timeout)).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::future::pending;
+
+    use embassy_futures::block_on;
+    use embedded_hal_mock::eh1::digital::{
+        Mock as PinMock, State as PinState, Transaction as PinTransaction,
+    };
+
+    use crate::{QuadStep, RotaryEncoder, RotaryMovement};
+
+    #[test]
+    fn poll_until() {
+        let pin_clk = PinMock::new(&[
+            PinTransaction::get(PinState::High),
+            PinTransaction::wait_for_state(PinState::Low),
+        ]);
+        let pin_dt = PinMock::new(&[PinTransaction::get(PinState::High)]);
+
+        let encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(pin_clk, pin_dt);
+        let mut encoder = encoder.into_async();
+
+        assert_eq!(
+            block_on(encoder.poll_until(pending::<()>())),
+            Ok(Some(RotaryMovement::Clockwise))
+        );
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+
+    #[cfg(feature = "embassy-time")]
+    #[test]
+    fn poll_with_timeout() {
+        use core::{pin::pin, task::Poll};
+
+        use embassy_futures::poll_once;
+        use embassy_time::{Duration, MockDriver};
+
+        use crate::mock_time::{self, IdlePin};
+
+        let _clock = mock_time::lock();
+
+        let timeout = Duration::from_secs(30);
+
+        let encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(IdlePin, IdlePin);
+        let mut encoder = encoder.into_async();
+
+        {
+            let mut poll = pin!(encoder.poll_with_timeout(timeout));
+            assert_eq!(poll_once(&mut poll), Poll::Pending);
+
+            MockDriver::get().advance(timeout);
+            assert_eq!(poll_once(&mut poll), Poll::Ready(Ok(None)));
+        }
+
+        assert_eq!(encoder.last_movement_age(), None);
+    }
+
+    #[cfg(feature = "embassy-time")]
+    #[test]
+    fn last_movement_age() {
+        use embassy_time::{Duration, MockDriver};
+
+        let _clock = crate::mock_time::lock();
+
+        let pin_clk = PinMock::new(&[
+            PinTransaction::get(PinState::High),
+            PinTransaction::wait_for_state(PinState::Low),
+        ]);
+        let pin_dt = PinMock::new(&[PinTransaction::get(PinState::High)]);
+
+        let encoder: RotaryEncoder<_, _, QuadStep> = RotaryEncoder::new(pin_clk, pin_dt);
+        let mut encoder = encoder.into_async();
+
+        assert_eq!(encoder.last_movement_age(), None);
+
+        assert_eq!(
+            block_on(encoder.poll_with_timeout(Duration::from_secs(30))),
+            Ok(Some(RotaryMovement::Clockwise))
+        );
+
+        MockDriver::get().advance(Duration::from_secs(5));
+        assert_eq!(encoder.last_movement_age(), Some(Duration::from_secs(5)));
+
+        let (mut pin_clk, mut pin_dt) = encoder.release();
+        pin_clk.done();
+        pin_dt.done();
+    }
+}
@@ -0,0 +1,281 @@
+//! Compact live event traces (e.g. streamed via `defmt` over RTT),
+//! and their host-side reconstruction into position traces.
+//!
+//! With the `defmt` feature enabled, `TraceRecord::emit()` logs records as `defmt` frames,
+//! which only carry the record's raw values (i.e. a few bytes per record), rather than formatted text.
+//! Once decoded on the host (e.g. by `probe-rs`, or `defmt-print`) each record is printed as a single line:
+//!
+//! ```plain
+//! qe:p <timestamp> <position>
+//! qe:m <timestamp> <change>
+//! qe:e <timestamp> <kind> <code>
+//! ```
+//!
+//! The host-side [`PositionTrace`] picks these lines out of the decoded log
+//! (ignoring any other lines, as well as prefixes such as log levels),
+//! reconstructing the encoder's position trace (e.g. for live plotting):
+//!
+//! ```
+//! use quadrature_encoder::trace::PositionTrace;
+//!
+//! let log = "\
+//! INFO qe:p 100 42
+//! INFO qe:m 150 1
+//! INFO some unrelated message
+//! INFO qe:m 175 -1
+//! ";
+//!
+//! let points: Vec<_> = PositionTrace::new().reconstruct(log.lines()).collect();
+//!
+//! assert_eq!(points, [(100, 42), (150, 43), (175, 42)]);
+//! ```
+
+use quadrature_decoder::Change;
+
+use crate::{Error, InputPinError, QuadratureError};
+
+/// The marker preceding every record's line in a decoded log.
+const MARKER: &str = "qe:";
+
+const KIND_QUADRATURE: u8 = 0;
+const KIND_INPUT_PIN: u8 = 1;
+const KIND_OVERFLOW: u8 = 2;
+
+/// An event of a trace.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TraceEvent {
+    /// The encoder's absolute position (e.g. emitted periodically, or after polls).
+    Position(i32),
+    /// A movement of the encoder, relative to the preceding position.
+    Movement(Change),
+    /// An error detected by the encoder.
+    Error(Error),
+}
+
+/// A timestamped event of a trace.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TraceRecord {
+    /// The record's timestamp in ticks, as provided by the caller's clock.
+    pub timestamp: u64,
+    /// The record's event.
+    pub event: TraceEvent,
+}
+
+impl TraceRecord {
+    /// Creates a record of the encoder's absolute `position` at the given `timestamp`.
+    pub const fn position(timestamp: u64, position: i32) -> Self {
+        Self {
+            timestamp,
+            event: TraceEvent::Position(position),
+        }
+    }
+
+    /// Creates a record of a movement in direction of `change` at the given `timestamp`.
+    pub const fn movement(timestamp: u64, change: Change) -> Self {
+        Self {
+            timestamp,
+            event: TraceEvent::Movement(change),
+        }
+    }
+
+    /// Creates a record of an `error` at the given `timestamp`.
+    pub const fn error(timestamp: u64, error: Error) -> Self {
+        Self {
+            timestamp,
+            event: TraceEvent::Error(error),
+        }
+    }
+
+    /// Emits the record as a compact `defmt` frame (e.g. for streaming via RTT).
+    ///
+    /// Positions and movements are logged at `info` level, while errors are logged at `warn` level.
+    #[cfg(feature = "defmt")]
+    pub fn emit(&self) {
+        let timestamp = self.timestamp;
+
+        match self.event {
+            TraceEvent::Position(position) => {
+                defmt::info!("qe:p {=u64} {=i32}", timestamp, position);
+            }
+            TraceEvent::Movement(change) => {
+                defmt::info!("qe:m {=u64} {=i8}", timestamp, change as i8);
+            }
+            TraceEvent::Error(error) => {
+                let (kind, code) = encode_error(error);
+                defmt::warn!("qe:e {=u64} {=u8} {=u8}", timestamp, kind, code);
+            }
+        }
+    }
+
+    /// Parses a record from a line of a decoded log,
+    /// returning `None` if the line does not contain a (valid) record.
+    pub fn parse(line: &str) -> Option<Self> {
+        let (_, record) = line.split_once(MARKER)?;
+        let mut fields = record.split_whitespace();
+
+        let tag = fields.next()?;
+        let timestamp = fields.next()?.parse().ok()?;
+
+        let event = match tag {
+            "p" => TraceEvent::Position(fields.next()?.parse().ok()?),
+            "m" => match fields.next()?.parse::<i8>().ok()? {
+                1 => TraceEvent::Movement(Change::Positive),
+                -1 => TraceEvent::Movement(Change::Negative),
+                _ => return None,
+            },
+            "e" => {
+                let kind = fields.next()?.parse().ok()?;
+                let code = fields.next()?.parse().ok()?;
+                TraceEvent::Error(decode_error(kind, code)?)
+            }
+            _ => return None,
+        };
+
+        Some(Self { timestamp, event })
+    }
+}
+
+/// A host-side reconstruction of an encoder's position trace from its trace records.
+///
+/// Positions are reconstructed relative to `0` until the first position record,
+/// with subsequent position records re-synchronizing the trace (e.g. after dropped frames).
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct PositionTrace {
+    position: i32,
+    errors: u32,
+}
+
+impl PositionTrace {
+    /// Creates a trace, starting at position `0`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the trace's current position.
+    pub fn position(&self) -> i32 {
+        self.position
+    }
+
+    /// Returns the number of error records observed by the trace.
+    pub fn errors(&self) -> u32 {
+        self.errors
+    }
+
+    /// Updates the trace with the given `record`,
+    /// returning the resulting `(timestamp, position)` point,
+    /// or `None` for records not affecting the position (i.e. errors).
+    pub fn update(&mut self, record: TraceRecord) -> Option<(u64, i32)> {
+        match record.event {
+            TraceEvent::Position(position) => self.position = position,
+            TraceEvent::Movement(change) => {
+                self.position = self.position.wrapping_add(change as i32);
+            }
+            TraceEvent::Error(_) => {
+                self.errors = self.errors.saturating_add(1);
+                return None;
+            }
+        }
+
+        Some((record.timestamp, self.position))
+    }
+
+    /// Returns an iterator over the `(timestamp, position)` points reconstructed
+    /// from the given lines of a decoded log, skipping lines without records.
+    pub fn reconstruct<'a, I>(&'a mut self, lines: I) -> impl Iterator<Item = (u64, i32)> + 'a
+    where
+        I: IntoIterator<Item = &'a str>,
+        I::IntoIter: 'a,
+    {
+        lines
+            .into_iter()
+            .filter_map(TraceRecord::parse)
+            .filter_map(|record| self.update(record))
+    }
+}
+
+#[cfg(feature = "defmt")]
+fn encode_error(error: Error) -> (u8, u8) {
+    match error {
+        Error::Quadrature(error) => (KIND_QUADRATURE, error as u8),
+        Error::InputPin(error) => (KIND_INPUT_PIN, error as u8),
+        Error::Overflow => (KIND_OVERFLOW, 0),
+    }
+}
+
+fn decode_error(kind: u8, code: u8) -> Option<Error> {
+    match kind {
+        KIND_QUADRATURE => [
+            QuadratureError::E00_11,
+            QuadratureError::E11_00,
+            QuadratureError::E01_10,
+            QuadratureError::E10_01,
+        ]
+        .into_iter()
+        .find(|&error| error as u8 == code)
+        .map(Error::Quadrature),
+        KIND_INPUT_PIN => [
+            InputPinError::PinClk,
+            InputPinError::PinDt,
+            InputPinError::PinIdx,
+        ]
+        .into_iter()
+        .find(|&error| error as u8 == code)
+        .map(Error::InputPin),
+        KIND_OVERFLOW => Some(Error::Overflow),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        assert_eq!(
+            TraceRecord::parse("0.000100 INFO  qe:p 100 -42"),
+            Some(TraceRecord::position(100, -42))
+        );
+        assert_eq!(
+            TraceRecord::parse("qe:m 150 -1"),
+            Some(TraceRecord::movement(150, Change::Negative))
+        );
+        assert_eq!(
+            TraceRecord::parse("WARN qe:e 175 0 3"),
+            Some(TraceRecord::error(
+                175,
+                Error::Quadrature(QuadratureError::E00_11)
+            ))
+        );
+        assert_eq!(
+            TraceRecord::parse("qe:e 175 1 2"),
+            Some(TraceRecord::error(
+                175,
+                Error::InputPin(InputPinError::PinIdx)
+            ))
+        );
+
+        assert_eq!(TraceRecord::parse("INFO unrelated"), None);
+        assert_eq!(TraceRecord::parse("qe:m 150 2"), None);
+        assert_eq!(TraceRecord::parse("qe:e 175 3 0"), None);
+        assert_eq!(TraceRecord::parse("qe:p 100"), None);
+    }
+
+    #[test]
+    fn reconstruct() {
+        let log = [
+            "qe:m 10 1",
+            "qe:m 20 1",
+            "qe:e 25 0 12",
+            "qe:p 30 100",
+            "qe:m 40 -1",
+        ];
+
+        let mut trace = PositionTrace::new();
+        let points: Vec<_> = trace.reconstruct(log).collect();
+
+        assert_eq!(points, [(10, 1), (20, 2), (30, 100), (40, 99)]);
+        assert_eq!(trace.position(), 99);
+        assert_eq!(trace.errors(), 1);
+    }
+}